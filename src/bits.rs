@@ -0,0 +1,236 @@
+//! Runs of bits in packed bitmaps.
+//!
+//! Use [`bit_runs`] to find the index ranges of the runs of set or cleared bits in a bitmap that is given as an iterator of words:
+//!
+//! ```
+//! use spans::bits;
+//!
+//! let bitmap: Vec<u8> = vec![0b1111_0110, 0b0000_0001];
+//! let runs: Vec<_> = bits::bit_runs(bitmap, true).collect();
+//!
+//! assert_eq!(runs, vec![1..3, 4..9]);
+//! ```
+//!
+//! Bit `i` of word `k` has index `k * W + i`, where `W` is the number of bits of a word, such that the least significant bit comes first.
+//! Runs are found a word at a time by counting trailing zeros and ones, instead of testing each bit.
+
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+/// `BitWord` provides the bit operations of primitive unsigned integer types used by [`bit_runs`].
+///
+/// `BitWord` is implemented for all primitive unsigned integer types.
+pub trait BitWord: Copy {
+    /// The number of bits of the word.
+    const BITS: u32;
+
+    /// Returns the number of trailing zero bits.
+    fn trailing_zeros(self) -> u32;
+
+    /// Returns the number of trailing one bits.
+    fn trailing_ones(self) -> u32;
+
+    /// Returns the word with all bits inverted.
+    fn invert(self) -> Self;
+
+    /// Returns the word shifted right by `n` bits, where `n` is less than [`BitWord::BITS`].
+    fn shift_right(self, n: u32) -> Self;
+
+    /// Returns whether no bit is set.
+    fn is_zero(self) -> bool;
+}
+
+macro_rules! impl_bit_word {
+    ($($t:ty),*) => {
+        $(
+            impl BitWord for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                fn trailing_zeros(self) -> u32 {
+                    <$t>::trailing_zeros(self)
+                }
+
+                fn trailing_ones(self) -> u32 {
+                    <$t>::trailing_ones(self)
+                }
+
+                fn invert(self) -> Self {
+                    !self
+                }
+
+                fn shift_right(self, n: u32) -> Self {
+                    self >> n
+                }
+
+                fn is_zero(self) -> bool {
+                    self == 0
+                }
+            }
+        )*
+    };
+}
+
+impl_bit_word!(u8, u16, u32, u64, u128, usize);
+
+/// Returns the index ranges of the runs of bits equal to `value` in the bitmap given by `words`.
+///
+/// Runs continue across words.
+/// The bits of the last word are all part of the bitmap; a run of cleared bits thus ends at the end of the last word.
+///
+/// # Example
+///
+/// ```
+/// use spans::bits;
+///
+/// let bitmap: Vec<u32> = vec![u32::MAX, 0xFFFF_0000];
+/// let set: Vec<_> = bits::bit_runs(bitmap.iter().copied(), true).collect();
+/// let cleared: Vec<_> = bits::bit_runs(bitmap, false).collect();
+///
+/// assert_eq!(set, vec![0..32, 48..64]);
+/// assert_eq!(cleared, vec![32..48]);
+/// ```
+pub fn bit_runs<W, T>(words: W, value: bool) -> BitRuns<W::IntoIter, T>
+where
+    W: IntoIterator<Item = T>,
+    T: BitWord,
+{
+    BitRuns {
+        words: words.into_iter(),
+        value,
+        word: None,
+        remaining: 0,
+        index: 0,
+        start: None,
+    }
+}
+
+/// `BitRuns` is an iterator over the index ranges of the runs of set or cleared bits of a bitmap.
+///
+/// See [`bit_runs`] for more information.
+#[derive(Clone)]
+pub struct BitRuns<I, T> {
+    /// The words of the bitmap.
+    words: I,
+    /// Whether runs of set bits are yielded, rather than runs of cleared bits.
+    value: bool,
+    /// The bits of the current word that have not been visited yet, shifted to the least significant bits, and inverted if `value` is `false`.
+    word: Option<T>,
+    /// The number of bits of `word` that have not been visited yet.
+    remaining: u32,
+    /// The index of the next bit.
+    index: usize,
+    /// The start of the current run, if the previous bit is part of it.
+    start: Option<usize>,
+}
+
+impl<I, T> BitRuns<I, T>
+where
+    I: Iterator<Item = T>,
+    T: BitWord,
+{
+    /// Skips `n` bits of the current word.
+    fn advance(&mut self, word: T, n: u32) {
+        self.index += n as usize;
+        self.remaining -= n;
+        self.word = if self.remaining == 0 {
+            None
+        } else {
+            Some(word.shift_right(n))
+        };
+    }
+}
+
+impl<I, T> Iterator for BitRuns<I, T>
+where
+    I: Iterator<Item = T>,
+    T: BitWord,
+{
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let word = match self.word {
+                Some(word) => word,
+                None => match self.words.next() {
+                    Some(word) => {
+                        self.remaining = T::BITS;
+                        if self.value {
+                            word
+                        } else {
+                            word.invert()
+                        }
+                    }
+                    None => {
+                        let start = self.start.take()?;
+                        return Some(start..self.index);
+                    }
+                },
+            };
+
+            match self.start {
+                None if word.is_zero() => self.advance(word, self.remaining),
+                None => {
+                    self.advance(word, word.trailing_zeros());
+                    self.start = Some(self.index);
+                }
+                Some(start) => {
+                    // Shifted-in bits are zero, such that the count never exceeds the remaining bits.
+                    let ones = word.trailing_ones();
+                    self.advance(word, ones);
+
+                    if self.remaining > 0 {
+                        self.start = None;
+                        return Some(start..self.index);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<I, T> FusedIterator for BitRuns<I, T>
+where
+    I: FusedIterator<Item = T>,
+    T: BitWord,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ranges;
+
+    /// Returns the bits of `words`, least significant bit first.
+    fn mask(words: &[u16]) -> Vec<bool> {
+        words
+            .iter()
+            .flat_map(|&word| (0..16).map(move |i| word >> i & 1 == 1))
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_mask() {
+        let samples: &[&[u16]] = &[
+            &[],
+            &[0],
+            &[u16::MAX],
+            &[0x8001, 0x0003, 0xFF00],
+            &[0xF0F0, 0, 0, 0x0FFF],
+        ];
+
+        for &words in samples {
+            let mask = mask(words);
+            let set = super::bit_runs(words.iter().copied(), true).collect::<Vec<_>>();
+            let cleared = super::bit_runs(words.iter().copied(), false).collect::<Vec<_>>();
+            let not_mask = mask.iter().map(|bit| !bit).collect::<Vec<_>>();
+
+            assert_eq!(set, ranges::mask_to_spans(mask).collect::<Vec<_>>());
+            assert_eq!(cleared, ranges::mask_to_spans(not_mask).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_wide_words() {
+        let runs = super::bit_runs(vec![u128::MAX << 1, u128::MAX], true).collect::<Vec<_>>();
+        assert_eq!(runs, vec![1..256]);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{Connector, SpansBy};
+use core::iter::FusedIterator;
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Converts `self` into an iterator over the first and the last item of each span.
+    ///
+    /// The returned [`SpanBounds`] yields a pair for every span.
+    /// The first element of the pair is the first item of the span.
+    /// The second element is the last item of the span, or `None` if the span consists of a single item.
+    /// Items between the first and the last item of a span are discarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 3, 7, 10, 11];
+    /// let bounds: Vec<_> = vec
+    ///     .into_iter()
+    ///     .spans_by_key(|&x| x, |a, b| a + 1 == b)
+    ///     .span_bounds()
+    ///     .collect();
+    ///
+    /// assert_eq!(bounds, vec![(1, Some(3)), (7, None), (10, Some(11))]);
+    /// ```
+    pub fn span_bounds(self) -> SpanBounds<I, F> {
+        SpanBounds { spans: self }
+    }
+}
+
+/// `SpanBounds` is an iterator over the first and the last item of each span of a `SpansBy`.
+///
+/// See [`SpansBy::span_bounds`] for more information.
+pub struct SpanBounds<I: Iterator, F> {
+    /// The wrapped spans.
+    spans: SpansBy<I, F>,
+}
+
+impl<I, F> Iterator for SpanBounds<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    type Item = (I::Item, Option<I::Item>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut span = self.spans.next()?;
+        let first = span.next()?;
+        Some((first, span.last()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.span_count_hint()
+    }
+}
+
+impl<I, F> FusedIterator for SpanBounds<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_empty() {
+        let mut bounds = [].iter().spans_by(|&&a: &&u8, &&b| a == b).span_bounds();
+        assert_eq!(bounds.next(), None);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let bounds = "aaabcc"
+            .chars()
+            .spans_by(|a, b| a == b)
+            .span_bounds()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            bounds,
+            vec![('a', Some('a')), ('b', None), ('c', Some('c'))]
+        );
+    }
+}
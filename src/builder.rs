@@ -0,0 +1,180 @@
+use crate::{are_equal, ByKey, ByKeyChange, Connector};
+use alloc::vec::Vec;
+use core::mem;
+
+/// `SpanBuilder` splits items that are pushed one at a time into contiguous spans.
+///
+/// This is the push-based counterpart of [`Spans`][crate::Spans] for items that are not available as an [`Iterator`], such as items received in callbacks.
+/// A span is completed when an item is pushed that is not connected to the last item of the span, or when [`finish`][SpanBuilder::finish] is called.
+/// The connector is called with the same pairs of items in the same order as by the pull-based adapters.
+///
+/// # Example
+///
+/// ```
+/// use spans::SpanBuilder;
+///
+/// let mut builder = SpanBuilder::by_key(|&x: &i32| x, |a, b| a + 1 == b);
+/// let mut spans = Vec::new();
+///
+/// for x in vec![1, 2, 5, 6, 7, 11] {
+///     spans.extend(builder.push(x));
+/// }
+/// spans.extend(builder.finish());
+///
+/// assert_eq!(spans, vec![vec![1, 2], vec![5, 6, 7], vec![11]]);
+/// ```
+#[derive(Clone)]
+pub struct SpanBuilder<T, F> {
+    /// Whether two adjacent items belong to the same span.
+    connector: F,
+    /// The items of the current span.
+    span: Vec<T>,
+}
+
+impl<T, F> SpanBuilder<T, F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    /// Creates a builder that compares adjacent items.
+    ///
+    /// This is the push-based counterpart of [`Spans::spans_by`][crate::Spans::spans_by].
+    pub fn new(are_connected: F) -> Self {
+        SpanBuilder::with_connector(are_connected)
+    }
+}
+
+impl<T, K, C, F> SpanBuilder<T, ByKey<K, C, F>>
+where
+    K: FnMut(&T) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    /// Creates a builder that compares the keys of adjacent items.
+    ///
+    /// This is the push-based counterpart of [`Spans::spans_by_key`][crate::Spans::spans_by_key].
+    pub fn by_key(key: K, are_connected: F) -> Self {
+        SpanBuilder::with_connector(ByKey::new(key, are_connected))
+    }
+}
+
+impl<T, K, C> SpanBuilder<T, ByKeyChange<K, C>>
+where
+    K: FnMut(&T) -> C,
+    C: Clone + PartialEq,
+{
+    /// Creates a builder that groups adjacent items with equal keys.
+    ///
+    /// This is the push-based counterpart of [`Spans::spans_by_key_change`][crate::Spans::spans_by_key_change].
+    pub fn by_key_change(key: K) -> Self {
+        SpanBuilder::by_key(key, are_equal)
+    }
+}
+
+impl<T, F> SpanBuilder<T, F>
+where
+    F: Connector<T>,
+{
+    /// Creates a builder with the given connector.
+    pub fn with_connector(connector: F) -> Self {
+        SpanBuilder {
+            connector,
+            span: Vec::new(),
+        }
+    }
+
+    /// Adds `item` to the current span.
+    ///
+    /// If `item` is not connected to the last item of the current span, the current span is completed and returned, and `item` starts a new span.
+    pub fn push(&mut self, item: T) -> Option<Vec<T>> {
+        let completed = match self.span.last() {
+            Some(last) if self.connector.are_connected(last, &item) => None,
+            Some(_) => Some(mem::take(&mut self.span)),
+            None => None,
+        };
+
+        if self.span.is_empty() {
+            self.connector.start_span(&item);
+        }
+
+        self.span.push(item);
+        completed
+    }
+
+    /// Completes and returns the current span, or returns `None` if no item has been pushed since the last span was completed.
+    ///
+    /// The next pushed item starts a new span that is not compared to the items pushed before.
+    pub fn finish(&mut self) -> Option<Vec<T>> {
+        self.connector.reset();
+
+        if self.span.is_empty() {
+            None
+        } else {
+            Some(mem::take(&mut self.span))
+        }
+    }
+
+    /// Returns the items of the current span.
+    pub fn current_span(&self) -> &[T] {
+        &self.span
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SpanBuilder;
+    use crate::Spans;
+
+    /// Pushes all items into `builder` and collects the completed spans.
+    fn push_all<F>(builder: &mut SpanBuilder<i32, F>, items: &[i32]) -> Vec<Vec<i32>>
+    where
+        F: crate::Connector<i32>,
+    {
+        let mut spans = items
+            .iter()
+            .filter_map(|&x| builder.push(x))
+            .collect::<Vec<_>>();
+        spans.extend(builder.finish());
+        spans
+    }
+
+    #[test]
+    fn test_matches_pull_api() {
+        let inputs: &[&[i32]] = &[&[], &[1], &[1, 2, 5, 6, 7, 11], &[3, 3, 1, 1, 1, 3]];
+
+        for &items in inputs {
+            let pulled = items
+                .iter()
+                .copied()
+                .spans_by_key(|&x| x, |a, b| a + 1 == b)
+                .collect_spans();
+            let mut builder = SpanBuilder::by_key(|&x: &i32| x, |a, b| a + 1 == b);
+            assert_eq!(push_all(&mut builder, items), pulled);
+
+            let pulled = items
+                .iter()
+                .copied()
+                .spans_by_key_change(|&x| x)
+                .collect_spans();
+            let mut builder = SpanBuilder::by_key_change(|&x: &i32| x);
+            assert_eq!(push_all(&mut builder, items), pulled);
+        }
+    }
+
+    #[test]
+    fn test_finish_resets() {
+        let mut builder = SpanBuilder::new(|a: &i32, b: &i32| a + 1 == *b);
+        assert_eq!(push_all(&mut builder, &[1, 2]), vec![vec![1, 2]]);
+        assert_eq!(push_all(&mut builder, &[3, 4]), vec![vec![3, 4]]);
+        assert!(builder.finish().is_none());
+    }
+
+    #[test]
+    fn test_current_span() {
+        let mut builder = SpanBuilder::by_key_change(|&x: &i32| x % 2);
+        assert!(builder.push(1).is_none());
+        assert!(builder.push(3).is_none());
+        assert_eq!(builder.current_span(), &[1, 3]);
+        assert_eq!(builder.push(4), Some(vec![1, 3]));
+        assert_eq!(builder.current_span(), &[4]);
+    }
+}
@@ -0,0 +1,505 @@
+//! Spans of byte slices.
+//!
+//! Import the [`ByteSpans`] trait to split byte slices into subslices with simple predicates:
+//!
+//! ```
+//! use spans::byte::{ByteClass, ByteSpans};
+//!
+//! let digits = ByteClass::from_fn(|b| b.is_ascii_digit());
+//! let spans: Vec<&[u8]> = b"id=42;n=7".spans_by_byte_class(digits).collect();
+//!
+//! assert_eq!(spans, vec![&b"id="[..], b"42", b";n=", b"7"]);
+//! ```
+//!
+//! Unlike the general adapters of [`SliceSpans`][crate::slice::SliceSpans], which compare one pair of bytes at a time, these adapters search for the end of a span in bulk.
+//! [`ByteSpans::byte_runs`] compares eight bytes at once.
+//! With the `memchr` feature, [`ByteSpans::spans_by_byte_class`] uses [`memchr`](https://docs.rs/memchr) to search for the end of a span if the byte class or its complement has at most three bytes.
+
+use core::iter::FusedIterator;
+
+/// `ByteSpans` provides accelerated span adapters for byte slices.
+pub trait ByteSpans {
+    /// Splits the byte slice into runs of equal bytes.
+    ///
+    /// This is like [`SliceSpans::spans_by`][crate::slice::SliceSpans::spans_by] with `|a, b| a == b`, but faster for long runs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::byte::ByteSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let mut runs = b"aaaab  ".byte_runs();
+    ///
+    /// assert_eq!(runs.next()?, b"aaaa");
+    /// assert_eq!(runs.next()?, b"b");
+    /// assert_eq!(runs.next()?, b"  ");
+    /// assert!(runs.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn byte_runs(&self) -> ByteRuns<'_>;
+
+    /// Splits the byte slice into spans of bytes that are either all members or all non-members of `class`.
+    ///
+    /// This is like [`SliceSpans::spans_by_key`][crate::slice::SliceSpans::spans_by_key] with `|&b| class.contains(b)` as the key and `|a, b| a == b` as the connector, but faster for long spans.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::byte::{ByteClass, ByteSpans};
+    /// # fn test() -> Option<()> {
+    ///
+    /// let mut lines = b"GET /\r\n\r\nbody".spans_by_byte_class(ByteClass::from_bytes(b"\r\n"));
+    ///
+    /// assert_eq!(lines.next()?, b"GET /");
+    /// assert_eq!(lines.next()?, b"\r\n\r\n");
+    /// assert_eq!(lines.next()?, b"body");
+    /// assert!(lines.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by_byte_class(&self, class: ByteClass) -> ByteClassSpans<'_>;
+
+    /// Splits the byte slice into runs of bytes of the same [`AsciiClass`].
+    ///
+    /// Each run is yielded together with its class.
+    /// This is a common first step of tokenizers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::byte::{AsciiClass, ByteSpans};
+    ///
+    /// let runs: Vec<_> = b"x1 = 42;".ascii_runs().collect();
+    ///
+    /// assert_eq!(
+    ///     runs,
+    ///     vec![
+    ///         (AsciiClass::Alpha, &b"x"[..]),
+    ///         (AsciiClass::Digit, b"1"),
+    ///         (AsciiClass::Whitespace, b" "),
+    ///         (AsciiClass::Other, b"="),
+    ///         (AsciiClass::Whitespace, b" "),
+    ///         (AsciiClass::Digit, b"42"),
+    ///         (AsciiClass::Other, b";"),
+    ///     ]
+    /// );
+    /// ```
+    fn ascii_runs(&self) -> AsciiRuns<'_>;
+}
+
+impl ByteSpans for [u8] {
+    fn byte_runs(&self) -> ByteRuns<'_> {
+        ByteRuns { bytes: self }
+    }
+
+    fn spans_by_byte_class(&self, class: ByteClass) -> ByteClassSpans<'_> {
+        ByteClassSpans {
+            bytes: self,
+            finder: ByteClassFinder::new(class),
+        }
+    }
+
+    fn ascii_runs(&self) -> AsciiRuns<'_> {
+        AsciiRuns { bytes: self }
+    }
+}
+
+/// A set of bytes.
+///
+/// See [`ByteSpans::spans_by_byte_class`] for more information.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ByteClass {
+    /// The members of the set, where bit `b % 64` of word `b / 64` is set if `b` is a member.
+    bits: [u64; 4],
+}
+
+impl ByteClass {
+    /// Creates an empty byte class.
+    pub const fn new() -> Self {
+        ByteClass { bits: [0; 4] }
+    }
+
+    /// Creates a byte class of the given bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut class = ByteClass::new();
+        for &byte in bytes {
+            class.insert(byte);
+        }
+        class
+    }
+
+    /// Creates a byte class of the bytes for which `is_member` returns `true`.
+    ///
+    /// `is_member` is called once with each byte.
+    pub fn from_fn<P>(mut is_member: P) -> Self
+    where
+        P: FnMut(u8) -> bool,
+    {
+        let mut class = ByteClass::new();
+        for byte in 0..=u8::MAX {
+            if is_member(byte) {
+                class.insert(byte);
+            }
+        }
+        class
+    }
+
+    /// Adds `byte` to the class.
+    pub fn insert(&mut self, byte: u8) {
+        self.bits[usize::from(byte / 64)] |= 1 << (byte % 64);
+    }
+
+    /// Returns whether `byte` is a member of the class.
+    pub fn contains(&self, byte: u8) -> bool {
+        self.bits[usize::from(byte / 64)] & (1 << (byte % 64)) != 0
+    }
+
+    /// Returns the number of bytes in the class.
+    pub fn len(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns whether the class has no members.
+    pub fn is_empty(&self) -> bool {
+        self.bits == [0; 4]
+    }
+
+    /// Returns the class of all bytes that are not members of `self`.
+    pub fn complement(&self) -> Self {
+        let [a, b, c, d] = self.bits;
+        ByteClass {
+            bits: [!a, !b, !c, !d],
+        }
+    }
+}
+
+/// `ByteClassFinder` finds the ends of the spans of a byte class.
+///
+/// With the `memchr` feature, the needles of the class and of its complement are computed once, when the finder is created.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ByteClassFinder {
+    /// The byte class that determines the spans.
+    class: ByteClass,
+    /// The needles for the members of the class.
+    #[cfg(feature = "memchr")]
+    members: Needles,
+    /// The needles for the non-members of the class.
+    #[cfg(feature = "memchr")]
+    non_members: Needles,
+}
+
+impl ByteClassFinder {
+    /// Creates a finder for the spans of `class`.
+    pub(crate) fn new(class: ByteClass) -> Self {
+        ByteClassFinder {
+            class,
+            #[cfg(feature = "memchr")]
+            members: Needles::of(&class),
+            #[cfg(feature = "memchr")]
+            non_members: Needles::of(&class.complement()),
+        }
+    }
+
+    /// Returns the length of the span of bytes that are either all members or all non-members of the class at the start of `bytes`.
+    pub(crate) fn span_len(&self, bytes: &[u8]) -> usize {
+        let (&first, rest) = match bytes.split_first() {
+            Some(split) => split,
+            None => return 0,
+        };
+
+        // The span ends at the first byte that is in the other class.
+        let is_member = self.class.contains(first);
+        self.find(!is_member, rest)
+            .map_or(bytes.len(), |index| 1 + index)
+    }
+
+    /// Returns the index of the first byte in `bytes` that is a member of the class if `is_member` is `true`, or a non-member otherwise.
+    #[cfg(feature = "memchr")]
+    fn find(&self, is_member: bool, bytes: &[u8]) -> Option<usize> {
+        let needles = if is_member {
+            self.members
+        } else {
+            self.non_members
+        };
+
+        match needles {
+            Needles::None => None,
+            Needles::One(a) => memchr::memchr(a, bytes),
+            Needles::Two(a, b) => memchr::memchr2(a, b, bytes),
+            Needles::Three(a, b, c) => memchr::memchr3(a, b, c, bytes),
+            Needles::Many => bytes
+                .iter()
+                .position(|&byte| self.class.contains(byte) == is_member),
+        }
+    }
+
+    /// Returns the index of the first byte in `bytes` that is a member of the class if `is_member` is `true`, or a non-member otherwise.
+    #[cfg(not(feature = "memchr"))]
+    fn find(&self, is_member: bool, bytes: &[u8]) -> Option<usize> {
+        bytes
+            .iter()
+            .position(|&byte| self.class.contains(byte) == is_member)
+    }
+}
+
+/// The members of a byte class as needles for `memchr`.
+#[cfg(feature = "memchr")]
+#[derive(Clone, Copy, Debug)]
+enum Needles {
+    /// The class has no members.
+    None,
+    /// The class has one member.
+    One(u8),
+    /// The class has two members.
+    Two(u8, u8),
+    /// The class has three members.
+    Three(u8, u8, u8),
+    /// The class has more than three members, which are searched for without `memchr`.
+    Many,
+}
+
+#[cfg(feature = "memchr")]
+impl Needles {
+    /// Returns the needles for the members of `class`.
+    fn of(class: &ByteClass) -> Self {
+        let mut members = (0..=u8::MAX).filter(|&byte| class.contains(byte));
+        match (
+            members.next(),
+            members.next(),
+            members.next(),
+            members.next(),
+        ) {
+            (None, ..) => Needles::None,
+            (Some(a), None, ..) => Needles::One(a),
+            (Some(a), Some(b), None, _) => Needles::Two(a, b),
+            (Some(a), Some(b), Some(c), None) => Needles::Three(a, b, c),
+            _ => Needles::Many,
+        }
+    }
+}
+
+/// The class of a byte, as used by [`ByteSpans::ascii_runs`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AsciiClass {
+    /// An ASCII decimal digit, `0` to `9`.
+    Digit,
+    /// An ASCII letter, `A` to `Z` or `a` to `z`.
+    Alpha,
+    /// An ASCII whitespace byte, as defined by [`u8::is_ascii_whitespace`].
+    Whitespace,
+    /// Any other byte, including all non-ASCII bytes.
+    Other,
+}
+
+impl AsciiClass {
+    /// Returns the class of `byte`.
+    pub fn of(byte: u8) -> Self {
+        if byte.is_ascii_digit() {
+            AsciiClass::Digit
+        } else if byte.is_ascii_alphabetic() {
+            AsciiClass::Alpha
+        } else if byte.is_ascii_whitespace() {
+            AsciiClass::Whitespace
+        } else {
+            AsciiClass::Other
+        }
+    }
+
+    /// Returns the length of the run of bytes of class `self` at the start of `bytes`.
+    pub(crate) fn run_len(self, bytes: &[u8]) -> usize {
+        bytes
+            .iter()
+            .position(|&byte| AsciiClass::of(byte) != self)
+            .unwrap_or(bytes.len())
+    }
+}
+
+/// Returns the length of the run of equal bytes at the start of `bytes`.
+///
+/// The bytes are compared eight at a time by XOR-ing them with the first byte, such that the first non-zero byte of the result marks the end of the run.
+pub(crate) fn run_len(bytes: &[u8]) -> usize {
+    let first = match bytes.first() {
+        Some(&first) => first,
+        None => return 0,
+    };
+    let pattern = u64::from_le_bytes([first; 8]);
+
+    let mut chunks = bytes.chunks_exact(8);
+    let mut len = 0;
+
+    for chunk in &mut chunks {
+        let mut word = [0; 8];
+        word.copy_from_slice(chunk);
+        let diff = u64::from_le_bytes(word) ^ pattern;
+
+        if diff != 0 {
+            // In little-endian order, the first byte of the chunk is the least significant byte of the word.
+            return len + (diff.trailing_zeros() / 8) as usize;
+        }
+
+        len += 8;
+    }
+
+    len + chunks
+        .remainder()
+        .iter()
+        .take_while(|&&byte| byte == first)
+        .count()
+}
+
+/// `ByteRuns` is an iterator over the runs of equal bytes of a byte slice.
+///
+/// See [`ByteSpans::byte_runs`] for more information.
+#[derive(Clone)]
+pub struct ByteRuns<'a> {
+    /// The bytes that are not part of any returned run yet.
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for ByteRuns<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let (run, rest) = self.bytes.split_at(run_len(self.bytes));
+        self.bytes = rest;
+        Some(run)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.bytes.len().min(1), Some(self.bytes.len()))
+    }
+}
+
+impl FusedIterator for ByteRuns<'_> {}
+
+/// `ByteClassSpans` is an iterator over the spans of a byte slice split by membership in a byte class.
+///
+/// See [`ByteSpans::spans_by_byte_class`] for more information.
+#[derive(Clone)]
+pub struct ByteClassSpans<'a> {
+    /// The bytes that are not part of any returned span yet.
+    bytes: &'a [u8],
+    /// The finder for the byte class that determines the spans.
+    finder: ByteClassFinder,
+}
+
+impl<'a> Iterator for ByteClassSpans<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let (span, rest) = self.bytes.split_at(self.finder.span_len(self.bytes));
+        self.bytes = rest;
+        Some(span)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.bytes.len().min(1), Some(self.bytes.len()))
+    }
+}
+
+impl FusedIterator for ByteClassSpans<'_> {}
+
+/// `AsciiRuns` is an iterator over the runs of bytes of the same ASCII class of a byte slice.
+///
+/// See [`ByteSpans::ascii_runs`] for more information.
+#[derive(Clone)]
+pub struct AsciiRuns<'a> {
+    /// The bytes that are not part of any returned run yet.
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for AsciiRuns<'a> {
+    type Item = (AsciiClass, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let class = AsciiClass::of(*self.bytes.first()?);
+        let (run, rest) = self.bytes.split_at(class.run_len(self.bytes));
+        self.bytes = rest;
+        Some((class, run))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.bytes.len().min(1), Some(self.bytes.len()))
+    }
+}
+
+impl FusedIterator for AsciiRuns<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsciiClass, ByteClass, ByteSpans};
+    use crate::slice::SliceSpans;
+
+    /// Bytes with runs of various lengths, including runs longer than a word.
+    fn bytes() -> Vec<u8> {
+        (0..40u8)
+            .flat_map(|n| core::iter::repeat_n(n % 5 * 51, usize::from(n % 19)))
+            .collect()
+    }
+
+    #[test]
+    fn test_byte_runs() {
+        let bytes = bytes();
+        let expected = bytes.spans_by(|a, b| a == b).collect::<Vec<_>>();
+        assert_eq!(bytes.byte_runs().collect::<Vec<_>>(), expected);
+        assert!([].byte_runs().next().is_none());
+    }
+
+    #[test]
+    fn test_byte_class_spans() {
+        let bytes = bytes();
+
+        for class in &[
+            ByteClass::new(),
+            ByteClass::from_bytes(&[0]),
+            ByteClass::from_bytes(&[0, 102]),
+            ByteClass::from_bytes(&[0, 102, 255]),
+            ByteClass::from_bytes(&[0, 51, 102, 153]),
+            ByteClass::from_bytes(&[0, 51]).complement(),
+        ] {
+            let expected = bytes
+                .spans_by_key(|&b| class.contains(b), |a, b| a == b)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                bytes.spans_by_byte_class(*class).collect::<Vec<_>>(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_byte_class() {
+        let class = ByteClass::from_fn(|b| b.is_ascii_digit());
+        assert_eq!(class.len(), 10);
+        assert!(class.contains(b'0') && !class.contains(b'a'));
+        assert_eq!(class.complement().len(), 246);
+        assert!(ByteClass::new().is_empty());
+    }
+
+    #[test]
+    fn test_ascii_runs() {
+        let bytes = "Grüße, 2024!\t\n".as_bytes();
+        let expected = bytes
+            .spans_by_key(|&b| AsciiClass::of(b), |a, b| a == b)
+            .map(|span| (AsciiClass::of(span[0]), span))
+            .collect::<Vec<_>>();
+        assert_eq!(bytes.ascii_runs().collect::<Vec<_>>(), expected);
+        assert_eq!(expected[1], (AsciiClass::Other, "üß".as_bytes()));
+        assert!([].ascii_runs().next().is_none());
+    }
+}
@@ -0,0 +1,399 @@
+//! Spans of [`bytes`] buffers.
+//!
+//! Import the [`BytesSpans`] trait to split a [`Bytes`] or [`BytesMut`] buffer into spans that share the memory of the buffer:
+//!
+//! ```
+//! use bytes::Bytes;
+//! use spans::bytes::BytesSpans;
+//!
+//! let buffer = Bytes::from_static(b"HEAD\r\nbody");
+//! let frames: Vec<Bytes> = buffer
+//!     .into_spans_by_key_change(|&byte| byte == b'\r' || byte == b'\n')
+//!     .collect();
+//!
+//! assert_eq!(frames, vec!["HEAD", "\r\n", "body"]);
+//! ```
+//!
+//! Each span is split off the buffer without copying.
+//! The spans of a `Bytes` buffer are reference-counted views of the same memory; the spans of a `BytesMut` buffer are unique views, which can be mutated or frozen.
+//! The spans are found like those of [`SliceSpans`][crate::slice::SliceSpans].
+
+use crate::byte::{self, AsciiClass, ByteClass, ByteClassFinder};
+use crate::{are_equal, ByKey, ByKeyChange, Connector};
+use bytes::{Bytes, BytesMut};
+use core::iter::FusedIterator;
+
+/// `BytesSpans` provides span adapters for `bytes` buffers that yield zero-copy views of the buffer.
+pub trait BytesSpans: Sized {
+    /// Splits the buffer into contiguous spans by comparing adjacent bytes.
+    ///
+    /// This is like [`SliceSpans::spans_by`][crate::slice::SliceSpans::spans_by], but yields views of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use spans::bytes::BytesSpans;
+    ///
+    /// let runs: Vec<Bytes> = Bytes::from_static(b"aaab").into_spans_by(|a, b| a == b).collect();
+    ///
+    /// assert_eq!(runs, vec!["aaa", "b"]);
+    /// ```
+    fn into_spans_by<F>(self, are_connected: F) -> IntoBytesSpans<Self, F>
+    where
+        F: FnMut(&u8, &u8) -> bool;
+
+    /// Splits the buffer into contiguous spans by comparing the keys of adjacent bytes.
+    ///
+    /// This is like [`SliceSpans::spans_by_key`][crate::slice::SliceSpans::spans_by_key], but yields views of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    /// use spans::bytes::BytesSpans;
+    ///
+    /// let buffer = BytesMut::from(&b"abdxy"[..]);
+    /// let spans: Vec<_> = buffer
+    ///     .into_spans_by_key(|&byte| byte, |a, b| a + 1 == b)
+    ///     .map(BytesMut::freeze)
+    ///     .collect();
+    ///
+    /// assert_eq!(spans, vec!["ab", "d", "xy"]);
+    /// ```
+    fn into_spans_by_key<K, C, F>(
+        self,
+        key: K,
+        are_connected: F,
+    ) -> IntoBytesSpans<Self, ByKey<K, C, F>>
+    where
+        K: FnMut(&u8) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool;
+
+    /// Splits the buffer into contiguous spans of bytes with equal keys.
+    ///
+    /// This is like [`Spans::spans_by_key_change`][crate::Spans::spans_by_key_change] for the bytes of the buffer, but yields views of the buffer.
+    /// The key can be thought of as a byte class, such as the delimiters of a protocol.
+    /// See the [module documentation][self] for an example.
+    fn into_spans_by_key_change<K, C>(self, key: K) -> IntoBytesSpans<Self, ByKeyChange<K, C>>
+    where
+        K: FnMut(&u8) -> C,
+        C: Clone + PartialEq;
+
+    /// Splits the buffer into runs of equal bytes.
+    ///
+    /// This is like [`ByteSpans::byte_runs`][crate::byte::ByteSpans::byte_runs], but yields views of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use spans::bytes::BytesSpans;
+    ///
+    /// let runs: Vec<Bytes> = Bytes::from_static(b"aaab").into_byte_runs().collect();
+    ///
+    /// assert_eq!(runs, vec!["aaa", "b"]);
+    /// ```
+    fn into_byte_runs(self) -> IntoByteRuns<Self>;
+
+    /// Splits the buffer into spans of bytes that are either all members or all non-members of `class`.
+    ///
+    /// This is like [`ByteSpans::spans_by_byte_class`][crate::byte::ByteSpans::spans_by_byte_class], but yields views of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use spans::byte::ByteClass;
+    /// use spans::bytes::BytesSpans;
+    ///
+    /// let buffer = Bytes::from_static(b"HEAD\r\nbody");
+    /// let frames: Vec<Bytes> = buffer
+    ///     .into_byte_class_spans(ByteClass::from_bytes(b"\r\n"))
+    ///     .collect();
+    ///
+    /// assert_eq!(frames, vec!["HEAD", "\r\n", "body"]);
+    /// ```
+    fn into_byte_class_spans(self, class: ByteClass) -> IntoByteClassSpans<Self>;
+
+    /// Splits the buffer into runs of bytes of the same [`AsciiClass`].
+    ///
+    /// This is like [`ByteSpans::ascii_runs`][crate::byte::ByteSpans::ascii_runs], but yields views of the buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    /// use spans::byte::AsciiClass;
+    /// use spans::bytes::BytesSpans;
+    ///
+    /// let buffer = BytesMut::from(&b"id 42"[..]);
+    /// let runs: Vec<_> = buffer.into_ascii_runs().map(|(class, run)| (class, run.freeze())).collect();
+    ///
+    /// assert_eq!(
+    ///     runs,
+    ///     vec![
+    ///         (AsciiClass::Alpha, "id".into()),
+    ///         (AsciiClass::Whitespace, " ".into()),
+    ///         (AsciiClass::Digit, "42".into()),
+    ///     ]
+    /// );
+    /// ```
+    fn into_ascii_runs(self) -> IntoAsciiRuns<Self>;
+}
+
+/// `IntoBytesSpans` is an iterator over the spans of a `bytes` buffer.
+///
+/// See [`BytesSpans::into_spans_by`] and [`BytesSpans::into_spans_by_key`] for more information.
+#[derive(Clone, Debug)]
+pub struct IntoBytesSpans<B, F> {
+    /// The bytes that are not part of any returned span yet.
+    buffer: B,
+    /// Whether two adjacent bytes belong to the same span.
+    connector: F,
+}
+
+/// `IntoByteRuns` is an iterator over the runs of equal bytes of a `bytes` buffer.
+///
+/// See [`BytesSpans::into_byte_runs`] for more information.
+#[derive(Clone, Debug)]
+pub struct IntoByteRuns<B> {
+    /// The bytes that are not part of any returned run yet.
+    buffer: B,
+}
+
+/// `IntoByteClassSpans` is an iterator over the spans of a `bytes` buffer split by membership in a byte class.
+///
+/// See [`BytesSpans::into_byte_class_spans`] for more information.
+#[derive(Clone, Debug)]
+pub struct IntoByteClassSpans<B> {
+    /// The bytes that are not part of any returned span yet.
+    buffer: B,
+    /// The finder for the byte class that determines the spans.
+    finder: ByteClassFinder,
+}
+
+/// `IntoAsciiRuns` is an iterator over the runs of bytes of the same ASCII class of a `bytes` buffer.
+///
+/// See [`BytesSpans::into_ascii_runs`] for more information.
+#[derive(Clone, Debug)]
+pub struct IntoAsciiRuns<B> {
+    /// The bytes that are not part of any returned run yet.
+    buffer: B,
+}
+
+/// Returns the length of the span at the start of `bytes`.
+fn span_len<F>(bytes: &[u8], connector: &mut F) -> usize
+where
+    F: Connector<u8>,
+{
+    let (first, rest) = match bytes.split_first() {
+        Some(split) => split,
+        None => return 0,
+    };
+    connector.start_span(first);
+
+    let mut prev = first;
+    for (index, next) in rest.iter().enumerate() {
+        if !connector.are_connected(prev, next) {
+            return 1 + index;
+        }
+
+        prev = next;
+    }
+
+    bytes.len()
+}
+
+macro_rules! impl_bytes_spans {
+    ($($t:ty),*) => {
+        $(
+            impl BytesSpans for $t {
+                fn into_spans_by<F>(self, are_connected: F) -> IntoBytesSpans<Self, F>
+                where
+                    F: FnMut(&u8, &u8) -> bool,
+                {
+                    IntoBytesSpans {
+                        buffer: self,
+                        connector: are_connected,
+                    }
+                }
+
+                fn into_spans_by_key<K, C, F>(
+                    self,
+                    key: K,
+                    are_connected: F,
+                ) -> IntoBytesSpans<Self, ByKey<K, C, F>>
+                where
+                    K: FnMut(&u8) -> C,
+                    C: Clone,
+                    F: FnMut(C, C) -> bool,
+                {
+                    IntoBytesSpans {
+                        buffer: self,
+                        connector: ByKey::new(key, are_connected),
+                    }
+                }
+
+                fn into_spans_by_key_change<K, C>(self, key: K) -> IntoBytesSpans<Self, ByKeyChange<K, C>>
+                where
+                    K: FnMut(&u8) -> C,
+                    C: Clone + PartialEq,
+                {
+                    self.into_spans_by_key(key, are_equal)
+                }
+
+                fn into_byte_runs(self) -> IntoByteRuns<Self> {
+                    IntoByteRuns { buffer: self }
+                }
+
+                fn into_byte_class_spans(self, class: ByteClass) -> IntoByteClassSpans<Self> {
+                    IntoByteClassSpans {
+                        buffer: self,
+                        finder: ByteClassFinder::new(class),
+                    }
+                }
+
+                fn into_ascii_runs(self) -> IntoAsciiRuns<Self> {
+                    IntoAsciiRuns { buffer: self }
+                }
+            }
+
+            impl<F> Iterator for IntoBytesSpans<$t, F>
+            where
+                F: Connector<u8>,
+            {
+                type Item = $t;
+
+                fn next(&mut self) -> Option<Self::Item> {
+                    if self.buffer.is_empty() {
+                        return None;
+                    }
+
+                    let len = span_len(&self.buffer, &mut self.connector);
+                    Some(self.buffer.split_to(len))
+                }
+
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    (self.buffer.len().min(1), Some(self.buffer.len()))
+                }
+            }
+
+            impl<F> FusedIterator for IntoBytesSpans<$t, F> where F: Connector<u8> {}
+
+            impl Iterator for IntoByteRuns<$t> {
+                type Item = $t;
+
+                fn next(&mut self) -> Option<Self::Item> {
+                    if self.buffer.is_empty() {
+                        return None;
+                    }
+
+                    let len = byte::run_len(&self.buffer);
+                    Some(self.buffer.split_to(len))
+                }
+
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    (self.buffer.len().min(1), Some(self.buffer.len()))
+                }
+            }
+
+            impl FusedIterator for IntoByteRuns<$t> {}
+
+            impl Iterator for IntoByteClassSpans<$t> {
+                type Item = $t;
+
+                fn next(&mut self) -> Option<Self::Item> {
+                    if self.buffer.is_empty() {
+                        return None;
+                    }
+
+                    let len = self.finder.span_len(&self.buffer);
+                    Some(self.buffer.split_to(len))
+                }
+
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    (self.buffer.len().min(1), Some(self.buffer.len()))
+                }
+            }
+
+            impl FusedIterator for IntoByteClassSpans<$t> {}
+
+            impl Iterator for IntoAsciiRuns<$t> {
+                type Item = (AsciiClass, $t);
+
+                fn next(&mut self) -> Option<Self::Item> {
+                    let class = AsciiClass::of(*self.buffer.first()?);
+                    let len = class.run_len(&self.buffer);
+                    Some((class, self.buffer.split_to(len)))
+                }
+
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    (self.buffer.len().min(1), Some(self.buffer.len()))
+                }
+            }
+
+            impl FusedIterator for IntoAsciiRuns<$t> {}
+        )*
+    };
+}
+
+impl_bytes_spans!(Bytes, BytesMut);
+
+#[cfg(test)]
+mod tests {
+    use super::BytesSpans;
+    use crate::byte::{ByteClass, ByteSpans};
+    use crate::slice::SliceSpans;
+    use bytes::{Bytes, BytesMut};
+
+    #[test]
+    fn test_spans_share_memory() {
+        let buffer = Bytes::from(b"aa  bbb".to_vec());
+        let base = buffer.as_ptr() as usize;
+        let runs: Vec<Bytes> = buffer.clone().into_spans_by(|a, b| a == b).collect();
+
+        assert_eq!(runs, buffer.spans_by(|a, b| a == b).collect::<Vec<_>>());
+        let offsets: Vec<usize> = runs
+            .iter()
+            .map(|run| run.as_ptr() as usize - base)
+            .collect();
+        assert_eq!(offsets, vec![0, 2, 4]);
+        assert_eq!(
+            buffer.clone().into_byte_runs().collect::<Vec<_>>(),
+            buffer.byte_runs().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_bytes_mut_spans_match_slice_spans() {
+        let bytes = b"GET / HTTP/1.1\r\nHost: a\r\n\r\n";
+        let is_line_end = |byte: &u8| *byte == b'\r' || *byte == b'\n';
+        let spans: Vec<BytesMut> = BytesMut::from(&bytes[..])
+            .into_spans_by_key_change(is_line_end)
+            .collect();
+        let expected: Vec<&[u8]> = bytes[..]
+            .spans_by(|a, b| is_line_end(a) == is_line_end(b))
+            .collect();
+
+        assert_eq!(
+            spans.iter().map(|span| &span[..]).collect::<Vec<_>>(),
+            expected
+        );
+        let class = ByteClass::from_bytes(b"\r\n");
+        let class_spans: Vec<BytesMut> = BytesMut::from(&bytes[..])
+            .into_byte_class_spans(class)
+            .collect();
+        assert_eq!(class_spans, spans);
+        assert_eq!(
+            bytes.spans_by_byte_class(class).collect::<Vec<_>>(),
+            expected
+        );
+        assert!(BytesMut::new()
+            .into_spans_by(|a, b| a == b)
+            .next()
+            .is_none());
+        assert!(BytesMut::new().into_ascii_runs().next().is_none());
+    }
+}
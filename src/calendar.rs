@@ -0,0 +1,50 @@
+/// `Calendar` determines the business days of a calendar.
+///
+/// `Calendar` is implemented for all closures of the form `Fn(&D) -> Option<D>`.
+/// With the `chrono` or the `time` feature, a weekday calendar with configurable holidays is provided by `chrono::Weekdays` or `time::Weekdays`.
+/// See [`consecutive_business_days`] for more information.
+pub trait Calendar<D> {
+    /// Returns the first business day after `date`, or `None` if there is no such day.
+    fn next_business_day(&self, date: &D) -> Option<D>;
+}
+
+impl<D, F> Calendar<D> for F
+where
+    F: Fn(&D) -> Option<D>,
+{
+    fn next_business_day(&self, date: &D) -> Option<D> {
+        self(date)
+    }
+}
+
+/// Returns a connector for consecutive business days of `calendar`.
+///
+/// Two dates are connected if the second date is the next business day after the first date.
+/// For example, with a calendar of weekdays, a Friday is connected to the following Monday.
+///
+/// # Example
+///
+/// ```
+/// use spans::{consecutive_business_days, Spans};
+///
+/// // Days of a month, where days 6 and 7 are a weekend and day 10 is a holiday.
+/// let calendar = |&day: &u32| {
+///     (day + 1..=31).find(|d| !matches!(d % 7, 6 | 0) && *d != 10)
+/// };
+///
+/// let trading_days = vec![4, 5, 8, 9, 11, 12, 15, 17];
+/// let spans: Vec<Vec<_>> = trading_days
+///     .into_iter()
+///     .spans_by_key(|&d| d, consecutive_business_days(calendar))
+///     .map_spans(|span| span.collect())
+///     .collect();
+///
+/// assert_eq!(spans, vec![vec![4, 5, 8, 9, 11, 12, 15], vec![17]]);
+/// ```
+pub fn consecutive_business_days<D, C>(calendar: C) -> impl FnMut(D, D) -> bool
+where
+    D: PartialEq,
+    C: Calendar<D>,
+{
+    move |prev, next| calendar.next_business_day(&prev) == Some(next)
+}
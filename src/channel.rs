@@ -0,0 +1,102 @@
+use crate::{Connector, SpansBy};
+use std::sync::mpsc::{SendError, Sender, SyncSender};
+use std::vec::Vec;
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Sends each span as a [`Vec`] into `sender`, returning the number of spans sent.
+    ///
+    /// This drives the underlying iterator on the current thread, such that the spans can be processed by the receiving threads.
+    /// Each span is sent as soon as it is complete.
+    /// If the receiver has been dropped, the span that could not be sent is returned in the error and no further spans are collected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// use std::sync::mpsc;
+    /// use std::thread;
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// let consumer = thread::spawn(move || receiver.iter().map(|span: Vec<i32>| span.len()).collect::<Vec<_>>());
+    ///
+    /// let sent = vec![1, 2, 5, 6, 7, 11]
+    ///     .into_iter()
+    ///     .spans_by_key(|&x| x, |a, b| a + 1 == b)
+    ///     .send_spans(&sender)
+    ///     .unwrap();
+    /// drop(sender);
+    ///
+    /// assert_eq!(sent, 3);
+    /// assert_eq!(consumer.join().unwrap(), vec![2, 3, 1]);
+    /// ```
+    pub fn send_spans(
+        self,
+        sender: &Sender<Vec<I::Item>>,
+    ) -> Result<usize, SendError<Vec<I::Item>>> {
+        self.try_for_each_owned_span(|span| sender.send(span))
+    }
+
+    /// Sends each span as a [`Vec`] into the bounded `sender`, returning the number of spans sent.
+    ///
+    /// This is like [`send_spans`][SpansBy::send_spans], except that sending blocks while the channel is full, such that span detection does not run ahead of the receivers.
+    pub fn sync_send_spans(
+        self,
+        sender: &SyncSender<Vec<I::Item>>,
+    ) -> Result<usize, SendError<Vec<I::Item>>> {
+        self.try_for_each_owned_span(|span| sender.send(span))
+    }
+
+    /// Calls `send` with each span as a [`Vec`] until it fails, returning the number of spans sent.
+    fn try_for_each_owned_span<E, S>(self, mut send: S) -> Result<usize, E>
+    where
+        S: FnMut(Vec<I::Item>) -> Result<(), E>,
+    {
+        let mut count = 0;
+
+        for span in self.into_owned_spans() {
+            send(span)?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_send_spans() {
+        let (sender, receiver) = mpsc::channel();
+        let sent = [1, 1, 2]
+            .iter()
+            .copied()
+            .spans_by_key_change(|&x| x)
+            .send_spans(&sender);
+        assert_eq!(sent, Ok(2));
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            vec![vec![1, 1], vec![2]]
+        );
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let (sender, receiver) = mpsc::sync_channel(4);
+        drop(receiver);
+        let mut iter = [1, 2, 3].iter().copied();
+        let error = iter
+            .by_ref()
+            .spans_by_key_change(|&x| x)
+            .sync_send_spans(&sender)
+            .unwrap_err();
+        assert_eq!(error.0, vec![1]);
+        assert_eq!(iter.next(), Some(3));
+    }
+}
@@ -0,0 +1,327 @@
+//! Helpers for splitting iterators of [`chrono`] date-times into contiguous spans.
+//!
+//! Use [`within`] as a connector for [`Spans::spans_by_key`] to split by time gap:
+//!
+//! ```
+//! use chrono::{TimeDelta, TimeZone, Utc};
+//! use spans::Spans;
+//!
+//! let logins = vec![
+//!     Utc.with_ymd_and_hms(2024, 5, 1, 8, 0, 0).unwrap(),
+//!     Utc.with_ymd_and_hms(2024, 5, 1, 8, 10, 0).unwrap(),
+//!     Utc.with_ymd_and_hms(2024, 5, 1, 14, 0, 0).unwrap(),
+//! ];
+//! let sessions: Vec<usize> = logins
+//!     .iter()
+//!     .spans_by_key(|&&t| t, spans::chrono::within(TimeDelta::minutes(30)))
+//!     .map_spans(|span| span.count())
+//!     .collect();
+//!
+//! assert_eq!(sessions, vec![2, 1]);
+//! ```
+//!
+//! Use a calendar bucket, such as [`hour`], [`day`], [`iso_week`], or [`month`], with [`Spans::spans_by_bucket`] to split at calendar boundaries:
+//!
+//! ```
+//! use chrono::{TimeZone, Utc};
+//! use spans::Spans;
+//!
+//! let readings = vec![
+//!     (Utc.with_ymd_and_hms(2024, 5, 1, 8, 0, 0).unwrap(), 12.5),
+//!     (Utc.with_ymd_and_hms(2024, 5, 1, 23, 0, 0).unwrap(), 9.0),
+//!     (Utc.with_ymd_and_hms(2024, 5, 2, 1, 0, 0).unwrap(), 8.5),
+//! ];
+//! let days: Vec<usize> = readings
+//!     .iter()
+//!     .spans_by_bucket(|(t, _)| spans::chrono::day(t))
+//!     .map_spans(|span| span.count())
+//!     .collect();
+//!
+//! assert_eq!(days, vec![2, 1]);
+//! ```
+//!
+//! Calendar boundaries are determined in the time zone of each date-time.
+//!
+//! Use [`Weekdays`] with [`consecutive_business_days`] to split dates into spans of consecutive business days.
+//!
+//! [`Spans::spans_by_key`]: crate::Spans::spans_by_key
+//! [`consecutive_business_days`]: crate::consecutive_business_days
+//! [`Spans::spans_by_bucket`]: crate::Spans::spans_by_bucket
+
+use crate::Calendar;
+use chrono::{
+    DateTime, Datelike, FixedOffset, IsoWeek, NaiveDate, TimeDelta, TimeZone, Timelike, Weekday,
+};
+
+/// Returns a connector for date-times that are at most `max_gap` apart.
+///
+/// Two date-times are connected if the second is not before the first and the difference between them is at most `max_gap`.
+/// The difference is computed on the instants in time, such that changes of the UTC offset, for example due to daylight saving time, do not affect it.
+pub fn within<Tz: TimeZone>(max_gap: TimeDelta) -> impl FnMut(DateTime<Tz>, DateTime<Tz>) -> bool {
+    move |prev, next| prev <= next && next - prev <= max_gap
+}
+
+/// Returns the calendar day of `datetime` in its time zone.
+pub fn day<Tz: TimeZone>(datetime: &DateTime<Tz>) -> NaiveDate {
+    datetime.date_naive()
+}
+
+/// Returns the ISO 8601 week of `datetime` in its time zone.
+///
+/// ISO weeks start on Monday; the first and last days of a calendar year may belong to a week of the adjacent ISO year.
+pub fn iso_week<Tz: TimeZone>(datetime: &DateTime<Tz>) -> IsoWeek {
+    datetime.iso_week()
+}
+
+/// Returns the year and the month (from 1 to 12) of `datetime` in its time zone.
+pub fn month<Tz: TimeZone>(datetime: &DateTime<Tz>) -> (i32, u32) {
+    (datetime.year(), datetime.month())
+}
+
+/// Returns the start of the hour of `datetime` in its time zone.
+///
+/// The UTC offset is kept, such that the repeated hour at the end of daylight saving time is distinct from the hour before it.
+pub fn hour<Tz: TimeZone>(datetime: &DateTime<Tz>) -> DateTime<FixedOffset> {
+    datetime
+        .fixed_offset()
+        .with_nanosecond(0)
+        .and_then(|datetime| datetime.with_second(0))
+        .and_then(|datetime| datetime.with_minute(0))
+        .expect("times with a fixed offset are never ambiguous")
+}
+
+/// `Weekdays` is a [`Calendar`] of weekdays, where Saturdays, Sundays, and holidays are not business days.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use spans::chrono::Weekdays;
+/// use spans::{consecutive_business_days, Spans};
+///
+/// let christmas = NaiveDate::from_ymd_opt(2024, 12, 25).unwrap();
+/// let calendar = Weekdays::with_holidays(move |&date| date == christmas);
+///
+/// let dates: Vec<_> = [20, 23, 24, 26, 30]
+///     .iter()
+///     .map(|&day| NaiveDate::from_ymd_opt(2024, 12, day).unwrap())
+///     .collect();
+/// let lengths: Vec<_> = dates
+///     .iter()
+///     .spans_by_key(|&&date| date, consecutive_business_days(calendar))
+///     .map_spans(|span| span.count())
+///     .collect();
+///
+/// assert_eq!(lengths, vec![4, 1]);
+/// ```
+pub struct Weekdays<H = fn(&NaiveDate) -> bool> {
+    /// Whether a date is a holiday.
+    is_holiday: H,
+}
+
+impl Weekdays {
+    /// Creates a new `Weekdays` calendar without holidays.
+    pub fn new() -> Self {
+        Weekdays {
+            is_holiday: |_| false,
+        }
+    }
+}
+
+impl Default for Weekdays {
+    fn default() -> Self {
+        Weekdays::new()
+    }
+}
+
+impl<H> Weekdays<H>
+where
+    H: Fn(&NaiveDate) -> bool,
+{
+    /// Creates a new `Weekdays` calendar where dates for which `is_holiday` returns `true` are not business days.
+    pub fn with_holidays(is_holiday: H) -> Self {
+        Weekdays { is_holiday }
+    }
+}
+
+impl<H> Calendar<NaiveDate> for Weekdays<H>
+where
+    H: Fn(&NaiveDate) -> bool,
+{
+    fn next_business_day(&self, date: &NaiveDate) -> Option<NaiveDate> {
+        let mut date = date.succ_opt()?;
+
+        while matches!(date.weekday(), Weekday::Sat | Weekday::Sun) || (self.is_holiday)(&date) {
+            date = date.succ_opt()?;
+        }
+
+        Some(date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Spans;
+
+    /// Returns the time in New York with the given offset in hours.
+    fn new_york(
+        offset: i32,
+        (y, m, d): (i32, u32, u32),
+        (h, min): (u32, u32),
+    ) -> DateTime<FixedOffset> {
+        FixedOffset::east_opt(offset * 3600)
+            .unwrap()
+            .with_ymd_and_hms(y, m, d, h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_within_spring_forward() {
+        // 01:59 EST is followed by 03:00 EDT one minute later.
+        let times = [
+            new_york(-5, (2024, 3, 10), (1, 59)),
+            new_york(-4, (2024, 3, 10), (3, 0)),
+            new_york(-4, (2024, 3, 10), (3, 30)),
+        ];
+        let spans = times
+            .iter()
+            .spans_by_key(|&&t| t, within(TimeDelta::minutes(5)))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_hour_fall_back() {
+        // The hour from 01:00 to 02:00 occurs twice, once in EDT and once in EST.
+        let times = [
+            new_york(-4, (2024, 11, 3), (1, 10)),
+            new_york(-4, (2024, 11, 3), (1, 50)),
+            new_york(-5, (2024, 11, 3), (1, 10)),
+            new_york(-5, (2024, 11, 3), (2, 10)),
+        ];
+        let spans = times
+            .iter()
+            .spans_by_bucket(|t| hour(t))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![2, 1, 1]);
+    }
+
+    #[test]
+    fn test_day_fall_back() {
+        // The day of the transition has 25 hours.
+        let times = [
+            new_york(-4, (2024, 11, 3), (0, 0)),
+            new_york(-5, (2024, 11, 3), (23, 59)),
+            new_york(-5, (2024, 11, 4), (0, 0)),
+        ];
+        let spans = times
+            .iter()
+            .spans_by_bucket(|t| day(t))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![2, 1]);
+    }
+
+    /// Returns `count` date-times an hour apart in New York, starting at midnight local time of `(y, m, d)`.
+    fn hourly_in_new_york((y, m, d): (i32, u32, u32), count: i64) -> Vec<DateTime<chrono_tz::Tz>> {
+        let start = chrono_tz::America::New_York
+            .with_ymd_and_hms(y, m, d, 0, 0, 0)
+            .unwrap();
+        (0..count).map(|h| start + TimeDelta::hours(h)).collect()
+    }
+
+    #[test]
+    fn test_buckets_spring_forward_in_time_zone() {
+        // From midnight EST, 23 hours pass until midnight EDT of the next day; 02:00 to 03:00 is skipped.
+        let times = hourly_in_new_york((2024, 3, 10), 24);
+        let days = times
+            .iter()
+            .spans_by_bucket(|t| day(t))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(days, vec![23, 1]);
+
+        let hours = times
+            .iter()
+            .spans_by_bucket(|t| hour(t))
+            .map_spans(|span| span.key().hour())
+            .collect::<Vec<_>>();
+        assert_eq!(&hours[..4], &[0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn test_buckets_fall_back_in_time_zone() {
+        // From midnight EDT, 25 hours pass until midnight EST of the next day; 01:00 to 02:00 occurs twice.
+        let times = hourly_in_new_york((2024, 11, 3), 26);
+        let days = times
+            .iter()
+            .spans_by_bucket(|t| day(t))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(days, vec![25, 1]);
+
+        let hours = times
+            .iter()
+            .spans_by_bucket(|t| hour(t))
+            .map_spans(|span| span.key().hour())
+            .collect::<Vec<_>>();
+        assert_eq!(hours.len(), 26);
+        assert_eq!(&hours[..4], &[0, 1, 1, 2]);
+    }
+
+    #[test]
+    fn test_iso_week_year_change() {
+        // 2024-12-30 is the Monday of the first ISO week of 2025.
+        let times = [
+            new_york(-5, (2024, 12, 29), (12, 0)),
+            new_york(-5, (2024, 12, 30), (12, 0)),
+            new_york(-5, (2025, 1, 5), (12, 0)),
+            new_york(-5, (2025, 1, 6), (12, 0)),
+        ];
+        let spans = times
+            .iter()
+            .spans_by_bucket(|t| iso_week(t))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn test_month() {
+        let times = [
+            new_york(-5, (2024, 1, 31), (23, 59)),
+            new_york(-5, (2024, 2, 1), (0, 0)),
+            new_york(-5, (2024, 2, 29), (0, 0)),
+            new_york(-5, (2025, 2, 1), (0, 0)),
+        ];
+        let spans = times
+            .iter()
+            .spans_by_bucket(|t| month(t))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn test_weekdays() {
+        let next =
+            |y, m, d| Weekdays::new().next_business_day(&NaiveDate::from_ymd_opt(y, m, d).unwrap());
+        // Thursday, Friday, Saturday, and Sunday.
+        assert_eq!(next(2024, 5, 2), NaiveDate::from_ymd_opt(2024, 5, 3));
+        assert_eq!(next(2024, 5, 3), NaiveDate::from_ymd_opt(2024, 5, 6));
+        assert_eq!(next(2024, 5, 4), NaiveDate::from_ymd_opt(2024, 5, 6));
+        assert_eq!(next(2024, 5, 5), NaiveDate::from_ymd_opt(2024, 5, 6));
+        assert_eq!(Weekdays::new().next_business_day(&NaiveDate::MAX), None);
+    }
+
+    #[test]
+    fn test_weekdays_with_holidays() {
+        // Friday and Monday are holidays.
+        let calendar = Weekdays::with_holidays(|date: &NaiveDate| matches!(date.day(), 3 | 6));
+        let next = calendar.next_business_day(&NaiveDate::from_ymd_opt(2024, 5, 2).unwrap());
+        assert_eq!(next, NaiveDate::from_ymd_opt(2024, 5, 7));
+    }
+}
@@ -0,0 +1,914 @@
+//! Compact notation for ranges of integers, such as page ranges.
+//!
+//! Use [`format_ranges`] to display ranges as a list like `1–3, 5, 9–12`:
+//!
+//! ```
+//! use spans::compact;
+//!
+//! let ranges = vec![1..=3, 5..=5, 9..=12];
+//!
+//! assert_eq!(compact::format_ranges(&ranges).to_string(), "1–3, 5, 9–12");
+//! ```
+//!
+//! Use [`parse_ranges`] to read such a list back into ranges:
+//!
+//! ```
+//! use spans::compact;
+//!
+//! let ranges: Result<Vec<_>, _> = compact::parse_ranges::<u32>("1-3,5,9-12").collect();
+//!
+//! assert_eq!(ranges.unwrap(), vec![1..=3, 5..=5, 9..=12]);
+//! ```
+//!
+//! A `RangeList` holds such ranges and converts from and to compact notation with [`FromStr`] and [`Display`][fmt::Display].
+//! With the `serde` feature, it deserializes from compact notation as well as from an array of ranges, such that configuration files can contain either form.
+
+use crate::{SpanKeyRanges, Spans};
+#[cfg(feature = "alloc")]
+use alloc::vec::{self, Vec};
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::{Range, RangeInclusive};
+use core::str::FromStr;
+#[cfg(feature = "alloc")]
+use core::{iter, slice};
+
+/// Returns a wrapper that displays `ranges` in compact notation.
+///
+/// Each range is displayed as its start and end joined by a dash, or as its start if it contains a single value.
+/// The ranges are joined by a separator.
+/// By default, the dash is an en dash (`–`) and the separator is a comma followed by a space.
+/// Use [`FormatRanges::dash`] and [`FormatRanges::separator`] to change them.
+///
+/// `ranges` is iterated each time the wrapper is displayed.
+///
+/// # Example
+///
+/// ```
+/// use spans::{compact, Spans};
+///
+/// let pages = vec![1, 2, 3, 5, 9, 10, 11, 12];
+/// let ranges: Vec<_> = pages.into_iter().consecutive_spans().span_key_ranges().collect();
+///
+/// let formatted = compact::format_ranges(&ranges).dash("-").separator(",");
+/// assert_eq!(formatted.to_string(), "1-3,5,9-12");
+/// ```
+pub fn format_ranges<R, T>(ranges: R) -> FormatRanges<'static, R::IntoIter, T>
+where
+    R: IntoIterator,
+    R::IntoIter: Clone,
+    R::Item: Borrow<RangeInclusive<T>>,
+{
+    FormatRanges {
+        ranges: ranges.into_iter(),
+        dash: "–",
+        separator: ", ",
+        bound: PhantomData,
+    }
+}
+
+/// `FormatRanges` displays ranges in compact notation.
+///
+/// See [`format_ranges`] for more information.
+#[derive(Clone)]
+pub struct FormatRanges<'a, I, T> {
+    /// The ranges to display.
+    ranges: I,
+    /// The text between the start and the end of a range.
+    dash: &'a str,
+    /// The text between two ranges.
+    separator: &'a str,
+    /// The type of the range bounds.
+    bound: PhantomData<fn() -> T>,
+}
+
+impl<'a, I, T> FormatRanges<'a, I, T> {
+    /// Sets the text between the start and the end of a range.
+    pub fn dash<'b>(self, dash: &'b str) -> FormatRanges<'b, I, T>
+    where
+        'a: 'b,
+    {
+        FormatRanges { dash, ..self }
+    }
+
+    /// Sets the text between two ranges.
+    pub fn separator<'b>(self, separator: &'b str) -> FormatRanges<'b, I, T>
+    where
+        'a: 'b,
+    {
+        FormatRanges { separator, ..self }
+    }
+}
+
+impl<I, T> fmt::Display for FormatRanges<'_, I, T>
+where
+    I: Iterator + Clone,
+    I::Item: Borrow<RangeInclusive<T>>,
+    T: fmt::Display + PartialEq,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, range) in self.ranges.clone().enumerate() {
+            let range = range.borrow();
+
+            if index > 0 {
+                f.write_str(self.separator)?;
+            }
+
+            if range.start() == range.end() {
+                write!(f, "{}", range.start())?;
+            } else {
+                write!(f, "{}{}{}", range.start(), self.dash, range.end())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns an iterator over the ranges of a list in compact notation.
+///
+/// The ranges in `text` are separated by commas.
+/// Each range is either a single value or its start and end joined by a hyphen (`-`) or an en dash (`–`).
+/// Whitespace around values, ranges, and the whole list is ignored.
+/// A leading hyphen of a value is part of that value, so negative bounds can be written as `-5--2`.
+///
+/// The iterator yields an error for each malformed range and continues with the next range.
+/// Collect into a `Result` to stop at the first error.
+/// An empty or blank `text` yields no ranges.
+///
+/// # Example
+///
+/// ```
+/// use spans::compact::{self, ParseRangesErrorKind};
+///
+/// let mut ranges = compact::parse_ranges::<u8>(" 2 – 4, 7, 9-x ");
+///
+/// assert_eq!(ranges.next(), Some(Ok(2..=4)));
+/// assert_eq!(ranges.next(), Some(Ok(7..=7)));
+///
+/// let error = ranges.next().unwrap().unwrap_err();
+/// assert_eq!(error.span(), 13..16);
+/// assert!(matches!(error.kind(), ParseRangesErrorKind::Bound(_)));
+///
+/// assert_eq!(ranges.next(), None);
+/// ```
+pub fn parse_ranges<T>(text: &str) -> ParseRanges<'_, T>
+where
+    T: FromStr + PartialOrd,
+{
+    ParseRanges {
+        text,
+        offset: 0,
+        is_done: text.trim().is_empty(),
+        bound: PhantomData,
+    }
+}
+
+/// `ParseRanges` is an iterator over the ranges of a list in compact notation.
+///
+/// See [`parse_ranges`] for more information.
+#[derive(Clone, Debug)]
+pub struct ParseRanges<'a, T> {
+    /// The list in compact notation.
+    text: &'a str,
+    /// The byte offset of the next range in `text`.
+    offset: usize,
+    /// Whether all ranges have been yielded.
+    is_done: bool,
+    /// The type of the range bounds.
+    bound: PhantomData<fn() -> T>,
+}
+
+impl<T> ParseRanges<'_, T>
+where
+    T: FromStr + PartialOrd,
+{
+    /// Parses a single range, whose leading and trailing whitespace has been removed.
+    fn parse_range(range: &str) -> Result<RangeInclusive<T>, ParseRangesErrorKind<T::Err>> {
+        if range.is_empty() {
+            return Err(ParseRangesErrorKind::Empty);
+        }
+
+        // The first character is skipped since it may be the sign of the start.
+        let dash = range
+            .char_indices()
+            .skip(1)
+            .find(|&(_, c)| c == '-' || c == '–');
+
+        let (start, end) = match dash {
+            Some((index, c)) => (&range[..index], &range[index + c.len_utf8()..]),
+            None => (range, range),
+        };
+
+        let start = start.trim().parse().map_err(ParseRangesErrorKind::Bound)?;
+        let end = end.trim().parse().map_err(ParseRangesErrorKind::Bound)?;
+
+        if start > end {
+            return Err(ParseRangesErrorKind::Reversed);
+        }
+
+        Ok(start..=end)
+    }
+}
+
+impl<T> Iterator for ParseRanges<'_, T>
+where
+    T: FromStr + PartialOrd,
+{
+    type Item = Result<RangeInclusive<T>, ParseRangesError<T::Err>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+
+        let rest = &self.text[self.offset..];
+        let length = match rest.find(',') {
+            Some(length) => length,
+            None => {
+                self.is_done = true;
+                rest.len()
+            }
+        };
+
+        let element = &rest[..length];
+        let range = element.trim();
+        let start = self.offset + (element.len() - element.trim_start().len());
+        let span = start..(start + range.len());
+
+        self.offset += length + 1;
+
+        Some(Self::parse_range(range).map_err(|kind| ParseRangesError { span, kind }))
+    }
+}
+
+impl<T> FusedIterator for ParseRanges<'_, T> where T: FromStr + PartialOrd {}
+
+/// An error which can be returned when parsing a range in compact notation.
+///
+/// See [`parse_ranges`] for more information.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseRangesError<E> {
+    /// The byte range of the malformed range in the input.
+    span: Range<usize>,
+    /// The cause of the error.
+    kind: ParseRangesErrorKind<E>,
+}
+
+impl<E> ParseRangesError<E> {
+    /// Returns the byte range of the malformed range in the input, excluding surrounding whitespace.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the cause of the error.
+    pub fn kind(&self) -> &ParseRangesErrorKind<E> {
+        &self.kind
+    }
+
+    /// Consumes the error, returning its cause.
+    pub fn into_kind(self) -> ParseRangesErrorKind<E> {
+        self.kind
+    }
+}
+
+impl<E> fmt::Display for ParseRangesError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}..{}", self.kind, self.span.start, self.span.end)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> std::error::Error for ParseRangesError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ParseRangesErrorKind::Bound(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// The cause of a [`ParseRangesError`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseRangesErrorKind<E> {
+    /// The range is empty, such as between two adjacent commas.
+    Empty,
+    /// A bound of the range could not be parsed.
+    Bound(E),
+    /// The start of the range is greater than its end.
+    Reversed,
+}
+
+impl<E> fmt::Display for ParseRangesErrorKind<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseRangesErrorKind::Empty => f.write_str("empty range"),
+            ParseRangesErrorKind::Bound(error) => write!(f, "invalid range bound: {}", error),
+            ParseRangesErrorKind::Reversed => f.write_str("range start is greater than its end"),
+        }
+    }
+}
+
+/// `RangeList` is a list of inclusive ranges that converts from and to compact notation.
+///
+/// Parse a `RangeList` from compact notation with [`str::parse`], which accepts the notation of [`parse_ranges`] and fails at the first malformed range.
+/// Display a `RangeList` to get the ranges in compact notation, joined by `-` and `,`.
+///
+/// With the `serde` feature, a `RangeList` is serialized in compact notation in human-readable formats and as an array of `[start, end]` pairs otherwise.
+/// In human-readable formats, it is deserialized from compact notation, such as `"1-3,7-9"`, or from an array of ranges, such as `[[1, 3], [7, 9]]`, where a range of a single value can also be given as the value itself.
+/// In other formats, it is deserialized from an array of `[start, end]` pairs.
+///
+/// # Example
+///
+/// ```
+/// use spans::compact::RangeList;
+///
+/// let list: RangeList<u32> = "1-3, 7-9".parse().unwrap();
+///
+/// assert_eq!(list.ranges(), &[1..=3, 7..=9]);
+/// assert_eq!(list.to_string(), "1-3,7-9");
+/// assert!("3-1".parse::<RangeList<u32>>().is_err());
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RangeList<T> {
+    /// The ranges in order of the list.
+    ranges: Vec<RangeInclusive<T>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> RangeList<T> {
+    /// Creates an empty `RangeList`.
+    pub fn new() -> Self {
+        RangeList { ranges: Vec::new() }
+    }
+
+    /// Returns the ranges in order of the list.
+    pub fn ranges(&self) -> &[RangeInclusive<T>] {
+        &self.ranges
+    }
+
+    /// Unwraps `self`, returning the ranges in order of the list.
+    pub fn into_ranges(self) -> Vec<RangeInclusive<T>> {
+        self.ranges
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> From<Vec<RangeInclusive<T>>> for RangeList<T> {
+    fn from(ranges: Vec<RangeInclusive<T>>) -> Self {
+        RangeList { ranges }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> core::iter::FromIterator<RangeInclusive<T>> for RangeList<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        RangeList {
+            ranges: ranges.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> IntoIterator for RangeList<T> {
+    type Item = RangeInclusive<T>;
+    type IntoIter = vec::IntoIter<RangeInclusive<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> FromStr for RangeList<T>
+where
+    T: FromStr + PartialOrd,
+{
+    type Err = ParseRangesError<T::Err>;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_ranges(text).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> fmt::Display for RangeList<T>
+where
+    T: fmt::Display + PartialEq,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format_ranges(&self.ranges).dash("-").separator(",").fmt(f)
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+impl<T> serde::Serialize for RangeList<T>
+where
+    T: serde::Serialize + fmt::Display + PartialEq,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.collect_seq(self.ranges.iter().map(|range| (range.start(), range.end())))
+        }
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+impl<'de, T> serde::Deserialize<'de> for RangeList<T>
+where
+    T: serde::Deserialize<'de> + FromStr + PartialOrd + Clone,
+    T::Err: fmt::Display,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(RangeListVisitor {
+                is_human_readable: true,
+                bound: PhantomData,
+            })
+        } else {
+            deserializer.deserialize_seq(RangeListVisitor {
+                is_human_readable: false,
+                bound: PhantomData,
+            })
+        }
+    }
+}
+
+/// A range in the array form of a [`RangeList`].
+#[cfg(all(feature = "serde", feature = "alloc"))]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum RangeElement<T> {
+    /// A range given as its start and end.
+    Bounds(T, T),
+    /// A range of a single value.
+    Value(T),
+}
+
+/// `RangeListVisitor` deserializes a [`RangeList`] from compact notation or from an array of ranges.
+#[cfg(all(feature = "serde", feature = "alloc"))]
+struct RangeListVisitor<T> {
+    /// Whether array elements can be single values, which requires a self-describing format.
+    is_human_readable: bool,
+    /// The type of the range bounds.
+    bound: PhantomData<fn() -> T>,
+}
+
+#[cfg(all(feature = "serde", feature = "alloc"))]
+impl<'de, T> serde::de::Visitor<'de> for RangeListVisitor<T>
+where
+    T: serde::Deserialize<'de> + FromStr + PartialOrd + Clone,
+    T::Err: fmt::Display,
+{
+    type Value = RangeList<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a list of ranges in compact notation or an array of ranges")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, text: &str) -> Result<Self::Value, E> {
+        text.parse().map_err(E::custom)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut ranges = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+
+        loop {
+            let element = if self.is_human_readable {
+                seq.next_element::<RangeElement<T>>()?
+            } else {
+                seq.next_element::<(T, T)>()?
+                    .map(|(start, end)| RangeElement::Bounds(start, end))
+            };
+            let range = match element {
+                None => break,
+                Some(RangeElement::Bounds(start, end)) if start > end => {
+                    return Err(serde::de::Error::custom(
+                        ParseRangesErrorKind::<T::Err>::Reversed,
+                    ));
+                }
+                Some(RangeElement::Bounds(start, end)) => start..=end,
+                Some(RangeElement::Value(value)) => value.clone()..=value,
+            };
+            ranges.push(range);
+        }
+
+        Ok(RangeList { ranges })
+    }
+}
+
+/// Returns an iterator over the ranges of consecutive ports in `ports`.
+///
+/// The ports may be given in any order and may contain duplicates.
+/// The ranges are yielded in ascending order.
+/// Displaying the iterator formats its remaining ranges in the notation of common firewall rules, such as `8000-8010,8443`.
+///
+/// The ports are collected and sorted first.
+///
+/// # Example
+///
+/// ```
+/// use spans::compact;
+///
+/// let ports = vec![8443, 8002, 8000, 8001, 8003, 8443];
+/// let ranges = compact::compress_ports(ports);
+///
+/// assert_eq!(ranges.to_string(), "8000-8003,8443");
+/// assert_eq!(ranges.len(), 2);
+/// assert_eq!(ranges.collect::<Vec<_>>(), vec![8000..=8003, 8443..=8443]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn compress_ports<P>(ports: P) -> PortRanges
+where
+    P: IntoIterator<Item = u16>,
+{
+    let mut ports: Vec<u16> = ports.into_iter().collect();
+    ports.sort_unstable();
+    ports.dedup();
+
+    let mut ranges = PortRanges {
+        ports,
+        start: 0,
+        len: 0,
+    };
+    ranges.len = ranges.remaining().count();
+    ranges
+}
+
+/// The ranges of the remaining ports of a [`PortRanges`].
+#[cfg(feature = "alloc")]
+type PortKeyRanges<'a> =
+    SpanKeyRanges<iter::Copied<slice::Iter<'a, u16>>, fn(&u16) -> u16, u16, fn(u16, u16) -> bool>;
+
+/// `PortRanges` is an iterator over the ranges of consecutive ports of a set of ports.
+///
+/// See [`compress_ports`] for more information.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct PortRanges {
+    /// The sorted ports without duplicates.
+    ports: Vec<u16>,
+    /// The index of the first port in `ports` that is not part of any returned range yet.
+    start: usize,
+    /// The number of remaining ranges.
+    len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl PortRanges {
+    /// Returns an iterator over the remaining ranges that borrows the ports.
+    fn remaining(&self) -> PortKeyRanges<'_> {
+        self.ports[self.start..]
+            .iter()
+            .copied()
+            .consecutive_spans()
+            .span_key_ranges()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for PortRanges {
+    type Item = RangeInclusive<u16>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let range = self.remaining().next()?;
+        self.start += usize::from(range.end() - range.start()) + 1;
+        self.len -= 1;
+        Some(range)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ExactSizeIterator for PortRanges {}
+
+#[cfg(feature = "alloc")]
+impl FusedIterator for PortRanges {}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for PortRanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format_ranges(self.remaining())
+            .dash("-")
+            .separator(",")
+            .fmt(f)
+    }
+}
+
+/// Returns an iterator over the ranges of consecutive code points in `chars`, which must be sorted.
+///
+/// This is like `compress_chars`, except that the characters must be given in ascending order, such that no allocation is needed to sort them.
+/// Duplicates are allowed.
+/// If the characters are not sorted, the ranges are still yielded in the order of `chars`, but they may overlap or be adjacent.
+///
+/// # Example
+///
+/// ```
+/// use spans::compact;
+///
+/// let ranges: Vec<_> = compact::compress_sorted_chars("aabcfg".chars()).collect();
+///
+/// assert_eq!(ranges, vec!['a'..='c', 'f'..='g']);
+/// ```
+pub fn compress_sorted_chars<C>(chars: C) -> SortedCharRanges<C::IntoIter>
+where
+    C: IntoIterator<Item = char>,
+{
+    let key: fn(&char) -> char = identity_char;
+    let are_connected: fn(char, char) -> bool = are_sorted_chars_connected;
+
+    SortedCharRanges {
+        ranges: chars
+            .into_iter()
+            .spans_by_key(key, are_connected)
+            .span_key_ranges(),
+    }
+}
+
+/// Returns an iterator over the ranges of consecutive code points in `chars`.
+///
+/// The characters may be given in any order and may contain duplicates.
+/// The ranges are yielded in ascending order.
+/// Since surrogate code points are not characters, `'\u{D7FF}'` and `'\u{E000}'` are consecutive and can share a range.
+///
+/// This is useful for generating character classes or font coverage tables.
+/// The characters are collected and sorted first; use [`compress_sorted_chars`] for characters that are already sorted, which does not need the `alloc` feature.
+///
+/// # Example
+///
+/// ```
+/// use spans::compact;
+///
+/// let ranges: Vec<_> = compact::compress_chars("cabfe\u{E000}\u{D7FF}".chars()).collect();
+///
+/// assert_eq!(ranges, vec!['a'..='c', 'e'..='f', '\u{D7FF}'..='\u{E000}']);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn compress_chars<C>(chars: C) -> CharRanges
+where
+    C: IntoIterator<Item = char>,
+{
+    let mut chars: Vec<char> = chars.into_iter().collect();
+    chars.sort_unstable();
+    chars.dedup();
+
+    CharRanges {
+        ranges: compress_sorted_chars(chars),
+    }
+}
+
+/// Returns a copy of `c`.
+fn identity_char(c: &char) -> char {
+    *c
+}
+
+/// Returns whether `b` is equal to `a` or the character directly following `a`, skipping the surrogate code points.
+fn are_sorted_chars_connected(a: char, b: char) -> bool {
+    match a {
+        '\u{D7FF}' => b == a || b == '\u{E000}',
+        _ => u32::from(b).wrapping_sub(u32::from(a)) <= 1,
+    }
+}
+
+/// The wrapped iterator of [`SortedCharRanges`].
+type CharKeyRanges<I> = SpanKeyRanges<I, fn(&char) -> char, char, fn(char, char) -> bool>;
+
+/// `SortedCharRanges` is an iterator over the ranges of consecutive code points of sorted characters.
+///
+/// See [`compress_sorted_chars`] for more information.
+#[derive(Clone, Debug)]
+pub struct SortedCharRanges<I: Iterator<Item = char>> {
+    /// The key ranges of the spans of the characters.
+    ranges: CharKeyRanges<I>,
+}
+
+impl<I: Iterator<Item = char>> Iterator for SortedCharRanges<I> {
+    type Item = RangeInclusive<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ranges.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ranges.size_hint()
+    }
+}
+
+impl<I: Iterator<Item = char>> FusedIterator for SortedCharRanges<I> {}
+
+/// `CharRanges` is an iterator over the ranges of consecutive code points of a set of characters.
+///
+/// See [`compress_chars`] for more information.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct CharRanges {
+    /// The ranges of the sorted characters.
+    ranges: SortedCharRanges<vec::IntoIter<char>>,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for CharRanges {
+    type Item = RangeInclusive<char>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ranges.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ranges.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FusedIterator for CharRanges {}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use super::{compress_chars, compress_ports};
+    use super::{compress_sorted_chars, format_ranges, parse_ranges, ParseRangesErrorKind};
+    use core::ops::RangeInclusive;
+
+    #[test]
+    fn test_empty() {
+        let ranges: [RangeInclusive<i32>; 0] = [];
+        assert_eq!(format_ranges(&ranges).to_string(), "");
+    }
+
+    #[test]
+    fn test_owned_ranges() {
+        let ranges = vec![-3..=-1, 0..=0];
+        let formatted = format_ranges(ranges).dash(" to ").separator("; ");
+        assert_eq!(formatted.to_string(), "-3 to -1; 0");
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let ranges = vec![-5..=-2, 0..=0, 3..=4];
+        let text = format_ranges(&ranges).to_string();
+        let parsed: Result<Vec<_>, _> = parse_ranges::<i64>(&text).collect();
+        assert_eq!(parsed.unwrap(), ranges);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_ranges::<u8>("  ").next(), None);
+
+        let errors: Vec<_> = parse_ranges::<u8>("1,,4-2, 300")
+            .filter_map(Result::err)
+            .map(|error| (error.span(), error.into_kind()))
+            .collect();
+
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0], (2..2, ParseRangesErrorKind::Empty));
+        assert_eq!(errors[1], (3..6, ParseRangesErrorKind::Reversed));
+        assert_eq!(errors[2].0, 8..11);
+        assert!(matches!(errors[2].1, ParseRangesErrorKind::Bound(_)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_compress_ports_bounds() {
+        let ranges = compress_ports([u16::MAX, 0, 1, 63, 64, u16::MAX - 1].iter().copied());
+        assert_eq!(ranges.to_string(), "0-1,63-64,65534-65535");
+        assert_eq!(compress_ports(0..=u16::MAX).to_string(), "0-65535");
+        assert_eq!(compress_ports(None).next(), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_compress_ports_len() {
+        let mut ranges = compress_ports([1, 2, 63, 64, 128, 200, 201].iter().copied());
+        assert_eq!(ranges.size_hint(), (4, Some(4)));
+        assert_eq!(ranges.next(), Some(1..=2));
+        assert_eq!(ranges.len(), 3);
+        assert_eq!(ranges.to_string(), "63-64,128,200-201");
+        assert_eq!(ranges.by_ref().count(), 3);
+        assert_eq!(ranges.len(), 0);
+        assert_eq!(ranges.to_string(), "");
+        assert_eq!(compress_ports(0..=u16::MAX).len(), 1);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_compress_chars_bounds() {
+        let chars = ['\u{10FFFF}', '\u{0}', '\u{D7FE}', '\u{E001}', '\u{10FFFE}'];
+        let ranges: Vec<_> = compress_chars(chars.iter().copied()).collect();
+        assert_eq!(
+            ranges,
+            vec![
+                '\u{0}'..='\u{0}',
+                '\u{D7FE}'..='\u{D7FE}',
+                '\u{E001}'..='\u{E001}',
+                '\u{10FFFE}'..='\u{10FFFF}'
+            ]
+        );
+
+        let all: Vec<_> = compress_chars('\u{D000}'..='\u{F000}').collect();
+        assert_eq!(all, vec!['\u{D000}'..='\u{F000}']);
+        assert_eq!(
+            format!("{:?}", compress_chars(None)),
+            format!("{:?}", compress_chars(None).clone())
+        );
+    }
+
+    #[test]
+    fn test_compress_sorted_chars() {
+        let chars = [
+            '\u{0}',
+            '\u{0}',
+            '\u{1}',
+            '\u{D7FF}',
+            '\u{D7FF}',
+            '\u{E000}',
+            '\u{10FFFF}',
+        ];
+        let ranges: Vec<_> = compress_sorted_chars(chars.iter().copied()).collect();
+        assert_eq!(
+            ranges,
+            vec![
+                '\u{0}'..='\u{1}',
+                '\u{D7FF}'..='\u{E000}',
+                '\u{10FFFF}'..='\u{10FFFF}'
+            ]
+        );
+        assert_eq!(compress_sorted_chars(None).next(), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_range_list_round_trip() {
+        use super::RangeList;
+
+        let list: RangeList<i32> = " -5--2, 0, 3 – 4 ".parse().unwrap();
+        assert_eq!(list.ranges(), &[-5..=-2, 0..=0, 3..=4]);
+        assert_eq!(list.to_string(), "-5--2,0,3-4");
+        assert_eq!(list.to_string().parse::<RangeList<i32>>(), Ok(list));
+        assert_eq!("".parse::<RangeList<i32>>(), Ok(RangeList::new()));
+
+        let error = "1-2,x".parse::<RangeList<u8>>().unwrap_err();
+        assert_eq!(error.span(), 4..5);
+    }
+
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    #[test]
+    fn test_range_list_serde() {
+        use super::RangeList;
+
+        let from_str = |json: &str| serde_json::from_str::<RangeList<u32>>(json);
+        let expected = RangeList::from(vec![1..=3, 5..=5, 7..=9]);
+        assert_eq!(from_str(r#""1-3,5,7-9""#).unwrap(), expected);
+        assert_eq!(from_str("[[1, 3], [5, 5], [7, 9]]").unwrap(), expected);
+        assert_eq!(from_str("[[1, 3], 5, [7, 9]]").unwrap(), expected);
+        assert_eq!(serde_json::to_string(&expected).unwrap(), r#""1-3,5,7-9""#);
+
+        assert!(from_str(r#""3-1""#).is_err());
+        assert!(from_str("[[3, 1]]").is_err());
+        assert!(from_str("[[1, 2, 3]]").is_err());
+    }
+
+    #[cfg(all(feature = "serde", feature = "alloc"))]
+    #[test]
+    fn test_range_list_serde_compact() {
+        use super::RangeList;
+        use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+        let list = RangeList::<u32>::from(vec![1..=3, 5..=5]);
+        assert_tokens(
+            &list.compact(),
+            &[
+                Token::Seq { len: Some(2) },
+                Token::Tuple { len: 2 },
+                Token::U32(1),
+                Token::U32(3),
+                Token::TupleEnd,
+                Token::Tuple { len: 2 },
+                Token::U32(5),
+                Token::U32(5),
+                Token::TupleEnd,
+                Token::SeqEnd,
+            ],
+        );
+        assert_de_tokens_error::<serde_test::Compact<RangeList<u32>>>(
+            &[Token::Seq { len: Some(1) }, Token::U32(5)],
+            "invalid type: integer `5`, expected a tuple of size 2",
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{Connector, SpansBy};
+use core::iter::FusedIterator;
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Converts `self` into an iterator over the first item of each span.
+    ///
+    /// The other items of each span are discarded.
+    /// With [`spans_by`][crate::Spans::spans_by], this is like `Vec::dedup_by`, except that it works on any iterator and the connector compares each item with the previous item rather than with the first item of its span.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let firsts: Vec<i32> = vec
+    ///     .into_iter()
+    ///     .spans_by_key(|&x| x, |a, b| a + 1 == b)
+    ///     .span_firsts()
+    ///     .collect();
+    ///
+    /// assert_eq!(firsts, vec![1, 5, 11]);
+    /// ```
+    pub fn span_firsts(self) -> SpanFirsts<I, F> {
+        SpanFirsts { spans: self }
+    }
+
+    /// Converts `self` into an iterator over the last item of each span.
+    ///
+    /// The other items of each span are discarded.
+    /// This is useful to get the latest item of each span, such as the latest reading of each session.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let readings = vec![(0, 1.5), (5, 2.0), (60, 0.5), (62, 0.25)];
+    /// let latest: Vec<f64> = readings
+    ///     .into_iter()
+    ///     .spans_by_key(|&(t, _)| t, |a, b| b - a <= 10)
+    ///     .span_lasts()
+    ///     .map(|(_, x)| x)
+    ///     .collect();
+    ///
+    /// assert_eq!(latest, vec![2.0, 0.25]);
+    /// ```
+    pub fn span_lasts(self) -> SpanLasts<I, F> {
+        SpanLasts { spans: self }
+    }
+}
+
+/// `SpanFirsts` is an iterator over the first item of each span of a `SpansBy`.
+///
+/// See [`SpansBy::span_firsts`] for more information.
+pub struct SpanFirsts<I: Iterator, F> {
+    /// The wrapped spans.
+    spans: SpansBy<I, F>,
+}
+
+impl<I, F> Iterator for SpanFirsts<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The rest of the span is skipped when the next span is requested.
+        self.spans.next()?.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.span_count_hint()
+    }
+}
+
+impl<I, F> FusedIterator for SpanFirsts<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+}
+
+/// `SpanLasts` is an iterator over the last item of each span of a `SpansBy`.
+///
+/// See [`SpansBy::span_lasts`] for more information.
+pub struct SpanLasts<I: Iterator, F> {
+    /// The wrapped spans.
+    spans: SpansBy<I, F>,
+}
+
+impl<I, F> Iterator for SpanLasts<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.spans.next()?.last()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.span_count_hint()
+    }
+}
+
+impl<I, F> FusedIterator for SpanLasts<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_firsts() {
+        let firsts = "aabccc"
+            .chars()
+            .spans_by(|a, b| a == b)
+            .span_firsts()
+            .collect::<String>();
+        assert_eq!(firsts, "abc");
+    }
+
+    #[test]
+    fn test_connector_calls() {
+        let mut calls = 0;
+        let count = [1, 2, 3, 5]
+            .iter()
+            .spans_by(|&&a, &&b| {
+                calls += 1;
+                a + 1 == b
+            })
+            .span_firsts()
+            .count();
+        assert_eq!((count, calls), (2, 3));
+    }
+
+    #[test]
+    fn test_lasts() {
+        let lasts = [1, 2, 5, 6, 7, 11]
+            .iter()
+            .copied()
+            .spans_by_key(|&x| x, |a, b| a + 1 == b)
+            .span_lasts()
+            .collect::<Vec<_>>();
+        assert_eq!(lasts, vec![2, 7, 11]);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::Connector;
+use core::ops::Sub;
+
+/// `Float` provides the arithmetic of primitive floating-point types used by the floating-point conveniences of this crate.
+///
+/// `Float` is implemented for [`f32`] and [`f64`].
+pub trait Float: Copy + PartialOrd + Sub<Output = Self> {}
+
+impl Float for f32 {}
+
+impl Float for f64 {}
+
+/// `ByDelta` is a [`Connector`] that connects floating-point numbers differing by at most a maximum delta.
+///
+/// See [`Spans::spans_by_delta`][crate::Spans::spans_by_delta] for more information.
+#[derive(Clone)]
+pub struct ByDelta<T> {
+    /// The maximum absolute difference between two connected numbers.
+    eps: T,
+}
+
+impl<T> ByDelta<T> {
+    /// Creates a new `ByDelta` connector.
+    pub(crate) fn new(eps: T) -> Self {
+        ByDelta { eps }
+    }
+}
+
+impl<T: Float> Connector<T> for ByDelta<T> {
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        // Both comparisons are `false` if any operand is NaN or if the difference is NaN.
+        *next - *prev <= self.eps && *prev - *next <= self.eps
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_delta() {
+        let spans = [0.0, 0.5, 1.0, 2.0, 1.75, -1.0]
+            .iter()
+            .copied()
+            .spans_by_delta(0.5)
+            .collect_spans();
+        assert_eq!(
+            spans,
+            vec![vec![0.0, 0.5, 1.0], vec![2.0, 1.75], vec![-1.0]]
+        );
+    }
+
+    #[test]
+    fn test_nan() {
+        let spans = [1.0f32, f32::NAN, f32::NAN, 1.0, 1.0]
+            .iter()
+            .copied()
+            .spans_by_delta(1.0)
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![1, 1, 1, 2]);
+    }
+
+    #[test]
+    fn test_infinity() {
+        let spans = [f64::INFINITY, f64::INFINITY, 1e300]
+            .iter()
+            .copied()
+            .spans_by_delta(f64::MAX)
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![1, 1, 1]);
+    }
+}
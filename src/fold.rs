@@ -0,0 +1,181 @@
+use crate::{Connector, SpansBy};
+use core::iter::FusedIterator;
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Converts `self` into an iterator that folds each span into a single value.
+    ///
+    /// For each span, `init` is called to create the initial accumulator, which is then folded with every item of the span using `fold`.
+    /// The final accumulator of each span is yielded by the returned [`FoldSpans`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let sums: Vec<i32> = vec
+    ///     .iter()
+    ///     .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+    ///     .fold_spans(|| 0, |sum, x| sum + x)
+    ///     .collect();
+    ///
+    /// assert_eq!(sums, vec![3, 18, 11]);
+    /// ```
+    pub fn fold_spans<B, N, G>(self, init: N, fold: G) -> FoldSpans<I, F, N, G>
+    where
+        N: FnMut() -> B,
+        G: FnMut(B, I::Item) -> B,
+    {
+        FoldSpans {
+            spans: self,
+            init,
+            fold,
+        }
+    }
+
+    /// Converts `self` into an iterator that reduces each span into a single item.
+    ///
+    /// The items of each span are reduced using `reduce`, starting with the first item of the span as the accumulator.
+    /// Since spans are non-empty, every span yields exactly one item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![4, 9, 2, -1, -5, 3];
+    /// let maxima: Vec<i32> = vec
+    ///     .into_iter()
+    ///     .spans_by_key(|&x| x >= 0, |a, b| a == b)
+    ///     .reduce_spans(i32::max)
+    ///     .collect();
+    ///
+    /// assert_eq!(maxima, vec![9, -1, 3]);
+    /// ```
+    pub fn reduce_spans<G>(self, reduce: G) -> ReduceSpans<I, F, G>
+    where
+        G: FnMut(I::Item, I::Item) -> I::Item,
+    {
+        ReduceSpans {
+            spans: self,
+            reduce,
+        }
+    }
+}
+
+/// `FoldSpans` is an iterator that folds each span of a `SpansBy` into a single value.
+///
+/// See [`SpansBy::fold_spans`] for more information.
+pub struct FoldSpans<I: Iterator, F, N, G> {
+    /// The wrapped spans.
+    spans: SpansBy<I, F>,
+    /// The function creating the initial accumulator of a span.
+    init: N,
+    /// The function folding an item into the accumulator.
+    fold: G,
+}
+
+impl<I, F, B, N, G> Iterator for FoldSpans<I, F, N, G>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+    N: FnMut() -> B,
+    G: FnMut(B, I::Item) -> B,
+{
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let span = self.spans.next()?;
+        Some(span.fold((self.init)(), &mut self.fold))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.span_count_hint()
+    }
+}
+
+impl<I, F, B, N, G> FusedIterator for FoldSpans<I, F, N, G>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+    N: FnMut() -> B,
+    G: FnMut(B, I::Item) -> B,
+{
+}
+
+/// `ReduceSpans` is an iterator that reduces each span of a `SpansBy` into a single item.
+///
+/// See [`SpansBy::reduce_spans`] for more information.
+pub struct ReduceSpans<I: Iterator, F, G> {
+    /// The wrapped spans.
+    spans: SpansBy<I, F>,
+    /// The function reducing two items into one.
+    reduce: G,
+}
+
+impl<I, F, G> Iterator for ReduceSpans<I, F, G>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+    G: FnMut(I::Item, I::Item) -> I::Item,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.spans.next()?.reduce(&mut self.reduce)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.span_count_hint()
+    }
+}
+
+impl<I, F, G> FusedIterator for ReduceSpans<I, F, G>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+    G: FnMut(I::Item, I::Item) -> I::Item,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_fold_empty() {
+        let mut spans = []
+            .iter()
+            .spans_by_key_change(|&&x: &&u8| x)
+            .fold_spans(|| 0, |count, _| count + 1);
+        assert_eq!(spans.next(), None);
+    }
+
+    #[test]
+    fn test_fold_min_max() {
+        let spans = [3, 1, 2, 10, 30, 20, 4]
+            .iter()
+            .spans_by_key(|&&x| x >= 10, |a, b| a == b);
+        let min_max = spans
+            .fold_spans(
+                || (u32::MAX, u32::MIN),
+                |(min, max), &x| (min.min(x), max.max(x)),
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(min_max, vec![(1, 3), (10, 30), (4, 4)]);
+    }
+
+    #[test]
+    fn test_reduce() {
+        let spans = ["a", "b", "cc", "dd", "e"]
+            .iter()
+            .map(|x| x.to_string())
+            .spans_by_key(|x| x.len(), |a, b| a == b);
+        let joined = spans.reduce_spans(|a, b| a + &b).collect::<Vec<_>>();
+        assert_eq!(joined, vec!["ab", "ccdd", "e"]);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{ByKey, Integer, SpanKeyRanges, SpansBy};
+use core::iter::FusedIterator;
+use core::ops::RangeInclusive;
+
+impl<I, K, C, F> SpansBy<I, ByKey<K, C, F>>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Integer,
+    F: FnMut(C, C) -> bool,
+{
+    /// Converts `self` into an iterator over the key ranges between spans.
+    ///
+    /// The returned [`Gaps`] yields the range of keys missing between the last key of a span and the first key of the following span.
+    /// Keys are expected to increase from span to span; if the first key of a span is not larger than the last key of the previous span plus one, no gap is yielded for the two spans.
+    /// The keys are made as for [`SpansBy::span_key_ranges`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 9, 10];
+    /// let gaps: Vec<_> = vec
+    ///     .iter()
+    ///     .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+    ///     .gaps()
+    ///     .collect();
+    ///
+    /// assert_eq!(gaps, vec![3..=4, 7..=8]);
+    /// ```
+    pub fn gaps(self) -> Gaps<I, K, C, F> {
+        Gaps {
+            ranges: self.span_key_ranges(),
+            prev_end: None,
+        }
+    }
+}
+
+/// `Gaps` is an iterator over the key ranges between the spans of a `SpansBy`.
+///
+/// See [`SpansBy::gaps`] for more information.
+pub struct Gaps<I: Iterator, K, C, F> {
+    /// The key ranges of the wrapped spans.
+    ranges: SpanKeyRanges<I, K, C, F>,
+    /// The last key of the previous span.
+    prev_end: Option<C>,
+}
+
+impl<I, K, C, F> Iterator for Gaps<I, K, C, F>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Integer,
+    F: FnMut(C, C) -> bool,
+{
+    type Item = RangeInclusive<C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (start, end) = self.ranges.next()?.into_inner();
+
+            if let Some(prev_end) = self.prev_end.replace(end) {
+                if let (Some(gap_start), Some(gap_end)) =
+                    (prev_end.checked_next(), start.checked_prev())
+                {
+                    if gap_start <= gap_end {
+                        return Some(gap_start..=gap_end);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<I, K, C, F> FusedIterator for Gaps<I, K, C, F>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Integer,
+    F: FnMut(C, C) -> bool,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_no_gaps() {
+        let mut gaps = [1, 2, 3]
+            .iter()
+            .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+            .gaps();
+        assert_eq!(gaps.next(), None);
+    }
+
+    #[test]
+    fn test_single_gaps() {
+        let gaps = [1u8, 3, 5, 6, 8]
+            .iter()
+            .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+            .gaps()
+            .collect::<Vec<_>>();
+        assert_eq!(gaps, vec![2..=2, 4..=4, 7..=7]);
+    }
+
+    #[test]
+    fn test_bounds_of_type() {
+        let gaps = [0u8, 255]
+            .iter()
+            .spans_by_key(|&&x| x, |a, b| a.checked_add(1) == Some(b))
+            .gaps()
+            .collect::<Vec<_>>();
+        assert_eq!(gaps, vec![1..=254]);
+    }
+
+    #[test]
+    fn test_unordered_spans() {
+        let gaps = [5, 6, 1, 2, 9]
+            .iter()
+            .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+            .gaps()
+            .collect::<Vec<_>>();
+        assert_eq!(gaps, vec![3..=8]);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{Connector, SpansBy};
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Converts `self` into an iterator over the index range of each span.
+    ///
+    /// The returned [`IndexRanges`] yields the range of positions that the items of a span had in the original iterator.
+    /// Positions are counted from the start of the original iterator, as with [`Span::start_index`][crate::Span::start_index], even if some spans were taken from `self` before.
+    /// If the current span was partially consumed, its remaining items are skipped.
+    /// The items themselves are discarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let ranges: Vec<_> = vec
+    ///     .iter()
+    ///     .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+    ///     .index_ranges()
+    ///     .collect();
+    ///
+    /// assert_eq!(ranges, vec![0..2, 2..5, 5..6]);
+    /// assert_eq!(&vec[ranges[1].clone()], &[5, 6, 7]);
+    /// ```
+    pub fn index_ranges(self) -> IndexRanges<I, F> {
+        IndexRanges { spans: self }
+    }
+
+    /// Converts `self` into an iterator over the index at which each span begins.
+    ///
+    /// The returned [`Boundaries`] yields the position that the first item of a span had in the original iterator.
+    /// Like the ranges of [`index_ranges`][SpansBy::index_ranges], positions are counted from the start of the original iterator.
+    /// If no span was taken from `self` before and the iterator is non-empty, the first boundary is thus `0`.
+    /// The items themselves are discarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let boundaries: Vec<_> = vec
+    ///     .iter()
+    ///     .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+    ///     .boundaries()
+    ///     .collect();
+    ///
+    /// assert_eq!(boundaries, vec![0, 2, 5]);
+    /// ```
+    pub fn boundaries(self) -> Boundaries<I, F> {
+        Boundaries {
+            ranges: self.index_ranges(),
+        }
+    }
+}
+
+/// `IndexRanges` is an iterator over the index range of each span of a `SpansBy`.
+///
+/// See [`SpansBy::index_ranges`] for more information.
+pub struct IndexRanges<I: Iterator, F> {
+    /// The wrapped spans.
+    spans: SpansBy<I, F>,
+}
+
+impl<I, F> Iterator for IndexRanges<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = {
+            let mut span = self.spans.next()?;
+            span.skip_rest();
+            span.start_index()
+        };
+
+        // Once the span is skipped, the position is the index of the item following its last item.
+        Some(start..self.spans.position)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.span_count_hint()
+    }
+}
+
+impl<I, F> FusedIterator for IndexRanges<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+}
+
+/// `Boundaries` is an iterator over the index at which each span of a `SpansBy` begins.
+///
+/// See [`SpansBy::boundaries`] for more information.
+pub struct Boundaries<I: Iterator, F> {
+    /// The index ranges of the wrapped spans.
+    ranges: IndexRanges<I, F>,
+}
+
+impl<I, F> Iterator for Boundaries<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ranges.next().map(|range| range.start)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ranges.size_hint()
+    }
+}
+
+impl<I, F> FusedIterator for Boundaries<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_empty() {
+        let mut ranges = "".chars().spans_by(|a, b| a == b).index_ranges();
+        assert_eq!(ranges.next(), None);
+    }
+
+    #[test]
+    fn test_index_ranges() {
+        let ranges = "aabccc"
+            .chars()
+            .spans_by(|a, b| a == b)
+            .index_ranges()
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![0..2, 2..3, 3..6]);
+    }
+
+    #[test]
+    fn test_index_ranges_after_consumed_spans() {
+        let vec = [1, 2, 5, 6, 7, 11];
+        let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+        assert_eq!(spans.next().unwrap().count(), 2);
+        assert_eq!(spans.index_ranges().collect::<Vec<_>>(), vec![2..5, 5..6]);
+
+        // The rest of a partially consumed span is skipped and still counted.
+        let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+        assert_eq!(spans.next().unwrap().next(), Some(&1));
+        assert_eq!(spans.index_ranges().collect::<Vec<_>>(), vec![2..5, 5..6]);
+    }
+
+    #[test]
+    fn test_boundaries() {
+        let boundaries = "aabccc"
+            .chars()
+            .spans_by(|a, b| a == b)
+            .boundaries()
+            .collect::<Vec<_>>();
+        assert_eq!(boundaries, vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn test_boundaries_after_consumed_spans() {
+        let mut spans = "aabccc".chars().spans_by(|a, b| a == b);
+        assert_eq!(spans.next().unwrap().next(), Some('a'));
+        assert_eq!(spans.boundaries().collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_boundaries() {
+        let boundaries = "aabccc"
+            .chars()
+            .spans_by(|a, b| a == b)
+            .boundaries()
+            .collect::<Vec<_>>();
+        let json = serde_json::to_string(&boundaries).unwrap();
+        assert_eq!(json, "[0,2,3]");
+        assert_eq!(
+            serde_json::from_str::<Vec<usize>>(&json).unwrap(),
+            boundaries
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use crate::Connector;
+
+/// `Integer` provides the stepping operations of primitive integer types used by the integer conveniences of this crate.
+///
+/// `Integer` is implemented for all primitive integer types.
+pub trait Integer: Copy + Ord {
+    /// Returns the integer following `self`, or `None` if `self` is the maximum value.
+    fn checked_next(self) -> Option<Self>;
+
+    /// Returns the integer preceding `self`, or `None` if `self` is the minimum value.
+    fn checked_prev(self) -> Option<Self>;
+
+    /// Returns the integer following `self`, wrapping around to the minimum value if `self` is the maximum value.
+    fn wrapping_next(self) -> Self;
+
+    /// Returns `self + other`, or `None` if the addition overflows.
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    /// Returns `self - other`, or `None` if the subtraction overflows.
+    fn checked_sub(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn checked_next(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn checked_prev(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn wrapping_next(self) -> Self {
+                    self.wrapping_add(1)
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Returns a copy of `x`.
+pub(crate) fn identity<T: Copy>(x: &T) -> T {
+    *x
+}
+
+/// Returns whether `b` directly follows `a`.
+pub(crate) fn are_consecutive<T: Integer>(a: T, b: T) -> bool {
+    a.checked_next() == Some(b)
+}
+
+/// Returns whether `b` directly follows `a`, wrapping around from the maximum to the minimum value.
+///
+/// This function can be used as a connector for [`Spans::spans_by_key`][crate::Spans::spans_by_key] to find spans of consecutive sequence numbers, ring buffer indices, or other values that wrap around.
+///
+/// # Example
+///
+/// ```
+/// use spans::{are_wrapping_consecutive, Spans};
+///
+/// let packets = vec![(254u8, "a"), (255, "b"), (0, "c"), (1, "d"), (7, "e")];
+/// let lengths: Vec<_> = packets
+///     .iter()
+///     .spans_by_key(|&&(seq, _)| seq, are_wrapping_consecutive)
+///     .map_spans(|span| span.count())
+///     .collect();
+///
+/// assert_eq!(lengths, vec![4, 1]);
+/// ```
+pub fn are_wrapping_consecutive<T: Integer>(a: T, b: T) -> bool {
+    a.wrapping_next() == b
+}
+
+/// `ByStride` is a [`Connector`] that connects integers differing by a fixed step.
+///
+/// See [`Spans::spans_by_stride`][crate::Spans::spans_by_stride] and [`Spans::spans_by_descending_stride`][crate::Spans::spans_by_descending_stride] for more information.
+#[derive(Clone)]
+pub struct ByStride<T> {
+    /// The difference between two connected integers.
+    step: T,
+    /// Whether `step` is subtracted from, rather than added to, an integer to get the integer connected to it.
+    is_descending: bool,
+}
+
+impl<T> ByStride<T> {
+    /// Creates a new `ByStride` connector.
+    pub(crate) fn new(step: T, is_descending: bool) -> Self {
+        ByStride {
+            step,
+            is_descending,
+        }
+    }
+}
+
+impl<T: Integer> Connector<T> for ByStride<T> {
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        let expected = if self.is_descending {
+            prev.checked_sub(self.step)
+        } else {
+            prev.checked_add(self.step)
+        };
+
+        expected == Some(*next)
+    }
+}
+
+/// `ByMaxGap` is a [`Connector`] that connects integers differing by at most a maximum gap.
+///
+/// See [`Spans::spans_with_max_gap`][crate::Spans::spans_with_max_gap] for more information.
+#[derive(Clone)]
+pub struct ByMaxGap<T> {
+    /// The maximum difference between two connected integers.
+    gap: T,
+}
+
+impl<T> ByMaxGap<T> {
+    /// Creates a new `ByMaxGap` connector.
+    pub(crate) fn new(gap: T) -> Self {
+        ByMaxGap { gap }
+    }
+}
+
+impl<T: Integer> Connector<T> for ByMaxGap<T> {
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        let distance = if prev <= next {
+            next.checked_sub(*prev)
+        } else {
+            prev.checked_sub(*next)
+        };
+
+        // A distance that overflows `T` is larger than any gap.
+        distance.is_some_and(|distance| distance <= self.gap)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_stride_zero() {
+        let spans = [1, 1, 2, 2, 2]
+            .iter()
+            .copied()
+            .spans_by_stride(0)
+            .collect_spans();
+        assert_eq!(spans, vec![vec![1, 1], vec![2, 2, 2]]);
+    }
+
+    #[test]
+    fn test_stride_negative() {
+        let spans = [10i8, 7, 4, 1, -2, 5, 2]
+            .iter()
+            .copied()
+            .spans_by_stride(-3)
+            .collect_spans();
+        assert_eq!(spans, vec![vec![10, 7, 4, 1, -2], vec![5, 2]]);
+    }
+
+    #[test]
+    fn test_descending_stride_unsigned() {
+        let spans = [20u32, 15, 10, 5, 0, 7, 2]
+            .iter()
+            .copied()
+            .spans_by_descending_stride(5)
+            .collect_spans();
+        assert_eq!(spans, vec![vec![20, 15, 10, 5, 0], vec![7, 2]]);
+    }
+
+    #[test]
+    fn test_stride_overflow() {
+        let spans = [u8::MAX - 10, u8::MAX, 9]
+            .iter()
+            .copied()
+            .spans_by_stride(10)
+            .collect_spans();
+        assert_eq!(spans, vec![vec![u8::MAX - 10, u8::MAX], vec![9]]);
+    }
+
+    #[test]
+    fn test_max_gap() {
+        let spans = [1, 2, 4, 9, 10, 13]
+            .iter()
+            .copied()
+            .spans_with_max_gap(2)
+            .collect_spans();
+        assert_eq!(spans, vec![vec![1, 2, 4], vec![9, 10], vec![13]]);
+    }
+
+    #[test]
+    fn test_max_gap_descending() {
+        let spans = [9, 8, 6, 1, 0]
+            .iter()
+            .copied()
+            .spans_with_max_gap(2)
+            .collect_spans();
+        assert_eq!(spans, vec![vec![9, 8, 6], vec![1, 0]]);
+    }
+
+    #[test]
+    fn test_max_gap_overflow() {
+        let spans = [i8::MIN, i8::MAX, i8::MAX]
+            .iter()
+            .copied()
+            .spans_with_max_gap(i8::MAX)
+            .collect_spans();
+        assert_eq!(spans, vec![vec![i8::MIN], vec![i8::MAX, i8::MAX]]);
+    }
+
+    #[test]
+    fn test_wrapping_consecutive_spans() {
+        let spans = [126i8, 127, -128, -127, 0]
+            .iter()
+            .copied()
+            .wrapping_consecutive_spans()
+            .collect_spans();
+        assert_eq!(spans, vec![vec![126, 127, -128, -127], vec![0]]);
+    }
+
+    #[test]
+    fn test_wrapping_full_cycle() {
+        let spans = (0..=u8::MAX)
+            .chain(0..=1)
+            .wrapping_consecutive_spans()
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![258]);
+    }
+}
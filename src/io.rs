@@ -0,0 +1,172 @@
+//! Helpers for splitting the lines of a [`BufRead`] into contiguous spans.
+//!
+//! Use [`line_spans_by_key`] to group streamed lines, for example log lines by their timestamp prefix:
+//!
+//! ```
+//! use std::io::Cursor;
+//!
+//! let log = Cursor::new("10:00 start\n10:00 load\n10:01 run\n");
+//! let minutes: Vec<usize> = spans::io::line_spans_by_key(log, |line| line[..5].to_owned(), |a, b| a == b)
+//!     .map_spans(|span| span.count())
+//!     .collect();
+//!
+//! assert_eq!(minutes, vec![2, 1]);
+//! ```
+//!
+//! The items of the spans are the [`io::Result`]s of [`BufRead::lines`].
+//! A line that could not be read ends the current span and is yielded as a span of its own, such that the error is surfaced to the caller.
+//! Whether reading continues after an error depends on the reader.
+
+use crate::{ByKey, Connector, SpansBy};
+use std::io::{self, BufRead, Lines};
+use std::string::String;
+
+/// Splits the lines of `reader` into contiguous spans by comparing adjacent lines.
+///
+/// This is like [`Spans::spans_by`][crate::Spans::spans_by] for [`BufRead::lines`], except that the connector is only called with lines that were read successfully.
+/// See the [module documentation][self] for how errors are handled.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// let text = Cursor::new("a\n  b\n  c\nd\n");
+/// let spans = spans::io::line_spans_by(text, |_, b| b.starts_with(' '))
+///     .map_spans(|span| span.collect::<Result<Vec<_>, _>>())
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(spans, vec![vec!["a", "  b", "  c"], vec!["d"]]);
+/// ```
+pub fn line_spans_by<R, F>(reader: R, are_connected: F) -> SpansBy<Lines<R>, ByLine<F>>
+where
+    R: BufRead,
+    F: FnMut(&String, &String) -> bool,
+{
+    SpansBy::new(reader.lines(), ByLine::new(are_connected))
+}
+
+/// Splits the lines of `reader` into contiguous spans by comparing the keys of adjacent lines.
+///
+/// This is like [`Spans::spans_by_key`][crate::Spans::spans_by_key] for [`BufRead::lines`], except that `key` is only called with lines that were read successfully.
+/// See the [module documentation][self] for how errors are handled.
+pub fn line_spans_by_key<R, K, C, F>(
+    reader: R,
+    key: K,
+    are_connected: F,
+) -> SpansBy<Lines<R>, ByLine<ByKey<K, C, F>>>
+where
+    R: BufRead,
+    K: FnMut(&String) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    SpansBy::new(reader.lines(), ByLine::new(ByKey::new(key, are_connected)))
+}
+
+/// `ByLine` is a [`Connector`] for the results of reading lines that connects lines using another connector.
+///
+/// A failed read is never connected to an adjacent result.
+/// See [`line_spans_by`] and [`line_spans_by_key`] for more information.
+#[derive(Clone)]
+pub struct ByLine<F> {
+    /// The connector for lines that were read successfully.
+    connector: F,
+}
+
+impl<F> ByLine<F> {
+    /// Creates a new `ByLine` connector.
+    fn new(connector: F) -> Self {
+        ByLine { connector }
+    }
+}
+
+impl<F> Connector<io::Result<String>> for ByLine<F>
+where
+    F: Connector<String>,
+{
+    fn are_connected(&mut self, prev: &io::Result<String>, next: &io::Result<String>) -> bool {
+        match (prev, next) {
+            (Ok(prev), Ok(next)) => self.connector.are_connected(prev, next),
+            _ => {
+                // The wrapped connector did not observe this pair, so the state it keeps about the previous line is stale.
+                self.connector.reset();
+                false
+            }
+        }
+    }
+
+    fn start_span(&mut self, first: &io::Result<String>) {
+        if let Ok(first) = first {
+            self.connector.start_span(first);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.connector.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, BufReader, Cursor, Read};
+
+    /// A reader that yields invalid UTF-8 between two valid chunks.
+    struct Chunks(Vec<&'static [u8]>);
+
+    impl Read for Chunks {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() {
+                return Ok(0);
+            }
+            let chunk = self.0.remove(0);
+            buf[..chunk.len()].copy_from_slice(chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(super::line_spans_by(Cursor::new(""), |_, _| true)
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn test_error_ends_span() {
+        let reader = BufReader::new(Chunks(vec![b"a1\na2\n", b"\xff\n", b"a3\nb1\n"]));
+        let spans: Vec<Vec<Result<String, io::ErrorKind>>> =
+            super::line_spans_by_key(reader, |line| line[..1].to_owned(), |a, b| a == b)
+                .map_spans(|span| {
+                    span.map(|line| line.map_err(|error| error.kind()))
+                        .collect()
+                })
+                .collect();
+
+        assert_eq!(
+            spans,
+            vec![
+                vec![Ok("a1".to_owned()), Ok("a2".to_owned())],
+                vec![Err(io::ErrorKind::InvalidData)],
+                vec![Ok("a3".to_owned())],
+                vec![Ok("b1".to_owned())],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_terminators() {
+        let spans: Vec<Vec<String>> =
+            super::line_spans_by(Cursor::new("x\r\ny\n\nz"), |_, b| !b.is_empty())
+                .map_spans(|span| span.map(Result::unwrap).collect())
+                .collect();
+        assert_eq!(
+            spans,
+            vec![
+                vec!["x".to_owned(), "y".to_owned()],
+                vec!["".to_owned(), "z".to_owned()]
+            ]
+        );
+    }
+}
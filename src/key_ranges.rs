@@ -0,0 +1,216 @@
+use crate::{ByKey, SpansBy};
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::RangeInclusive;
+
+impl<I, K, C, F> SpansBy<I, ByKey<K, C, F>>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    /// Converts `self` into an iterator over the key range of each span.
+    ///
+    /// The returned [`SpanKeyRanges`] yields a [`RangeInclusive`] for every span, from the key of the first item to the key of the last item of the span.
+    /// Items between the first and the last item of a span are discarded.
+    /// The keys are not made again; to keep the key of the last item of each span, each key is cloned once more.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 3, 7, 8];
+    /// let ranges: Vec<_> = vec
+    ///     .iter()
+    ///     .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+    ///     .span_key_ranges()
+    ///     .collect();
+    ///
+    /// assert_eq!(ranges, vec![1..=3, 7..=8]);
+    /// ```
+    pub fn span_key_ranges(mut self) -> SpanKeyRanges<I, K, C, F> {
+        self.connector.is_tracking_last_key = true;
+        SpanKeyRanges { spans: self }
+    }
+}
+
+/// `SpanKeyRanges` is an iterator over the key range of each span of a `SpansBy`.
+///
+/// See [`SpansBy::span_key_ranges`] for more information.
+pub struct SpanKeyRanges<I: Iterator, K, C, F> {
+    /// The wrapped spans.
+    spans: SpansBy<I, ByKey<K, C, F>>,
+}
+
+impl<I, K, C, F> Clone for SpanKeyRanges<I, K, C, F>
+where
+    I: Iterator,
+    SpansBy<I, ByKey<K, C, F>>: Clone,
+{
+    fn clone(&self) -> Self {
+        SpanKeyRanges {
+            spans: self.spans.clone(),
+        }
+    }
+}
+
+impl<I, K, C, F> fmt::Debug for SpanKeyRanges<I, K, C, F>
+where
+    I: Iterator,
+    SpansBy<I, ByKey<K, C, F>>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpanKeyRanges")
+            .field("spans", &self.spans)
+            .finish()
+    }
+}
+
+impl<I, K, C, F> Iterator for SpanKeyRanges<I, K, C, F>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    type Item = RangeInclusive<C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end) = self.spans.next_span_key_bounds()?;
+        Some(start..=end)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.span_count_hint()
+    }
+}
+
+impl<I, K, C, F> FusedIterator for SpanKeyRanges<I, K, C, F>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+}
+
+/// Collects the key ranges of the spans into a [`rangemap::RangeInclusiveSet`], in which connected key ranges are merged.
+///
+/// Requires the `rangemap` feature.
+#[cfg(feature = "rangemap")]
+impl<I, K, C, F> From<SpanKeyRanges<I, K, C, F>> for rangemap::RangeInclusiveSet<C>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Ord + Clone + rangemap::StepLite,
+    F: FnMut(C, C) -> bool,
+{
+    fn from(ranges: SpanKeyRanges<I, K, C, F>) -> Self {
+        ranges.collect()
+    }
+}
+
+/// Collects the key ranges of the spans into a [`range_set_blaze::RangeSetBlaze`], in which connected key ranges are merged.
+///
+/// Requires the `range-set-blaze` feature.
+#[cfg(feature = "range-set-blaze")]
+impl<I, K, C, F> From<SpanKeyRanges<I, K, C, F>> for range_set_blaze::RangeSetBlaze<C>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: range_set_blaze::Integer,
+    F: FnMut(C, C) -> bool,
+{
+    fn from(ranges: SpanKeyRanges<I, K, C, F>) -> Self {
+        ranges.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_keys_are_made_once() {
+        let mut calls = 0;
+        let ranges = [1, 2, 3, 7, 9, 10]
+            .iter()
+            .spans_by_key(
+                |&&x| {
+                    calls += 1;
+                    x
+                },
+                |a, b| a + 1 == b,
+            )
+            .span_key_ranges()
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![1..=3, 7..=7, 9..=10]);
+        assert_eq!(calls, 6);
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut ranges = []
+            .iter()
+            .spans_by_key_change(|&&x: &&u8| x)
+            .span_key_ranges();
+        assert_eq!(ranges.next(), None);
+    }
+
+    #[test]
+    fn test_single_items() {
+        let ranges = [1, 3, 5]
+            .iter()
+            .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+            .span_key_ranges()
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![1..=1, 3..=3, 5..=5]);
+    }
+
+    #[test]
+    fn test_derived_keys() {
+        let ranges = ["a", "bb", "ccc", "eeeee", "ffffff"]
+            .iter()
+            .spans_by_key(|x| x.len(), |a, b| a + 1 == b)
+            .span_key_ranges()
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![1..=3, 5..=6]);
+    }
+
+    #[cfg(all(feature = "rangemap", feature = "range-set-blaze"))]
+    #[test]
+    fn test_into_range_sets() {
+        let ids = [1u32, 2, 3, 7, 9, 10];
+        let set = rangemap::RangeInclusiveSet::from(
+            ids.iter().copied().consecutive_spans().span_key_ranges(),
+        );
+        assert_eq!(
+            set.iter().cloned().collect::<Vec<_>>(),
+            vec![1..=3, 7..=7, 9..=10]
+        );
+        assert!(set.contains(&2) && !set.contains(&8));
+
+        let set = range_set_blaze::RangeSetBlaze::from(
+            ids.iter().copied().consecutive_spans().span_key_ranges(),
+        );
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![1..=3, 7..=7, 9..=10]);
+        assert_eq!(set.len(), 6);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let ranges = [1, 2, 3, 7, 9, 10]
+            .iter()
+            .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+            .span_key_ranges()
+            .collect::<Vec<_>>();
+        let json = serde_json::to_string(&ranges).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<core::ops::RangeInclusive<i32>>>(&json).unwrap(),
+            ranges
+        );
+    }
+}
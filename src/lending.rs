@@ -0,0 +1,80 @@
+use crate::{Connector, Span, SpansBy};
+
+impl<'lend, I, F> lender::Lending<'lend> for SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    type Lend = Span<'lend, I, F>;
+}
+
+/// `SpansBy` is a lending iterator; each span borrows its parent until it is dropped.
+///
+/// The remaining items of a span that is dropped before it is exhausted are skipped.
+///
+/// # Example
+///
+/// ```
+/// use lender::{Lend, Lender};
+/// use spans::Spans;
+///
+/// fn span_lengths<L>(mut spans: L) -> Vec<usize>
+/// where
+///     L: Lender,
+///     for<'lend> Lend<'lend, L>: Iterator,
+/// {
+///     let mut lengths = Vec::new();
+///     while let Some(span) = spans.next() {
+///         lengths.push(span.count());
+///     }
+///     lengths
+/// }
+///
+/// let vec = [1, 2, 5, 6, 7, 11];
+/// let spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+/// assert_eq!(span_lengths(spans), vec![2, 3, 1]);
+/// ```
+impl<I, F> lender::Lender for SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    lender::check_covariance!();
+
+    fn next(&mut self) -> Option<lender::Lend<'_, Self>> {
+        SpansBy::next(self)
+    }
+}
+
+impl<I, F> lender::FusedLender for SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+    use lender::Lender;
+
+    #[test]
+    fn test_lender_next() {
+        let vec = ["a", "b", "bb", "c"];
+        let mut spans = vec.iter().spans_by_key_change(|x| x.len());
+        let span = Lender::next(&mut spans).unwrap();
+        assert_eq!(span.collect::<Vec<_>>(), vec![&"a", &"b"]);
+        let span = Lender::next(&mut spans).unwrap();
+        assert_eq!(span.collect::<Vec<_>>(), vec![&"bb"]);
+        let span = Lender::next(&mut spans).unwrap();
+        assert_eq!(span.collect::<Vec<_>>(), vec![&"c"]);
+        assert!(Lender::next(&mut spans).is_none());
+    }
+
+    #[test]
+    fn test_lender_count() {
+        let vec = ["a", "b", "bb", "c"];
+        let spans = vec.iter().spans_by_key_change(|x| x.len());
+        assert_eq!(Lender::count(spans), 3);
+    }
+}
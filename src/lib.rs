@@ -29,184 +29,1800 @@
 //! ```
 //!
 //! For more information, refer to the [`spans_by_key`][Spans::spans_by_key] documentation.
+//!
+//! This crate is `no_std`.
+//! The `alloc` feature, which is enabled by default, provides the adapters that collect spans into vectors, such as `SpansBy::collect_spans`.
+//! The `std` feature provides the `io` module for splitting the lines of readers and adapters that send spans into channels.
+//! Integrations with other crates are provided behind features named after these crates, such as `bytes`, `chrono`, and `time`.
 
+#![cfg_attr(not(test), no_std)]
 #![deny(missing_docs)]
 
-use std::iter::Peekable;
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-/// `SpansBy` wraps an iterator and provides progressive access to contiguous spans of the iterator.
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::{FusedIterator, Peekable, Rev};
+use core::ops::Sub;
+
+pub mod bits;
+mod bounds;
+#[cfg(feature = "alloc")]
+mod builder;
+pub mod byte;
+#[cfg(feature = "bytes")]
+pub mod bytes;
+mod calendar;
+#[cfg(feature = "std")]
+mod channel;
+#[cfg(feature = "chrono")]
+pub mod chrono;
+pub mod compact;
+mod ends;
+mod float;
+mod fold;
+mod gaps;
+mod indices;
+mod integer;
+#[cfg(feature = "std")]
+pub mod io;
+mod key_ranges;
+#[cfg(feature = "lender")]
+mod lending;
+mod limit;
+#[cfg(feature = "alloc")]
+pub mod list;
+mod map;
+#[cfg(feature = "alloc")]
+mod merge;
+pub mod net;
+mod optional;
+#[cfg(feature = "alloc")]
+mod owned;
+#[cfg(feature = "rayon")]
+mod parallel;
+pub mod ranges;
+mod rev;
+mod rle;
+#[cfg(feature = "roaring")]
+pub mod roaring;
+mod runs;
+mod scan;
+mod session;
+pub mod slice;
+mod split;
+#[cfg(feature = "futures")]
+pub mod stream;
+#[cfg(feature = "streaming-iterator")]
+mod streaming;
+mod tagged;
+pub mod text;
+#[cfg(feature = "time")]
+pub mod time;
+#[cfg(feature = "alloc")]
+mod tolerance;
+mod transitions;
+mod transparent;
+mod try_items;
+mod try_key;
+#[cfg(feature = "unicode")]
+pub mod unicode;
+
+pub use bounds::SpanBounds;
+#[cfg(feature = "alloc")]
+pub use builder::SpanBuilder;
+pub use calendar::{consecutive_business_days, Calendar};
+pub use ends::{SpanFirsts, SpanLasts};
+pub use float::{ByDelta, Float};
+pub use fold::{FoldSpans, ReduceSpans};
+pub use gaps::Gaps;
+pub use indices::{Boundaries, IndexRanges};
+pub use integer::{are_wrapping_consecutive, ByMaxGap, ByStride, Integer};
+pub use key_ranges::SpanKeyRanges;
+pub use limit::{ByCapacity, MaxLen};
+pub use map::MapSpans;
+#[cfg(feature = "alloc")]
+pub use merge::{MergePolicy, MergeShortSpans};
+pub use optional::ByOptionalKey;
+#[cfg(feature = "alloc")]
+pub use owned::OwnedSpans;
+pub use rev::ByRevKey;
+pub use rle::{RunLengthDecode, RunLengthEncode};
+#[cfg(feature = "alloc")]
+pub use runs::{ByNaturalOrder, NaturalRuns, Run};
+pub use scan::{ByDecision, ByScan, SplitDecision};
+pub use session::BySessionGap;
+pub use split::{BySeparator, SplitBy};
+#[cfg(feature = "streaming-iterator")]
+pub use streaming::StreamingSpans;
+pub use tagged::{Element, TaggedItems, WithBoundaries};
+#[cfg(feature = "alloc")]
+pub use tolerance::TolerantSpans;
+pub use transitions::KeyTransitions;
+pub use transparent::Transparent;
+pub use try_items::{ByOk, TrySpan, TrySpans};
+pub use try_key::{ByTryKey, TrySpansBy};
+
+/// `Connector` decides whether two adjacent iterator items belong to the same span.
+///
+/// `Connector` is implemented for all closures of the form `FnMut(&T, &T) -> bool`.
+/// See [`Spans::spans_by`] and [`Spans::spans_by_key`] for more information.
+pub trait Connector<T> {
+    /// Returns `true` if `next` is part of the same span as `prev`, `false` otherwise.
+    ///
+    /// `next` is the item directly following `prev` in the iterator.
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool;
+
+    /// Called with the first item of each span when the span starts, before the item is compared to any following item.
+    ///
+    /// The default implementation does nothing.
+    fn start_span(&mut self, first: &T) {
+        let _ = first;
+    }
+
+    /// Discards any state derived from previously compared items.
+    ///
+    /// Called when the items passed to the connector are no longer adjacent, such as after [`SpansBy::get_mut`].
+    /// The next call to [`Connector::are_connected`] is treated like the first call.
+    /// The default implementation does nothing.
+    fn reset(&mut self) {}
+}
+
+impl<T, F> Connector<T> for F
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        self(prev, next)
+    }
+}
+
+/// `ByKey` is a [`Connector`] that compares items by their keys.
 ///
 /// See [`Spans::spans_by_key`] for more information.
-pub struct SpansBy<I: Iterator, K, F> {
-    /// The wrapped iterator.
-    iter: Peekable<I>,
+#[derive(Clone, Debug)]
+pub struct ByKey<K, C, F> {
     /// A function transforming an iterator item to a comparison key.
     key: K,
     /// Whether two iterator items belong to the same span as determined by their respective keys.
     are_connected: F,
+    /// The key of the item most recently passed as `next` to `Connector::are_connected`.
+    ///
+    /// `None` before the first comparison.
+    prev_key: Option<C>,
+    /// The key of the first item of the current span.
+    ///
+    /// `None` before the first span starts.
+    span_key: Option<C>,
+    /// The key of the item most recently passed as `prev` to `Connector::are_connected`, if `is_tracking_last_key` is `true`.
+    last_key: Option<C>,
+    /// Whether `last_key` is stored, which costs an additional clone per comparison.
+    is_tracking_last_key: bool,
+}
+
+impl<K, C, F> ByKey<K, C, F> {
+    /// Creates a new `ByKey` connector.
+    fn new(key: K, are_connected: F) -> Self {
+        ByKey {
+            key,
+            are_connected,
+            prev_key: None,
+            span_key: None,
+            last_key: None,
+            is_tracking_last_key: false,
+        }
+    }
+}
+
+impl<T, K, C, F> Connector<T> for ByKey<K, C, F>
+where
+    K: FnMut(&T) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        // Items are compared pairwise in iteration order, so the stored key is the key of `prev`.
+        let prev_key = match self.prev_key.take() {
+            Some(prev_key) => prev_key,
+            None => (self.key)(prev),
+        };
+        let next_key = (self.key)(next);
+
+        if self.is_tracking_last_key {
+            self.last_key = Some(prev_key.clone());
+        }
+
+        let is_connected = (self.are_connected)(prev_key, next_key.clone());
+        self.prev_key = Some(next_key);
+        is_connected
+    }
+
+    fn start_span(&mut self, first: &T) {
+        // The stored key is the key of the item most recently passed as `next`, which is the first item of the span.
+        let key = match &self.prev_key {
+            Some(key) => key.clone(),
+            None => {
+                let key = (self.key)(first);
+                self.prev_key = Some(key.clone());
+                key
+            }
+        };
+        self.span_key = Some(key);
+    }
+
+    fn reset(&mut self) {
+        self.prev_key = None;
+        self.last_key = None;
+    }
+}
+
+/// `ByKeyChange` is a [`ByKey`] connector that connects items with equal keys.
+///
+/// See [`Spans::spans_by_key_change`] for more information.
+pub type ByKeyChange<K, C> = ByKey<K, C, fn(C, C) -> bool>;
+
+/// `ByBucket` is a [`ByKey`] connector that connects items in the same bucket.
+///
+/// See [`Spans::spans_by_bucket`] for more information.
+pub type ByBucket<B, C> = ByKey<B, C, fn(C, C) -> bool>;
+
+/// `BySortedOrder` is a [`Connector`] that connects items in non-decreasing order.
+///
+/// See [`Spans::sorted_runs`] for more information.
+pub type BySortedOrder<T> = fn(&T, &T) -> bool;
+
+/// `BySortedKeys` is a [`ByKey`] connector that connects items with keys in non-decreasing order.
+///
+/// See [`Spans::sorted_runs_by_key`] for more information.
+pub type BySortedKeys<K, C> = ByKey<K, C, fn(C, C) -> bool>;
+
+/// `ByConsecutive` is a [`ByKey`] connector that connects consecutive integers.
+///
+/// See [`Spans::consecutive_spans`] and [`Spans::wrapping_consecutive_spans`] for more information.
+pub type ByConsecutive<T> = ByKey<fn(&T) -> T, T, fn(T, T) -> bool>;
+
+/// `SpansBy` wraps an iterator and provides progressive access to contiguous spans of the iterator.
+///
+/// See [`Spans::spans_by`] and [`Spans::spans_by_key`] for more information.
+pub struct SpansBy<I: Iterator, F> {
+    /// The wrapped iterator.
+    iter: Peekable<I>,
+    /// Whether two adjacent iterator items belong to the same span.
+    connector: F,
+    /// Whether the peeked item of `iter` belongs to the current span.
+    ///
+    /// `true` when a span starts and `false` once the current span is exhausted.
+    is_connected: bool,
+    /// Whether `iter` returned `None`.
+    ///
+    /// No further items are taken from `iter` once it terminated, even if it is not fused.
+    is_done: bool,
+    /// The number of spans that have been started, including skipped spans.
+    span_count: usize,
+    /// The number of items taken from `iter`, not counting items taken through `SpansBy::get_mut`.
+    position: usize,
+    /// The value of `position` when the current span started.
+    span_start: usize,
+}
+
+impl<I, F> fmt::Debug for SpansBy<I, F>
+where
+    I: Iterator + fmt::Debug,
+    I::Item: fmt::Debug,
+    F: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpansBy")
+            .field("iter", &self.iter)
+            .field("connector", &self.connector)
+            .field("is_connected", &self.is_connected)
+            .field("is_done", &self.is_done)
+            .field("span_count", &self.span_count)
+            .field("position", &self.position)
+            .field("span_start", &self.span_start)
+            .finish()
+    }
+}
+
+impl<I, F> Clone for SpansBy<I, F>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    F: Clone,
+{
+    /// Returns a copy of `self` that yields the same spans as `self` from now on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// let count = spans.clone().map_spans(|_| ()).count();
+    /// let spans = spans
+    ///     .map_spans(|span| span.collect::<Vec<_>>())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(count, spans.len());
+    /// ```
+    fn clone(&self) -> Self {
+        SpansBy {
+            iter: self.iter.clone(),
+            connector: self.connector.clone(),
+            is_connected: self.is_connected,
+            is_done: self.is_done,
+            span_count: self.span_count,
+            position: self.position,
+            span_start: self.span_start,
+        }
+    }
+}
+
+impl<I: Iterator, F> SpansBy<I, F> {
+    /// Creates a new `SpansBy` splitting `iter` using `connector`.
+    fn new(iter: I, connector: F) -> Self {
+        SpansBy {
+            iter: iter.peekable(),
+            connector,
+            is_connected: false,
+            is_done: false,
+            span_count: 0,
+            position: 0,
+            span_start: 0,
+        }
+    }
+
+    /// Unwraps `self`, returning the wrapped iterator.
+    ///
+    /// The returned [`Peekable`] yields all items that have not been yielded by a span yet, including the item peeked by `self` and the remaining items of a span that was dropped before it was exhausted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 2, 5, 6, 9];
+    /// let mut spans = vec.into_iter().spans_by_key(|&x| x, |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![1, 2]);
+    /// assert_eq!(spans.into_inner().collect::<Vec<_>>(), vec![5, 6, 9]);
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    pub fn into_inner(self) -> Peekable<I> {
+        self.iter
+    }
+
+    /// Returns the bounds on the number of remaining spans.
+    ///
+    /// The bounds are derived from the [`size_hint`][Iterator::size_hint] of the wrapped iterator, since every span has at least one item.
+    /// The lower bound is at most 1, since all remaining items may be part of a single span.
+    /// A span that is partially consumed is not counted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(spans.span_count_hint(), (1, Some(6)));
+    /// ```
+    pub fn span_count_hint(&self) -> (usize, Option<usize>) {
+        if self.is_done {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.iter.size_hint();
+
+        // The remaining items of a partially consumed span may be all remaining items.
+        let lower = if self.is_connected { 0 } else { lower.min(1) };
+        (lower, upper)
+    }
+
+    /// Returns a reference to the wrapped iterator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5];
+    /// let spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(spans.get_ref().len(), 3);
+    /// ```
+    pub fn get_ref(&self) -> &Peekable<I> {
+        &self.iter
+    }
+}
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Returns a mutable reference to the wrapped iterator.
+    ///
+    /// Since the wrapped iterator may be advanced arbitrarily, the current span ends and the connector is [reset][Connector::reset].
+    /// The next span thus starts with the next item of the wrapped iterator, regardless of whether it is connected to the previous item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 2, 3, 4, 9];
+    /// let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(spans.next()?.next(), Some(&1));
+    /// assert_eq!(spans.get_mut().next(), Some(&2));
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&3, &4]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&9]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    pub fn get_mut(&mut self) -> &mut Peekable<I> {
+        self.is_connected = false;
+        self.connector.reset();
+        &mut self.iter
+    }
 }
 
-impl<I, K, C, F> SpansBy<I, K, F>
+impl<I, F> SpansBy<I, F>
 where
     I: Iterator,
-    K: Fn(&I::Item) -> C,
-    C: Copy,
-    F: Fn(C, C) -> bool,
+    F: Connector<I::Item>,
 {
     /// Returns the next span or `None` if the iterator terminated.
     ///
+    /// The remaining items of the previous span are skipped if it was dropped before it was exhausted, such that every span starts at a span boundary.
+    /// Skipped items are still passed to the connector.
+    ///
+    /// Once `None` is returned, `None` is returned from then on, even if the wrapped iterator is not fused.
+    /// Likewise, [`Span`] and the iterators over spans implement [`FusedIterator`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5];
+    /// let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// while let Some(span) = spans.next() {
+    ///     // do something with `span`
+    ///     let count = span.count();
+    ///     assert!(count > 0); // true for all spans
+    ///     assert!(count < 3); // true for the spans in this example
+    /// }
+    /// ```
+    ///
+    /// Partially consumed spans:
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 2, 3, 7, 8];
+    /// let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(spans.next()?.next(), Some(&1));
+    /// assert_eq!(spans.next()?.next(), Some(&7));
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Span<'_, I, F>> {
+        self.skip_current_span();
+        self.start_span()?;
+        Some(Span { parent: self })
+    }
+
+    /// Skips the next span and returns whether there was a span to skip.
+    ///
+    /// This is equivalent to dropping the span returned by [`SpansBy::next`], without constructing it.
+    /// The items of the skipped span are still passed to the connector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 2, 5, 6, 9];
+    /// let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// assert!(spans.skip_span());
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&5, &6]);
+    /// assert!(spans.skip_span());
+    /// assert!(!spans.skip_span());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    pub fn skip_span(&mut self) -> bool {
+        self.skip_current_span();
+
+        if self.start_span().is_some() {
+            self.skip_current_span();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns a reference to the first item of the next span without consuming the span, or `None` if the iterator terminated.
+    ///
+    /// Like [`SpansBy::next`], this skips the remaining items of the previous span if it was dropped before it was exhausted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 2, 5, 6];
+    /// let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(spans.peek_span(), Some(&&1));
+    /// assert!(spans.skip_span());
+    /// assert_eq!(spans.peek_span(), Some(&&5));
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&5, &6]);
+    /// assert_eq!(spans.peek_span(), None);
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    pub fn peek_span(&mut self) -> Option<&I::Item> {
+        self.skip_current_span();
+
+        if self.is_at_end() {
+            return None;
+        }
+
+        self.iter.peek()
+    }
+
+    /// Returns the next item of the current span or `None` if the current span is exhausted.
+    fn next_item(&mut self) -> Option<I::Item> {
+        if !self.is_connected {
+            return None;
+        }
+
+        let item = self.iter.next()?;
+        self.position += 1;
+
+        // The next item is compared now, while `item` is still borrowable.
+        self.is_connected = match self.iter.peek() {
+            Some(next) => self.connector.are_connected(&item, next),
+            None => {
+                self.is_done = true;
+                false
+            }
+        };
+
+        Some(item)
+    }
+
+    /// Returns whether the wrapped iterator terminated.
+    ///
+    /// Once the wrapped iterator returned `None`, `true` is returned from then on, even if the wrapped iterator is not fused.
+    /// If `false` is returned, the next item of the wrapped iterator is peeked.
+    fn is_at_end(&mut self) -> bool {
+        if !self.is_done {
+            self.is_done = self.iter.peek().is_none();
+        }
+
+        self.is_done
+    }
+
+    /// Starts a span with the peeked item, or returns `None` if the iterator terminated.
+    fn start_span(&mut self) -> Option<()> {
+        if self.is_at_end() {
+            return None;
+        }
+
+        let first = self.iter.peek()?;
+        self.connector.start_span(first);
+        self.is_connected = true;
+        self.span_count += 1;
+        self.span_start = self.position;
+        Some(())
+    }
+
+    /// Skips the remaining items of the current span.
+    fn skip_current_span(&mut self) {
+        while self.next_item().is_some() {}
+    }
+}
+
+impl<I, K, C, F> SpansBy<I, ByKey<K, C, F>>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    /// Returns the key of the first item of the next span without consuming the span, or `None` if the iterator terminated.
+    ///
+    /// The key is not made again when the span is iterated; `key` is called with each item at most once, as with [`SpansBy::next`].
+    /// See [`SpansBy::peek_span`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec!["apple", "avocado", "banana"];
+    /// let mut spans = vec.into_iter().spans_by_key_change(|s| s.chars().next());
+    ///
+    /// while let Some(key) = spans.peek_span_key() {
+    ///     if key == Some('b') {
+    ///         assert_eq!(spans.next()?.collect::<Vec<_>>(), vec!["banana"]);
+    ///     } else {
+    ///         spans.skip_span();
+    ///     }
+    /// }
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    pub fn peek_span_key(&mut self) -> Option<C> {
+        self.skip_current_span();
+
+        if self.is_at_end() {
+            return None;
+        }
+
+        let item = self.iter.peek()?;
+
+        // The stored key is the key of the item most recently passed as `next`, which is the peeked item.
+        let key = match &self.connector.prev_key {
+            Some(key) => key.clone(),
+            None => {
+                let key = (self.connector.key)(item);
+                self.connector.prev_key = Some(key.clone());
+                key
+            }
+        };
+        Some(key)
+    }
+
+    /// Returns the key of the first and the key of the last item of the next span, or `None` if the iterator terminated.
+    ///
+    /// The items of the span are skipped.
+    /// Requires `ByKey::is_tracking_last_key` to be set.
+    fn next_span_key_bounds(&mut self) -> Option<(C, C)> {
+        debug_assert!(self.connector.is_tracking_last_key);
+
+        let mut span = self.next()?;
+        let start = span.key().clone();
+
+        if span.skip_rest() == 1 {
+            return Some((start.clone(), start));
+        }
+
+        // A span ends either before the next item, which was compared to the last item, or at the end of the iterator, where the last item was compared as `next`.
+        let end = if self.is_done {
+            self.connector.prev_key.clone()
+        } else {
+            self.connector.last_key.clone()
+        };
+        Some((start, end?))
+    }
+}
+
+/// A `Span` is an iterator that iterates over a span of its parent iterator.
+///
+/// A span is always non-empty; at least one item is provided when iterating over a span.
+/// This is because empty spans are handled and discarded by the parent `SpansBy` iterator.
+/// If a span is dropped before it is exhausted, its remaining items are skipped by the parent.
+///
+/// See [`Spans::spans_by`] and [`Spans::spans_by_key`] for more information.
+pub struct Span<'a, I: Iterator, F> {
+    /// The parent iterator.
+    parent: &'a mut SpansBy<I, F>,
+}
+
+impl<I, F> Span<'_, I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Skips the remaining items of the span and returns the number of skipped items.
+    ///
+    /// Unlike [`Iterator::count`], this does not consume the span, such that it can still be inspected afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 2, 3, 7];
+    /// let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// let mut span = spans.next()?;
+    /// assert_eq!(span.next(), Some(&1));
+    /// assert_eq!(span.skip_rest(), 2);
+    /// assert_eq!(span.skip_rest(), 0);
+    /// assert_eq!(span.key(), &1);
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    pub fn skip_rest(&mut self) -> usize {
+        let mut count = 0;
+
+        while self.parent.next_item().is_some() {
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Returns the zero-based index of the span among all spans of its parent, including skipped spans.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// let mut report = Vec::new();
+    /// while let Some(span) = spans.next() {
+    ///     report.push(format!("run #{}: {} items", span.index(), span.count()));
+    /// }
+    ///
+    /// assert_eq!(report, vec!["run #0: 2 items", "run #1: 3 items", "run #2: 1 items"]);
+    /// ```
+    pub fn index(&self) -> usize {
+        self.parent.span_count - 1
+    }
+
+    /// Returns the position that the first item of the span had in the wrapped iterator.
+    ///
+    /// Positions count all items taken from the wrapped iterator, including items of skipped spans and separators, but not items taken through [`SpansBy::get_mut`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// let mut ranges = Vec::new();
+    /// while let Some(span) = spans.next() {
+    ///     let start = span.start_index();
+    ///     ranges.push(start..start + span.count());
+    /// }
+    ///
+    /// assert_eq!(ranges, vec![0..2, 2..5, 5..6]);
+    /// ```
+    pub fn start_index(&self) -> usize {
+        self.parent.span_start
+    }
+}
+
+impl<I, K, C, F> Span<'_, I, ByKey<K, C, F>>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    /// Returns the key of the first item of the span.
+    ///
+    /// The key is available throughout the iteration of the span.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let records = vec![("fruit", "apple"), ("fruit", "pear"), ("nut", "pecan")];
+    /// let mut spans = records.into_iter().spans_by_key_change(|&(category, _)| category);
+    ///
+    /// let mut groups = Vec::new();
+    /// while let Some(span) = spans.next() {
+    ///     let category = *span.key();
+    ///     groups.push((category, span.map(|(_, name)| name).collect::<Vec<_>>()));
+    /// }
+    ///
+    /// assert_eq!(groups, vec![("fruit", vec!["apple", "pear"]), ("nut", vec!["pecan"])]);
+    /// ```
+    pub fn key(&self) -> &C {
+        self.parent
+            .connector
+            .span_key
+            .as_ref()
+            .expect("the key is set when the span starts")
+    }
+}
+
+impl<I, F> Iterator for Span<'_, I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.parent.next_item()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.parent.is_connected {
+            (1, self.parent.iter.size_hint().1)
+        } else {
+            (0, Some(0))
+        }
+    }
+}
+
+impl<I, F> FusedIterator for Span<'_, I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+}
+
+/// `Spans` provides iterator adapters for `SpansBy`.
+pub trait Spans: Iterator {
+    /// Splits the iterator into contiguous spans by comparing adjacent items.
+    ///
+    /// `are_connected` returns `true` if the two given adjacent items are part of the same span, `false` otherwise.
+    /// It is given a reference to the previous item and a reference to the current item.
+    /// `are_connected` may be a stateful [`FnMut`] closure; it is called with each pair of adjacent items in iteration order.
+    ///
+    /// # Example
+    ///
+    /// Create spans for strings starting with the same letter:
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec!["apple", "avocado", "banana", "cherry", "coconut"];
+    /// let mut spans = vec.into_iter().spans_by(|a, b| a[..1] == b[..1]);
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec!["apple", "avocado"]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec!["banana"]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec!["cherry", "coconut"]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by<F>(self, are_connected: F) -> SpansBy<Self, F>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+        Self: Sized,
+    {
+        SpansBy::new(self, are_connected)
+    }
+
+    /// Splits the iterator into contiguous spans by comparing adjacent items, tolerating up to `max_outliers` consecutive disconnected items within a span.
+    ///
+    /// An item that is not connected to the previous item is an outlier if one of the following `max_outliers` items is connected to the last item of the span that is not an outlier.
+    /// Outliers stay in the current span, along with the connected item following them.
+    /// Otherwise, the item starts a new span; outliers at the end of the iterator thus start a new span as well.
+    /// With a `max_outliers` of zero, this is equivalent to [`Spans::spans_by`].
+    ///
+    /// To look ahead, up to `max_outliers + 1` items are buffered and each span is collected into a [`Vec`].
+    /// `are_connected` is given the last item of the span that is not an outlier and a following item; it may be called more than once with the same item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let readings = vec![20, 21, 99, 21, 22, 40, 41];
+    /// let spans: Vec<_> = readings
+    ///     .into_iter()
+    ///     .spans_by_with_tolerance(1, |a: &i32, b: &i32| (a - b).abs() <= 2)
+    ///     .collect();
+    ///
+    /// assert_eq!(spans, vec![vec![20, 21, 99, 21, 22], vec![40, 41]]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn spans_by_with_tolerance<F>(
+        self,
+        max_outliers: usize,
+        are_connected: F,
+    ) -> TolerantSpans<Self, F>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+        Self: Sized,
+    {
+        TolerantSpans::new(self, max_outliers, are_connected)
+    }
+
+    /// Splits the iterator into contiguous spans separated by the items for which `is_separator` returns `true`.
+    ///
+    /// Separators are consumed and not yielded by any span, like the separators of [`str::split`].
+    /// Unlike `str::split`, no empty spans are yielded; leading, trailing, and repeated separators are skipped.
+    /// `is_separator` is called with each item at least once; it may be called twice with separators.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 2, 0, 3, 0, 0, 4, 5, 0];
+    /// let mut spans = vec.into_iter().split_by(|&x| x == 0);
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![1, 2]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![3]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![4, 5]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn split_by<P>(self, is_separator: P) -> SplitBy<Self, P>
+    where
+        P: FnMut(&Self::Item) -> bool,
+        Self: Sized,
+    {
+        SplitBy::new(self, is_separator)
+    }
+
+    /// Splits the iterator into contiguous spans.
+    ///
+    /// `are_connected` returns `true` if the two given adjacent items are part of the same span, `false` otherwise.
+    /// Items are not compared directly; instead, a key is made for each item using the `key` function.
+    /// `are_connected` is given the key of the previous item and the key of the current item.
+    ///
+    /// Both `key` and `are_connected` may be stateful [`FnMut`] closures.
+    /// `key` is called exactly once with each item, in iteration order, and `are_connected` is called with each pair of adjacent keys in iteration order.
+    ///
+    /// Keys are owned values and need only implement [`Clone`].
+    /// The key of each item is cloned once, such that it can be passed to `are_connected` both as the current key and, in the following comparison, as the previous key.
+    /// The key of the first item of each span is cloned once more, such that it is available through [`Span::key`].
+    ///
+    /// # Examples
+    ///
+    /// Create spans for items increasing in value by 1:
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11, 13, 14, 15];
+    /// let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&1, &2]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&5, &6, &7]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&11]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&13, &14, &15]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    ///
+    /// Create spans for strings of the same length:
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec!["abc", "run", "tag", "go", "be", "ring", "zip", "zap", "put"];
+    /// let mut spans = vec.iter().spans_by_key(|x| x.len(), |a, b| a == b);
+    ///
+    /// assert_eq!(
+    ///     spans.next()?.collect::<Vec<_>>(),
+    ///     vec![&"abc", &"run", &"tag"]
+    /// );
+    /// assert_eq!(
+    ///     spans.next()?.collect::<Vec<_>>(),
+    ///     vec![&"go", &"be"]
+    /// );
+    /// assert_eq!(
+    ///     spans.next()?.collect::<Vec<_>>(),
+    ///     vec![&"ring"]
+    /// );
+    /// assert_eq!(
+    ///     spans.next()?.collect::<Vec<_>>(),
+    ///     vec![&"zip", &"zap", &"put"]
+    /// );
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    ///
+    /// Create spans for strings sharing a common prefix using owned keys:
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec!["main.rs", "main.o", "lib.rs", "lib.o"];
+    /// let mut spans = vec.iter().spans_by_key(
+    ///     |x| x.split('.').next().unwrap().to_string(),
+    ///     |a, b| a == b,
+    /// );
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&"main.rs", &"main.o"]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&"lib.rs", &"lib.o"]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by_key<K, C, F>(self, key: K, are_connected: F) -> SpansBy<Self, ByKey<K, C, F>>
+    where
+        K: FnMut(&Self::Item) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+        Self: Sized,
+    {
+        SpansBy::new(self, ByKey::new(key, are_connected))
+    }
+
+    /// Splits the iterator into contiguous spans, treating the iterator as circular, and collects the spans into a vector of vectors.
+    ///
+    /// This is like [`spans_by_key`][Spans::spans_by_key], except that the last item of the iterator is compared with the first item of the iterator.
+    /// If they are connected, the first span is appended to the last span, since the two spans form a single span that wraps around.
+    /// See [`SpansBy::collect_circular_spans`] for more information.
+    ///
+    /// # Example
+    ///
+    /// Create spans of consecutive hours of a day:
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let hours = vec![0, 1, 2, 10, 11, 22, 23];
+    /// let spans = hours
+    ///     .into_iter()
+    ///     .circular_spans_by_key(|&x| x, |a, b| (a + 1) % 24 == b);
+    ///
+    /// assert_eq!(spans, vec![vec![10, 11], vec![22, 23, 0, 1, 2]]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn circular_spans_by_key<K, C, F>(self, key: K, are_connected: F) -> Vec<Vec<Self::Item>>
+    where
+        K: FnMut(&Self::Item) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+        Self: Sized,
+    {
+        self.spans_by_key(key, are_connected)
+            .collect_circular_spans_by_key()
+    }
+
+    /// Splits the iterator into contiguous spans, starting from the back of the iterator.
+    ///
+    /// This is like [`spans_by_key`][Spans::spans_by_key] for the reversed iterator: the last span is returned first and the items of each span are yielded from back to front.
+    /// However, `are_connected` is given the keys of adjacent items in the order of the original iterator, such that the same `are_connected` makes the same spans as with `spans_by_key`.
+    /// Only the items of the returned spans are visited; items preceding them are not.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let mut spans = vec.iter().rspans_by_key(|&&x| x, |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&11]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&7, &6, &5]);
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn rspans_by_key<K, C, F>(
+        self,
+        key: K,
+        are_connected: F,
+    ) -> SpansBy<Rev<Self>, ByRevKey<K, C, F>>
+    where
+        K: FnMut(&Self::Item) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+        Self: DoubleEndedIterator + Sized,
+    {
+        SpansBy::new(self.rev(), ByRevKey::new(key, are_connected))
+    }
+
+    /// Splits the iterator into contiguous spans of items with equal keys.
+    ///
+    /// A new span starts whenever the key of an item differs from the key of the previous item.
+    /// This is equivalent to calling [`spans_by_key`][Spans::spans_by_key] with `|a, b| a == b`.
+    ///
+    /// To split by a bucket, such as the calendar day of a timestamp, use [`spans_by_bucket`][Spans::spans_by_bucket].
+    ///
+    /// # Example
+    ///
+    /// Create spans for strings of the same length:
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec!["abc", "run", "go", "be", "ring"];
+    /// let mut spans = vec.iter().spans_by_key_change(|x| x.len());
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&"abc", &"run"]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&"go", &"be"]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&"ring"]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by_key_change<K, C>(self, key: K) -> SpansBy<Self, ByKeyChange<K, C>>
+    where
+        K: FnMut(&Self::Item) -> C,
+        C: Clone + PartialEq,
+        Self: Sized,
+    {
+        self.spans_by_key(key, are_equal)
+    }
+
+    /// Splits the iterator into contiguous spans of items in the same bucket.
+    ///
+    /// `bucket` assigns each item to a bucket, such as the calendar day or month of a timestamp.
+    /// A new span starts whenever the bucket of an item differs from the bucket of the previous item.
+    /// Items of the same bucket that are not adjacent form separate spans.
+    /// The bucket of a span is returned by [`Span::key`].
+    /// `bucket` is called with each item once.
+    ///
+    /// With the `chrono` or the `time` feature, calendar buckets for hours, days, ISO weeks, and months are provided by the `chrono` and `time` modules.
+    /// These determine the bucket in the time zone of each date-time, such that days around changes of daylight saving time are bucketed correctly.
+    ///
+    /// # Example
+    ///
+    /// Group readings with Unix timestamps by UTC day:
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let readings = vec![(86_000, 1.5), (86_399, 2.0), (86_400, 0.5), (200_000, 3.0)];
+    /// let mut days = readings.iter().spans_by_bucket(|&&(t, _)| t / 86_400);
+    ///
+    /// let day = days.next()?;
+    /// assert_eq!(*day.key(), 0);
+    /// assert_eq!(day.count(), 2);
+    /// assert_eq!(*days.next()?.key(), 1);
+    /// assert_eq!(*days.next()?.key(), 2);
+    /// assert!(days.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by_bucket<B, C>(self, bucket: B) -> SpansBy<Self, ByBucket<B, C>>
+    where
+        B: FnMut(&Self::Item) -> C,
+        C: Clone + PartialEq,
+        Self: Sized,
+    {
+        self.spans_by_key(bucket, are_equal)
+    }
+
+    /// Splits the iterator into contiguous spans by comparing the optional keys of adjacent items.
+    ///
+    /// This is like [`spans_by_key`][Spans::spans_by_key], except that `key` returns an [`Option`].
+    /// An item with the key `None` is never connected to its neighbors and thus always forms a span of its own.
+    /// `are_connected` is only called when both keys are `Some`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec!["1", "2", "x", "3", "5"];
+    /// let mut spans = vec
+    ///     .into_iter()
+    ///     .spans_by_optional_key(|s| s.parse::<i32>().ok(), |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec!["1", "2"]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec!["x"]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec!["3"]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec!["5"]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by_optional_key<K, C, F>(
+        self,
+        key: K,
+        are_connected: F,
+    ) -> SpansBy<Self, ByOptionalKey<K, C, F>>
+    where
+        K: FnMut(&Self::Item) -> Option<C>,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+        Self: Sized,
+    {
+        SpansBy::new(self, ByOptionalKey::new(key, are_connected))
+    }
+
+    /// Splits an iterator of results into contiguous spans of values, separated by errors.
+    ///
+    /// This is like [`spans_by`][Spans::spans_by] for the values of the results, except that errors are not part of any span.
+    /// An error ends the current span and is returned once by [`TrySpans::next`] instead of a span.
+    /// Iteration resumes after the error, such that a source with recoverable errors, like the lines of a reader, can be processed completely.
+    /// `are_connected` is only called with adjacent values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec!["1", "2", "x", "3", "4"];
+    /// let mut spans = vec
+    ///     .into_iter()
+    ///     .map(|s| s.parse::<i32>())
+    ///     .try_spans_by(|a, b| a + 1 == *b);
+    ///
+    /// let mut sums = Vec::new();
+    /// let mut errors = 0;
+    ///
+    /// while let Some(span) = spans.next() {
+    ///     match span {
+    ///         Ok(span) => sums.push(span.sum::<i32>()),
+    ///         Err(_) => errors += 1,
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(sums, vec![3, 7]);
+    /// assert_eq!(errors, 1);
+    /// ```
+    fn try_spans_by<T, E, F>(self, are_connected: F) -> TrySpans<Self, F>
+    where
+        Self: Iterator<Item = Result<T, E>> + Sized,
+        F: FnMut(&T, &T) -> bool,
+    {
+        TrySpans::new(self, are_connected)
+    }
+
+    /// Splits the iterator into contiguous spans by comparing the keys of adjacent items, where making a key may fail.
+    ///
+    /// This is like [`spans_by_key`][Spans::spans_by_key], except that `key` returns a [`Result`].
+    /// The first error ends the iteration: the current span ends before the item whose key could not be made, and [`TrySpansBy::next`] then returns the error instead of a span.
+    /// `key` is called with each item at most once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// # fn main() -> Result<(), std::num::ParseIntError> {
+    /// let vec = vec!["1", "2", "4", "5"];
+    /// let mut spans = vec
+    ///     .into_iter()
+    ///     .try_spans_by_key(|s| s.parse::<i32>(), |a, b| a + 1 == b);
+    ///
+    /// while let Some(span) = spans.next() {
+    ///     assert_eq!(span?.count(), 2);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn try_spans_by_key<K, C, E, F>(self, key: K, are_connected: F) -> TrySpansBy<Self, K, C, E, F>
+    where
+        K: FnMut(&Self::Item) -> Result<C, E>,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+        Self: Sized,
+    {
+        TrySpansBy::new(self, key, are_connected)
+    }
+
+    /// Encodes the runs of equal items of the iterator as pairs of the first item of each run and the length of the run.
+    ///
+    /// This is equivalent to calling [`spans_by`][Spans::spans_by] with `|a, b| a == b` and counting the items of each span.
+    /// Only the first item of each run is kept; the other items are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let runs: Vec<(char, usize)> = "aaabcc".chars().run_length_encode().collect();
+    ///
+    /// assert_eq!(runs, vec![('a', 3), ('b', 1), ('c', 2)]);
+    /// ```
+    fn run_length_encode(self) -> RunLengthEncode<Self>
+    where
+        Self::Item: PartialEq,
+        Self: Sized,
+    {
+        RunLengthEncode::new(self)
+    }
+
+    /// Decodes pairs of items and run lengths by repeating each item as many times as its run length.
+    ///
+    /// This is the inverse of [`run_length_encode`][Spans::run_length_encode].
+    /// Each item is cloned for all but the last item of its run; items with a run length of 0 are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let text: String = vec![('a', 3), ('b', 1), ('c', 2)].into_iter().run_length_decode().collect();
+    ///
+    /// assert_eq!(text, "aaabcc");
+    /// ```
+    fn run_length_decode<T>(self) -> RunLengthDecode<Self, T>
+    where
+        Self: Iterator<Item = (T, usize)> + Sized,
+        T: Clone,
+    {
+        RunLengthDecode::new(self)
+    }
+
+    /// Splits the iterator into maximal non-decreasing runs.
+    ///
+    /// A new span starts whenever an item is smaller than the previous item or incomparable to it.
+    /// This is equivalent to calling [`spans_by`][Spans::spans_by] with `|a, b| a <= b`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 4, 4, 9, 2, 3, 0];
+    /// let mut runs = vec.into_iter().sorted_runs();
+    ///
+    /// assert_eq!(runs.next()?.collect::<Vec<_>>(), vec![1, 4, 4, 9]);
+    /// assert_eq!(runs.next()?.collect::<Vec<_>>(), vec![2, 3]);
+    /// assert_eq!(runs.next()?.collect::<Vec<_>>(), vec![0]);
+    /// assert!(runs.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn sorted_runs(self) -> SpansBy<Self, BySortedOrder<Self::Item>>
+    where
+        Self::Item: PartialOrd,
+        Self: Sized,
+    {
+        self.spans_by(runs::are_sorted)
+    }
+
+    /// Splits the iterator into maximal runs of items with non-decreasing keys.
+    ///
+    /// A new span starts whenever the key of an item is smaller than the key of the previous item or incomparable to it.
+    /// This is equivalent to calling [`spans_by_key`][Spans::spans_by_key] with `|a, b| a <= b`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![("b", 1), ("c", 2), ("a", 3), ("b", 4)];
+    /// let mut runs = vec.into_iter().sorted_runs_by_key(|&(name, _)| name);
+    ///
+    /// assert_eq!(runs.next()?.collect::<Vec<_>>(), vec![("b", 1), ("c", 2)]);
+    /// assert_eq!(runs.next()?.collect::<Vec<_>>(), vec![("a", 3), ("b", 4)]);
+    /// assert!(runs.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn sorted_runs_by_key<K, C>(self, key: K) -> SpansBy<Self, BySortedKeys<K, C>>
+    where
+        K: FnMut(&Self::Item) -> C,
+        C: Clone + PartialOrd,
+        Self: Sized,
+    {
+        self.spans_by_key(key, runs::are_sorted_keys)
+    }
+
+    /// Splits the iterator into sessions of items with at most `max_gap` between the timestamps of adjacent items.
+    ///
+    /// `timestamp` returns the timestamp of an item.
+    /// A new session starts whenever the timestamp of an item is more than `max_gap` after the timestamp of the previous item, or before the timestamp of the previous item.
+    /// Timestamps can be of any type whose difference is comparable to `max_gap`, such as integers or `std::time::Instant` with [`Duration`][core::time::Duration].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// use std::time::Duration;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let clicks = vec![
+    ///     ("home", Duration::from_secs(0)),
+    ///     ("search", Duration::from_secs(20)),
+    ///     ("cart", Duration::from_secs(50)),
+    ///     ("home", Duration::from_secs(4000)),
+    /// ];
+    /// let mut sessions = clicks
+    ///     .iter()
+    ///     .sessions_by_gap(|&&(_, t)| t, Duration::from_secs(30 * 60));
+    ///
+    /// assert_eq!(sessions.next()?.count(), 3);
+    /// assert_eq!(sessions.next()?.count(), 1);
+    /// assert!(sessions.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn sessions_by_gap<K, T, D>(
+        self,
+        timestamp: K,
+        max_gap: D,
+    ) -> SpansBy<Self, BySessionGap<K, T, D>>
+    where
+        K: FnMut(&Self::Item) -> T,
+        T: Sub<Output = D> + PartialOrd + Copy,
+        D: PartialOrd,
+        Self: Sized,
+    {
+        SpansBy::new(self, BySessionGap::new(timestamp, max_gap))
+    }
+
+    /// Splits the iterator into natural runs, as used by merge sort algorithms such as Timsort.
+    ///
+    /// A natural run is either a maximal non-decreasing run or a maximal strictly decreasing run.
+    /// The direction of a run is determined by its first two items.
+    /// Each run is collected into a [`Run`], which provides [`Run::into_sorted`] to reverse descending runs.
+    /// Descending runs are strictly decreasing, such that reversing them keeps equal items in iteration order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::{Run, Spans};
+    ///
+    /// let vec = vec![9, 7, 4, 1, 3, 3, 8, 2];
+    /// let mut runs = vec.into_iter().natural_runs();
+    ///
+    /// assert_eq!(runs.next(), Some(Run::Descending(vec![9, 7, 4, 1])));
+    /// assert_eq!(runs.next(), Some(Run::Ascending(vec![3, 3, 8])));
+    /// assert_eq!(runs.next(), Some(Run::Ascending(vec![2])));
+    /// assert_eq!(runs.next(), None);
+    ///
+    /// let sorted: Vec<_> = vec![3, 2, 1, 5, 6]
+    ///     .into_iter()
+    ///     .natural_runs()
+    ///     .map(Run::into_sorted)
+    ///     .collect();
+    /// assert_eq!(sorted, vec![vec![1, 2, 3], vec![5, 6]]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn natural_runs(self) -> NaturalRuns<Self>
+    where
+        Self::Item: PartialOrd,
+        Self: Sized,
+    {
+        NaturalRuns::new(SpansBy::new(self, ByNaturalOrder::new()))
+    }
+
+    /// Splits the iterator into contiguous spans of consecutive integers.
+    ///
+    /// A new span starts whenever an item is not exactly 1 larger than the previous item.
+    /// This is equivalent to calling [`spans_by_key`][Spans::spans_by_key] with `|&x| x` and `|a, b| a + 1 == b`, except that the maximum value of the integer type is never connected to any following item instead of overflowing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec: Vec<u8> = vec![1, 2, 5, 6, 7, 11];
+    /// let mut spans = vec.into_iter().consecutive_spans();
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![1, 2]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![5, 6, 7]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![11]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn consecutive_spans(self) -> SpansBy<Self, ByConsecutive<Self::Item>>
+    where
+        Self::Item: Integer,
+        Self: Sized,
+    {
+        self.spans_by_key(integer::identity, integer::are_consecutive)
+    }
+
+    /// Splits the iterator into contiguous spans of consecutive integers, wrapping around from the maximum to the minimum value.
+    ///
+    /// This is like [`consecutive_spans`][Spans::consecutive_spans], except that the minimum value of the integer type is connected to the maximum value of the integer type.
+    /// See [`are_wrapping_consecutive`] for more information.
+    ///
     /// # Example
     ///
     /// ```
     /// use spans::Spans;
+    /// # fn test() -> Option<()> {
     ///
-    /// let vec = vec![1, 2, 5];
-    /// let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    /// let vec: Vec<u8> = vec![253, 254, 255, 0, 1, 5];
+    /// let mut spans = vec.into_iter().wrapping_consecutive_spans();
     ///
-    /// while let Some(span) = spans.next() {
-    ///     // do something with `span`
-    ///     let count = span.count();
-    ///     assert!(count > 0); // true for all spans
-    ///     assert!(count < 3); // true for the spans in this example
-    /// }
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![253, 254, 255, 0, 1]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![5]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
     /// ```
-    pub fn next(&mut self) -> Option<Span<'_, I, K, C, F>> {
-        if let Some(first) = self.iter.peek() {
-            let prev_key = (self.key)(first);
-            Some(Span {
-                parent: self,
-                prev_key,
-                is_init: true,
-            })
-        } else {
-            None
-        }
+    fn wrapping_consecutive_spans(self) -> SpansBy<Self, ByConsecutive<Self::Item>>
+    where
+        Self::Item: Integer,
+        Self: Sized,
+    {
+        self.spans_by_key(integer::identity, are_wrapping_consecutive)
     }
-}
 
-/// A `Span` is an iterator that iterates over a span of its parent iterator.
-///
-/// A span is always non-empty; at least one item is provided when iterating over a span.
-/// This is because empty spans are handled and discarded by the parent `SpansBy` iterator.
-///
-/// See [`Spans::spans_by_key`] for more information.
-pub struct Span<'a, I: Iterator, K, C, F> {
-    /// The parent iterator.
-    parent: &'a mut SpansBy<I, K, F>,
-    /// The key of the previous iterator item.
-    prev_key: C,
-    /// Whether no item has been accessed yet.
+    /// Splits the iterator into contiguous spans of integers with a fixed step between adjacent items.
     ///
-    /// `true` initially, `false` after the first `Span::next` invocation.
-    is_init: bool,
-}
-
-impl<I, K, C, F> Iterator for Span<'_, I, K, C, F>
-where
-    I: Iterator,
-    K: Fn(&I::Item) -> C,
-    C: Copy,
-    F: Fn(C, C) -> bool,
-{
-    type Item = I::Item;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.is_init {
-            self.is_init = false;
-            return self.parent.iter.next();
-        }
+    /// A new span starts whenever an item does not equal the previous item plus `step`.
+    /// For signed integer types, `step` may be negative to find descending sequences.
+    /// For descending sequences of unsigned integers, use [`spans_by_descending_stride`][Spans::spans_by_descending_stride].
+    /// Adjacent items whose difference cannot be represented by the integer type are never connected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![0, 5, 10, 12, 17, 3, -2];
+    /// let mut spans = vec.iter().copied().spans_by_stride(5);
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![0, 5, 10]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![12, 17]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![3]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![-2]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by_stride(self, step: Self::Item) -> SpansBy<Self, ByStride<Self::Item>>
+    where
+        Self::Item: Integer,
+        Self: Sized,
+    {
+        SpansBy::new(self, ByStride::new(step, false))
+    }
 
-        let peek = self.parent.iter.peek()?;
-        let peek_key = (self.parent.key)(peek);
+    /// Splits the iterator into contiguous spans of integers counting down by a fixed step between adjacent items.
+    ///
+    /// A new span starts whenever an item does not equal the previous item minus `step`.
+    /// This is like [`spans_by_stride`][Spans::spans_by_stride] with a negated step, except that it also works for unsigned integer types.
+    /// Adjacent items whose difference cannot be represented by the integer type are never connected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec: Vec<u32> = vec![30, 20, 10, 0, 15, 5];
+    /// let mut spans = vec.into_iter().spans_by_descending_stride(10);
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![30, 20, 10, 0]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![15, 5]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by_descending_stride(self, step: Self::Item) -> SpansBy<Self, ByStride<Self::Item>>
+    where
+        Self::Item: Integer,
+        Self: Sized,
+    {
+        SpansBy::new(self, ByStride::new(step, true))
+    }
 
-        let item = if !(self.parent.are_connected)(self.prev_key, peek_key) {
-            None
-        } else {
-            self.parent.iter.next()
-        };
+    /// Splits the iterator into contiguous spans of integers with at most `gap` between adjacent items.
+    ///
+    /// A new span starts whenever the distance between an item and the previous item is larger than `gap`.
+    /// The distance is the absolute difference of the two items; spans may thus ascend and descend.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 2, 4, 9];
+    /// let mut spans = vec.into_iter().spans_with_max_gap(2);
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![1, 2, 4]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![9]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_with_max_gap(self, gap: Self::Item) -> SpansBy<Self, ByMaxGap<Self::Item>>
+    where
+        Self::Item: Integer,
+        Self: Sized,
+    {
+        SpansBy::new(self, ByMaxGap::new(gap))
+    }
 
-        self.prev_key = peek_key;
-        item
+    /// Splits the iterator into contiguous spans whose items have a total weight of at most `max_total`.
+    ///
+    /// `weight` returns the weight of an item.
+    /// A new span starts whenever adding the weight of an item to the total weight of the current span would exceed `max_total`.
+    /// An item whose weight alone exceeds `max_total` forms a span of its own.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec!["GET", "HEAD", "POST", "OPTIONS", "PUT"];
+    /// let mut spans = vec.into_iter().spans_by_capacity(|s| s.len(), 8);
+    ///
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec!["GET", "HEAD"]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec!["POST"]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec!["OPTIONS"]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec!["PUT"]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by_capacity<W>(self, weight: W, max_total: usize) -> SpansBy<Self, ByCapacity<W>>
+    where
+        W: FnMut(&Self::Item) -> usize,
+        Self: Sized,
+    {
+        SpansBy::new(self, ByCapacity::new(weight, max_total))
     }
-}
 
-/// `Spans` provides an iterator adapter for `SpansBy`.
-pub trait Spans: Iterator {
-    /// Splits the iterator into contiguous spans.
+    /// Splits the iterator into contiguous spans as decided by `decide` for each item.
     ///
-    /// `are_connected` returns `true` if the two given adjacent items are part of the same span, `false` otherwise.
-    /// Items are not compared directly; instead, a key is made for each item using the `key` function.
-    /// `are_connected` is given the key of the previous item and the key of the current item.
+    /// `decide` is called once for each item, in order.
+    /// [`SplitDecision::SplitBefore`] starts a new span with the item and [`SplitDecision::SplitAfter`] ends the current span with the item, like [`slice::split_inclusive`].
+    /// Since the first item always starts a span, `SplitDecision::SplitBefore` has no effect for the first item.
+    /// If the iterator has only a single item, `decide` is not called.
     ///
-    /// # Examples
+    /// # Example
     ///
-    /// Create spans for items increasing in value by 1:
+    /// ```
+    /// use spans::{Spans, SplitDecision};
+    /// # fn test() -> Option<()> {
     ///
+    /// let mut spans = "# Title\nText.\n# Next".chars().spans_by_decision(|&c| match c {
+    ///     '#' => SplitDecision::SplitBefore,
+    ///     '\n' => SplitDecision::SplitAfter,
+    ///     _ => SplitDecision::Continue,
+    /// });
+    ///
+    /// assert_eq!(spans.next()?.collect::<String>(), "# Title\n");
+    /// assert_eq!(spans.next()?.collect::<String>(), "Text.\n");
+    /// assert_eq!(spans.next()?.collect::<String>(), "# Next");
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
     /// ```
-    /// use spans::Spans;
+    fn spans_by_decision<G>(self, decide: G) -> SpansBy<Self, ByDecision<G>>
+    where
+        G: FnMut(&Self::Item) -> SplitDecision,
+        Self: Sized,
+    {
+        SpansBy::new(self, ByDecision::new(decide))
+    }
+
+    /// Splits the iterator into contiguous spans as decided by `scan` over an accumulated state.
+    ///
+    /// `scan` is called once for each item, in order, with a mutable reference to the state, which is initially `initial_state`.
+    /// It returns whether the item continues the current span, starts a new span, or ends the current span, as for [`Spans::spans_by_decision`].
+    /// The state is never reset by the iterator; `scan` resets it as needed when it decides to split.
+    /// The first item is scanned when its span starts, such that the state also sees the first item of an iterator with a single item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::{Spans, SplitDecision};
     /// # fn test() -> Option<()> {
     ///
-    /// let vec = vec![1, 2, 5, 6, 7, 11, 13, 14, 15];
-    /// let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+    /// // Start a new span after every second `!`.
+    /// let mut spans = "a!b!c!d".chars().spans_scan(0, |bangs, &c| {
+    ///     if *bangs == 2 {
+    ///         *bangs = 0;
+    ///         return SplitDecision::SplitBefore;
+    ///     }
+    ///     if c == '!' {
+    ///         *bangs += 1;
+    ///     }
+    ///     SplitDecision::Continue
+    /// });
     ///
-    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&1, &2]);
-    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&5, &6, &7]);
-    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&11]);
-    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![&13, &14, &15]);
+    /// assert_eq!(spans.next()?.collect::<String>(), "a!b!");
+    /// assert_eq!(spans.next()?.collect::<String>(), "c!d");
     /// assert!(spans.next().is_none());
     /// # Some(())
     /// # }
     /// # fn main() { assert_eq!(test(), Some(())) }
     /// ```
+    fn spans_scan<S, G>(self, initial_state: S, scan: G) -> SpansBy<Self, ByScan<S, G>>
+    where
+        G: FnMut(&mut S, &Self::Item) -> SplitDecision,
+        Self: Sized,
+    {
+        SpansBy::new(self, ByScan::new(initial_state, scan))
+    }
+
+    /// Splits the iterator into contiguous spans of floating-point numbers with at most `eps` between adjacent items.
     ///
-    /// Create spans for strings of the same length:
+    /// A new span starts whenever the absolute difference between an item and the previous item is larger than `eps`.
+    ///
+    /// NaN is never connected to any item.
+    /// Therefore, a NaN item always starts a new span and the item following a NaN item always starts another span.
+    /// Likewise, infinite items are not connected to each other, since the difference of two infinities is either infinite or NaN.
+    ///
+    /// # Example
     ///
     /// ```
     /// use spans::Spans;
     /// # fn test() -> Option<()> {
     ///
-    /// let vec = vec!["abc", "run", "tag", "go", "be", "ring", "zip", "zap", "put"];
-    /// let mut spans = vec.iter().spans_by_key(|x| x.len(), |a, b| a == b);
+    /// let vec = vec![0.1, 0.15, 0.3, f64::NAN, 0.35, 0.9];
+    /// let mut spans = vec.into_iter().spans_by_delta(0.2);
     ///
-    /// assert_eq!(
-    ///     spans.next()?.collect::<Vec<_>>(),
-    ///     vec![&"abc", &"run", &"tag"]
-    /// );
-    /// assert_eq!(
-    ///     spans.next()?.collect::<Vec<_>>(),
-    ///     vec![&"go", &"be"]
-    /// );
-    /// assert_eq!(
-    ///     spans.next()?.collect::<Vec<_>>(),
-    ///     vec![&"ring"]
-    /// );
-    /// assert_eq!(
-    ///     spans.next()?.collect::<Vec<_>>(),
-    ///     vec![&"zip", &"zap", &"put"]
-    /// );
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![0.1, 0.15, 0.3]);
+    /// assert!(spans.next()?.all(f64::is_nan));
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![0.35]);
+    /// assert_eq!(spans.next()?.collect::<Vec<_>>(), vec![0.9]);
     /// assert!(spans.next().is_none());
     /// # Some(())
     /// # }
     /// # fn main() { assert_eq!(test(), Some(())) }
     /// ```
-    fn spans_by_key<K, C, F>(self, key: K, are_connected: F) -> SpansBy<Self, K, F>
+    fn spans_by_delta(self, eps: Self::Item) -> SpansBy<Self, ByDelta<Self::Item>>
     where
-        K: Fn(&Self::Item) -> C,
-        C: Copy,
-        F: Fn(C, C) -> bool,
+        Self::Item: Float,
         Self: Sized,
     {
-        SpansBy {
-            iter: self.peekable(),
-            key,
-            are_connected,
-        }
+        SpansBy::new(self, ByDelta::new(eps))
     }
 }
 
+/// Returns whether `a` and `b` are equal.
+fn are_equal<C: PartialEq>(a: C, b: C) -> bool {
+    a == b
+}
+
 impl<I: Iterator> Spans for I {}
 
 #[cfg(test)]
+#[allow(clippy::useless_vec)]
 mod tests {
     use super::*;
 
@@ -250,6 +1866,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::iter_nth_zero)]
     fn test_two_items_two_spans() {
         let vec = vec!["abc", "xyz"];
         let mut spans = vec
@@ -298,4 +1915,245 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_spans_by_empty() {
+        let vec: Vec<&str> = Vec::new();
+        let mut spans = vec.iter().spans_by(|a, b| a.len() == b.len());
+        assert_eq_spans!(spans, []);
+    }
+
+    #[test]
+    fn test_spans_by_many_items() {
+        let vec = vec![1, 2, 3, 2, 1, 5, 6, 4];
+        let mut spans = vec.iter().spans_by(|a, b| a < b);
+        assert_eq_spans!(spans, [[1, 2, 3], [2], [1, 5, 6], [4]]);
+    }
+
+    #[test]
+    fn test_spans_by_non_copy_items() {
+        let vec = vec![String::from("a"), String::from("ab"), String::from("c")];
+        let mut spans = vec.into_iter().spans_by(|a, b| b.starts_with(a.as_str()));
+        assert_eq!(
+            spans.next().unwrap().collect::<Vec<_>>(),
+            vec![String::from("a"), String::from("ab")]
+        );
+        assert_eq!(
+            spans.next().unwrap().collect::<Vec<_>>(),
+            vec![String::from("c")]
+        );
+        assert!(spans.next().is_none());
+    }
+
+    #[test]
+    fn test_spans_by_key_change() {
+        let vec = vec![1, 1, 2, 2, 2, 1, 3];
+        let mut spans = vec.iter().spans_by_key_change(|&&x| x);
+        assert_eq_spans!(spans, [[1, 1], [2, 2, 2], [1], [3]]);
+    }
+
+    #[test]
+    fn test_clone_keys() {
+        let vec = vec!["ab1", "ab2", "cd1", "ab3"];
+        let mut spans = vec
+            .iter()
+            .spans_by_key(|x| x[..2].to_string(), |a, b| a == b);
+        assert_eq_spans!(spans, [["ab1", "ab2"], ["cd1"], ["ab3"]]);
+    }
+
+    #[test]
+    fn test_dropped_spans_are_skipped() {
+        let vec = vec![1, 2, 3, 5, 6, 8];
+        let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+        assert_eq!(spans.next().unwrap().next(), Some(&1));
+        assert!(spans.next().is_some());
+        assert_eq_span!(spans.next().unwrap(), [8]);
+        assert!(spans.next().is_none());
+    }
+
+    #[test]
+    fn test_skip_span() {
+        let vec = vec![1, 2, 3, 5, 6, 8];
+        let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+        assert_eq!(spans.next().unwrap().next(), Some(&1));
+        assert!(spans.skip_span());
+        assert_eq_span!(spans.next().unwrap(), [8]);
+        assert!(!spans.skip_span());
+    }
+
+    #[test]
+    fn test_peek_span_key_calls_key_once() {
+        let mut calls = 0;
+        let vec = vec![1, 2, 5];
+        let mut spans = vec.iter().spans_by_key(
+            |&&x| {
+                calls += 1;
+                x
+            },
+            |a, b| a + 1 == b,
+        );
+        assert_eq!(spans.peek_span_key(), Some(1));
+        assert_eq!(spans.peek_span_key(), Some(1));
+        assert_eq_span!(spans.next().unwrap(), [1, 2]);
+        assert_eq!(spans.peek_span_key(), Some(5));
+        assert_eq_span!(spans.next().unwrap(), [5]);
+        assert_eq!(spans.peek_span_key(), None);
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn test_span_key() {
+        let vec = vec![1, 2, 5];
+        let mut spans = vec.iter().spans_by_key(|&&x| x * 10, |a, b| a + 10 == b);
+        let mut span = spans.next().unwrap();
+        assert_eq!(*span.key(), 10);
+        assert_eq!(span.next(), Some(&1));
+        assert_eq!(span.next(), Some(&2));
+        assert_eq!(*span.key(), 10);
+        let span = spans.next().unwrap();
+        assert_eq!(*span.key(), 50);
+    }
+
+    #[test]
+    fn test_span_index() {
+        let vec = vec![1, 2, 5, 7, 8];
+        let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+        assert_eq!(spans.next().unwrap().index(), 0);
+        assert!(spans.skip_span());
+        assert_eq!(spans.peek_span(), Some(&&7));
+        let mut span = spans.next().unwrap();
+        assert_eq!(span.next(), Some(&7));
+        assert_eq!(span.index(), 2);
+        assert!(spans.next().is_none());
+    }
+
+    #[test]
+    fn test_span_start_index() {
+        let vec = vec![1, 2, 5, 7, 8];
+        let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+        assert_eq!(spans.next().unwrap().start_index(), 0);
+        assert!(spans.skip_span());
+        let mut span = spans.next().unwrap();
+        assert_eq!(span.next(), Some(&7));
+        assert_eq!(span.start_index(), 3);
+
+        let mut spans = "a,,bc".chars().split_by(|&c| c == ',');
+        let starts = core::iter::from_fn(|| spans.next().map(|span| span.start_index()));
+        assert_eq!(starts.collect::<Vec<_>>(), vec![0, 3]);
+    }
+
+    #[test]
+    fn test_into_inner_after_dropped_span() {
+        let vec = vec![1, 2, 3, 7];
+        let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+        assert_eq!(spans.next().unwrap().next(), Some(&1));
+        assert_eq!(spans.into_inner().collect::<Vec<_>>(), vec![&2, &3, &7]);
+    }
+
+    #[test]
+    fn test_get_mut_resets_key_cache() {
+        let vec = vec![1, 2, 5, 6];
+        let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+        assert_eq!(spans.next().unwrap().next(), Some(&1));
+        assert_eq!(spans.get_mut().nth(1), Some(&5));
+        assert_eq_span!(spans.next().unwrap(), [6]);
+        assert!(spans.next().is_none());
+
+        let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+        assert_eq!(spans.peek_span_key(), Some(1));
+        assert_eq!(spans.get_mut().next(), Some(&1));
+        assert_eq!(spans.peek_span_key(), Some(2));
+        assert_eq_span!(spans.next().unwrap(), [2]);
+    }
+
+    #[test]
+    fn test_clone_mid_span() {
+        let vec = vec![1, 2, 3, 7, 8];
+        let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+        assert_eq!(spans.next().unwrap().next(), Some(&1));
+        let mut clone = spans.clone();
+        assert_eq_spans!(spans, [[7, 8]]);
+        assert_eq_spans!(clone, [[7, 8]]);
+    }
+
+    #[test]
+    fn test_unfused_iterator() {
+        let mut count = 0;
+        let unfused = core::iter::from_fn(|| {
+            count += 1;
+            if count % 3 == 0 {
+                None
+            } else {
+                Some(count)
+            }
+        });
+        let mut spans = unfused.spans_by(|_, _| true);
+        assert_eq!(spans.next().unwrap().collect::<Vec<_>>(), vec![1, 2]);
+        assert!(spans.next().is_none());
+        assert!(spans.next().is_none());
+        assert!(spans.peek_span().is_none());
+    }
+
+    #[test]
+    fn test_size_hints() {
+        let vec = vec![1, 2, 3, 7];
+        let mut spans = vec.iter().spans_by_key(|&&x| x, |a, b| a + 1 == b);
+        assert_eq!(spans.span_count_hint(), (1, Some(4)));
+
+        let mut span = spans.next().unwrap();
+        assert_eq!(span.size_hint(), (1, Some(4)));
+        span.next();
+        assert_eq!(span.size_hint(), (1, Some(3)));
+        assert_eq!(span.skip_rest(), 2);
+        assert_eq!(span.size_hint(), (0, Some(0)));
+
+        assert_eq!(spans.span_count_hint(), (1, Some(1)));
+        assert_eq_span!(spans.next().unwrap(), [7]);
+        assert_eq!(spans.span_count_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_stateful_closures() {
+        let vec = vec![1, 2, 4, 5, 7];
+        let mut key_calls = 0;
+        let mut violations = 0;
+        let mut spans = vec.iter().spans_by_key(
+            |&&x| {
+                key_calls += 1;
+                x
+            },
+            |a, b| {
+                let is_connected = a + 1 == b;
+                if !is_connected {
+                    violations += 1;
+                }
+                is_connected
+            },
+        );
+        assert_eq_spans!(spans, [[1, 2], [4, 5], [7]]);
+        assert_eq!(key_calls, 5);
+        assert_eq!(violations, 2);
+    }
+
+    #[test]
+    fn test_consecutive_spans() {
+        let vec = vec![-3i64, -2, -1, 0, 2, 3, 5];
+        let mut spans = vec.iter().copied().consecutive_spans();
+        assert_eq!(
+            spans.next().unwrap().collect::<Vec<_>>(),
+            vec![-3, -2, -1, 0]
+        );
+        assert_eq!(spans.next().unwrap().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(spans.next().unwrap().collect::<Vec<_>>(), vec![5]);
+        assert!(spans.next().is_none());
+    }
+
+    #[test]
+    fn test_consecutive_spans_overflow() {
+        let vec = vec![254u8, 255, 0, 1];
+        let mut spans = vec.into_iter().consecutive_spans();
+        assert_eq!(spans.next().unwrap().collect::<Vec<_>>(), vec![254, 255]);
+        assert_eq!(spans.next().unwrap().collect::<Vec<_>>(), vec![0, 1]);
+        assert!(spans.next().is_none());
+    }
 }
@@ -0,0 +1,192 @@
+use crate::{Connector, SpansBy};
+
+impl<I: Iterator, F> SpansBy<I, F> {
+    /// Limits the number of items per span to `max_len`.
+    ///
+    /// A new span starts whenever the current span has `max_len` items, even if the next item is connected to the previous item.
+    /// Spans that are not split by the limit are unaffected.
+    /// The limit applies from the next span on; it should be set before iterating over any spans.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_len` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 3, 4, 5, 9, 10];
+    /// let spans = vec
+    ///     .into_iter()
+    ///     .spans_by_key(|&x| x, |a, b| a + 1 == b)
+    ///     .with_max_len(2)
+    ///     .map_spans(|span| span.collect::<Vec<_>>())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(spans, vec![vec![1, 2], vec![3, 4], vec![5], vec![9, 10]]);
+    /// ```
+    pub fn with_max_len(self, max_len: usize) -> SpansBy<I, MaxLen<F>> {
+        assert!(max_len > 0, "spans must be able to hold at least one item");
+
+        SpansBy {
+            iter: self.iter,
+            connector: MaxLen {
+                connector: self.connector,
+                max_len,
+                len: 1,
+            },
+            is_connected: self.is_connected,
+            is_done: self.is_done,
+            span_count: self.span_count,
+            position: self.position,
+            span_start: self.span_start,
+        }
+    }
+}
+
+/// `MaxLen` is a [`Connector`] that limits the number of items per span of another connector.
+///
+/// See [`SpansBy::with_max_len`] for more information.
+#[derive(Clone)]
+pub struct MaxLen<F> {
+    /// The wrapped connector.
+    connector: F,
+    /// The maximum number of items per span.
+    max_len: usize,
+    /// The number of items in the current span, including the item passed as `next` most recently.
+    len: usize,
+}
+
+impl<T, F> Connector<T> for MaxLen<F>
+where
+    F: Connector<T>,
+{
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        // The wrapped connector is asked even at the limit, such that it observes every pair of adjacent items.
+        let is_connected = self.connector.are_connected(prev, next) && self.len < self.max_len;
+
+        if is_connected {
+            self.len += 1;
+        } else {
+            self.len = 1;
+        }
+
+        is_connected
+    }
+
+    fn start_span(&mut self, first: &T) {
+        self.connector.start_span(first);
+    }
+
+    fn reset(&mut self) {
+        self.connector.reset();
+        self.len = 1;
+    }
+}
+
+/// `ByCapacity` is a [`Connector`] that connects items as long as the total weight of the current span does not exceed a capacity.
+///
+/// See [`Spans::spans_by_capacity`](crate::Spans::spans_by_capacity) for more information.
+#[derive(Clone)]
+pub struct ByCapacity<W> {
+    /// Returns the weight of an item.
+    weight: W,
+    /// The maximum total weight of a span.
+    max_total: usize,
+    /// The total weight of the current span, including the item passed as `next` most recently.
+    total: Option<usize>,
+}
+
+impl<W> ByCapacity<W> {
+    /// Creates a new `ByCapacity` connector.
+    pub(crate) fn new(weight: W, max_total: usize) -> Self {
+        ByCapacity {
+            weight,
+            max_total,
+            total: None,
+        }
+    }
+}
+
+impl<T, W> Connector<T> for ByCapacity<W>
+where
+    W: FnMut(&T) -> usize,
+{
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        let total = match self.total {
+            Some(total) => total,
+            None => (self.weight)(prev),
+        };
+        let weight = (self.weight)(next);
+
+        match total.checked_add(weight) {
+            Some(total) if total <= self.max_total => {
+                self.total = Some(total);
+                true
+            }
+            _ => {
+                self.total = Some(weight);
+                false
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.total = None;
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_max_len_one() {
+        let spans = "aab"
+            .chars()
+            .spans_by(|a, b| a == b)
+            .with_max_len(1)
+            .collect_spans();
+        assert_eq!(spans, vec![vec!['a'], vec!['a'], vec!['b']]);
+    }
+
+    #[test]
+    fn test_max_len_resets_at_boundary() {
+        let spans = "aaabbbb"
+            .chars()
+            .spans_by(|a, b| a == b)
+            .with_max_len(3)
+            .collect_spans();
+        assert_eq!(
+            spans,
+            vec![vec!['a', 'a', 'a'], vec!['b', 'b', 'b'], vec!['b']]
+        );
+    }
+
+    #[test]
+    fn test_capacity_heavy_items() {
+        let spans = [1, 5, 1, 1, 2]
+            .iter()
+            .copied()
+            .spans_by_capacity(|&x| x, 3)
+            .collect_spans();
+        assert_eq!(spans, vec![vec![1], vec![5], vec![1, 1], vec![2]]);
+    }
+
+    #[test]
+    fn test_capacity_overflow() {
+        let spans = [usize::MAX, 1, 1]
+            .iter()
+            .copied()
+            .spans_by_capacity(|&x| x, usize::MAX)
+            .collect_spans();
+        assert_eq!(spans, vec![vec![usize::MAX], vec![1, 1]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_max_len_zero() {
+        let _ = "a".chars().spans_by(|a, b| a == b).with_max_len(0);
+    }
+}
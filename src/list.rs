@@ -0,0 +1,803 @@
+//! A collected list of spans.
+//!
+//! A [`SpanList`] stores the items of all spans in a single [`Vec`] and the end of each span in a second [`Vec`], such that the memory overhead is one `usize` per span:
+//!
+//! ```
+//! use spans::Spans;
+//! use spans::list::SpanList;
+//!
+//! let list: SpanList<i32> = vec![1, 2, 5, 6, 7, 11]
+//!     .into_iter()
+//!     .spans_by_key(|&x| x, |a, b| a + 1 == b)
+//!     .collect_span_list();
+//!
+//! assert_eq!(list.iter().collect::<Vec<_>>(), vec![&[1, 2][..], &[5, 6, 7], &[11]]);
+//! assert_eq!(list.items(), &[1, 2, 5, 6, 7, 11]);
+//! ```
+//!
+//! A [`ConnectedSpanList`] also stores the connector, such that items can be appended with the connector state of the collected spans, which is useful for maintaining spans online.
+
+use crate::{are_equal, ByKey, ByKeyChange, Connector, SpansBy};
+use alloc::vec::Vec;
+use core::iter::{Extend, FromIterator, FusedIterator};
+use core::{mem, slice};
+
+/// `SpanList` is a list of non-empty spans that stores all items contiguously.
+///
+/// Collect owned spans into a `SpanList` with [`FromIterator`] or collect the spans of a [`SpansBy`] with [`SpansBy::collect_span_list`].
+/// Empty spans are discarded when collecting.
+///
+/// With the `serde` feature, a `SpanList` is serialized as its [`items`][SpanList::items] and [`ends`][SpanList::ends].
+/// Deserializing fails unless the ends are increasing and the last end is the number of items.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawSpanList<T>",
+        bound(deserialize = "T: serde::Deserialize<'de>")
+    )
+)]
+pub struct SpanList<T> {
+    /// The items of all spans in order.
+    items: Vec<T>,
+    /// The index in `items` after the last item of each span, in increasing order.
+    ends: Vec<usize>,
+}
+
+/// `RawSpanList` is the unchecked form of a [`SpanList`] that is deserialized before the ends are checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawSpanList<T> {
+    /// The items of all spans in order.
+    items: Vec<T>,
+    /// The index in `items` after the last item of each span.
+    ends: Vec<usize>,
+}
+
+/// `InvalidEnds` is the error of deserializing a [`SpanList`] whose ends do not split its items into non-empty spans.
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+struct InvalidEnds;
+
+#[cfg(feature = "serde")]
+impl core::fmt::Display for InvalidEnds {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("span ends must be increasing and the last end must be the number of items")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> core::convert::TryFrom<RawSpanList<T>> for SpanList<T> {
+    type Error = InvalidEnds;
+
+    fn try_from(raw: RawSpanList<T>) -> Result<Self, Self::Error> {
+        let is_increasing = raw.ends.windows(2).all(|ends| ends[0] < ends[1]);
+        let is_valid = match (raw.ends.first(), raw.ends.last()) {
+            (Some(&first), Some(&last)) => is_increasing && first > 0 && last == raw.items.len(),
+            _ => raw.items.is_empty(),
+        };
+
+        if is_valid {
+            Ok(SpanList {
+                items: raw.items,
+                ends: raw.ends,
+            })
+        } else {
+            Err(InvalidEnds)
+        }
+    }
+}
+
+impl<T> SpanList<T> {
+    /// Creates an empty `SpanList`.
+    pub fn new() -> Self {
+        SpanList {
+            items: Vec::new(),
+            ends: Vec::new(),
+        }
+    }
+
+    /// Returns an iterator over the spans as slices.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            items: &self.items,
+            ends: self.ends.iter(),
+            start: 0,
+        }
+    }
+
+    /// Returns the span at `index`, or `None` if `index` is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::list::SpanList;
+    ///
+    /// let list: SpanList<i32> = vec![vec![1, 2], vec![5]].into_iter().collect();
+    ///
+    /// assert_eq!(list.get(1), Some(&[5][..]));
+    /// assert_eq!(list.get(2), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&[T]> {
+        let end = *self.ends.get(index)?;
+        let start = if index == 0 { 0 } else { self.ends[index - 1] };
+        Some(&self.items[start..end])
+    }
+
+    /// Returns the number of items of all spans.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether the list has no spans.
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Returns the number of spans.
+    pub fn span_count(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns the index of the span whose key range contains `target`, or `None` if no span contains it.
+    ///
+    /// The key range of a span is the range from the key of its first item to the key of its last item.
+    /// The span is found by a binary search, which requires the keys of all items to be sorted in non-decreasing order.
+    /// `key` is called _O_(log _n_) times for _n_ spans.
+    /// If the keys are not sorted, the returned index is unspecified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let list = vec![1, 2, 5, 6, 7, 11]
+    ///     .into_iter()
+    ///     .spans_by_key(|&x| x, |a, b| a + 1 == b)
+    ///     .collect_span_list();
+    ///
+    /// assert_eq!(list.span_containing_key(|&x| x, &6), Some(1));
+    /// assert_eq!(list.span_containing_key(|&x| x, &9), None);
+    /// ```
+    pub fn span_containing_key<K, C>(&self, mut key: K, target: &C) -> Option<usize>
+    where
+        K: FnMut(&T) -> C,
+        C: Ord,
+    {
+        // The first span whose last key is not smaller than `target` is the only candidate.
+        let index = self
+            .ends
+            .partition_point(|&end| key(&self.items[end - 1]) < *target);
+        let span = self.get(index)?;
+
+        if key(&span[0]) <= *target {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the items of all spans in order.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Returns the index in [`items`][SpanList::items] after the last item of each span.
+    pub fn ends(&self) -> &[usize] {
+        &self.ends
+    }
+
+    /// Unwraps `self`, returning the items of all spans in order.
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    /// Appends `item` to the last span if it is connected to the last item, or as a new span otherwise.
+    ///
+    /// `are_connected` is called with the last item and `item`, unless the list is empty.
+    /// Pushing the items of an iterator one by one with the connector that split it extends the spans as if the items had been part of the iterator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let mut list = vec![1, 2, 5].into_iter().spans_by(|a, b| a + 1 == *b).collect_span_list();
+    /// list.push_by(6, |a, b| a + 1 == *b);
+    /// list.push_by(9, |a, b| a + 1 == *b);
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&[1, 2][..], &[5, 6], &[9]]);
+    /// ```
+    pub fn push_by<F>(&mut self, item: T, are_connected: F)
+    where
+        F: FnOnce(&T, &T) -> bool,
+    {
+        let is_connected = match self.items.last() {
+            Some(last) => are_connected(last, &item),
+            None => false,
+        };
+
+        self.items.push(item);
+
+        if is_connected {
+            *self.ends.last_mut().expect("a list with items has spans") += 1;
+        } else {
+            self.ends.push(self.items.len());
+        }
+    }
+
+    /// Appends `item` to the last span if its key is connected to the key of the last item, or as a new span otherwise.
+    ///
+    /// This is like [`push_by`][SpanList::push_by] with the connector of [`Spans::spans_by_key`][crate::Spans::spans_by_key].
+    /// `key` is called with the last item and `item`, unless the list is empty.
+    pub fn push_by_key<K, C, F>(&mut self, item: T, mut key: K, are_connected: F)
+    where
+        K: FnMut(&T) -> C,
+        F: FnOnce(C, C) -> bool,
+    {
+        self.push_by(item, |prev, next| are_connected(key(prev), key(next)));
+    }
+
+    /// Appends the spans of `other`, joining the last span of `self` and the first span of `other` if their adjacent items are connected.
+    ///
+    /// `are_connected` is called with the last item of `self` and the first item of `other`, unless either is empty.
+    /// If the two lists are the spans of two consecutive parts of an iterator, the merged list equals the spans of the whole iterator, given that the connector only depends on adjacent items.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let mut list = vec![1, 2, 5].into_iter().spans_by(|a, b| a + 1 == *b).collect_span_list();
+    /// let other = vec![6, 9].into_iter().spans_by(|a, b| a + 1 == *b).collect_span_list();
+    /// list.merge_by(other, |a, b| a + 1 == *b);
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&[1, 2][..], &[5, 6], &[9]]);
+    /// ```
+    pub fn merge_by<F>(&mut self, other: SpanList<T>, are_connected: F)
+    where
+        F: FnOnce(&T, &T) -> bool,
+    {
+        let is_connected = match (self.items.last(), other.items.first()) {
+            (Some(last), Some(first)) => are_connected(last, first),
+            _ => false,
+        };
+
+        if is_connected {
+            // The first span of `other` then ends the last span of `self`.
+            self.ends.pop();
+        }
+
+        let offset = self.items.len();
+        self.items.extend(other.items);
+        self.ends
+            .extend(other.ends.into_iter().map(|end| offset + end));
+    }
+
+    /// Appends the spans of `other`, joining the last span of `self` and the first span of `other` if the keys of their adjacent items are connected.
+    ///
+    /// This is like [`merge_by`][SpanList::merge_by] with the connector of [`Spans::spans_by_key`][crate::Spans::spans_by_key].
+    pub fn merge_by_key<K, C, F>(&mut self, other: SpanList<T>, mut key: K, are_connected: F)
+    where
+        K: FnMut(&T) -> C,
+        F: FnOnce(C, C) -> bool,
+    {
+        self.merge_by(other, |prev, next| are_connected(key(prev), key(next)));
+    }
+
+    /// Merges the items of `other` into `self` by their keys and splits the merged items into spans again.
+    ///
+    /// This is for lists whose items are sorted by `key`, such as the spans of different sources of sorted items: unlike [`merge_by_key`][SpanList::merge_by_key], which appends `other`, the items are interleaved in key order.
+    /// Items with equal keys keep their order, with the items of `self` first.
+    /// The merged items are then split like by [`Spans::spans_by_key`][crate::Spans::spans_by_key], such that the result equals collecting the spans of the sorted union.
+    /// `key` is called once for each item.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let mut list = vec![1, 2, 7].into_iter().spans_by(|a, b| a + 1 == *b).collect_span_list();
+    /// let other = vec![3, 5, 6].into_iter().spans_by(|a, b| a + 1 == *b).collect_span_list();
+    /// list.merge_sorted_by_key(other, |&x| x, |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&[1, 2, 3][..], &[5, 6, 7]]);
+    /// ```
+    pub fn merge_sorted_by_key<K, C, F>(
+        &mut self,
+        other: SpanList<T>,
+        mut key: K,
+        mut are_connected: F,
+    ) where
+        K: FnMut(&T) -> C,
+        C: Ord + Clone,
+        F: FnMut(C, C) -> bool,
+    {
+        let mut keyed = |item: T| (key(&item), item);
+        let mut left = mem::take(&mut self.items).into_iter();
+        let mut right = other.items.into_iter();
+        let mut next_left = left.next().map(&mut keyed);
+        let mut next_right = right.next().map(&mut keyed);
+        let mut prev_key: Option<C> = None;
+        self.ends.clear();
+
+        loop {
+            let is_left = match (&next_left, &next_right) {
+                (Some((left_key, _)), Some((right_key, _))) => left_key <= right_key,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+            let (item_key, item) = if is_left {
+                mem::replace(&mut next_left, left.next().map(&mut keyed))
+            } else {
+                mem::replace(&mut next_right, right.next().map(&mut keyed))
+            }
+            .expect("the chosen side has an item");
+
+            let is_connected = match prev_key.replace(item_key.clone()) {
+                Some(prev_key) => are_connected(prev_key, item_key),
+                None => false,
+            };
+            self.push_by(item, |_, _| is_connected);
+        }
+    }
+
+    /// Appends the items of `span` as a new span, unless it has no items.
+    pub fn push_span<S>(&mut self, span: S)
+    where
+        S: IntoIterator<Item = T>,
+    {
+        self.extend_span(span);
+    }
+
+    /// Appends `span` as a new span, unless it is empty.
+    fn extend_span<S>(&mut self, span: S)
+    where
+        S: IntoIterator<Item = T>,
+    {
+        let len = self.items.len();
+        self.items.extend(span);
+
+        if self.items.len() > len {
+            self.ends.push(self.items.len());
+        }
+    }
+}
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Collects all spans into a [`SpanList`].
+    ///
+    /// Unlike [`collect_spans`][SpansBy::collect_spans], this allocates a single vector for all items instead of a vector per span.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let list = "aabccc".chars().spans_by(|a, b| a == b).collect_span_list();
+    ///
+    /// assert_eq!(list.ends(), &[2, 3, 6]);
+    /// ```
+    pub fn collect_span_list(mut self) -> SpanList<I::Item> {
+        let mut list = SpanList::new();
+
+        while let Some(span) = self.next() {
+            list.extend_span(span);
+        }
+
+        list
+    }
+
+    /// Collects all spans into a [`ConnectedSpanList`], which keeps the connector to append more items.
+    ///
+    /// The connector is kept in the state after the last comparison, such that [`ConnectedSpanList::push`] continues the spans as if the pushed items had been part of the iterator.
+    pub fn collect_connected_span_list(mut self) -> ConnectedSpanList<I::Item, F> {
+        let mut list = SpanList::new();
+
+        while let Some(span) = self.next() {
+            list.extend_span(span);
+        }
+
+        ConnectedSpanList {
+            list,
+            connector: self.connector,
+        }
+    }
+}
+
+/// `ConnectedSpanList` is a [`SpanList`] together with the connector that splits its items into spans.
+///
+/// Pushing an item continues with the state of the connector, like [`SpanBuilder`][crate::SpanBuilder] does, such that stateful connectors work and the key of the last item is not made again.
+/// Create a `ConnectedSpanList` from the spans of an iterator with [`SpansBy::collect_connected_span_list`], or from a connector to push all items.
+///
+/// # Example
+///
+/// ```
+/// use spans::Spans;
+///
+/// let mut list = vec!["GET", "HEAD", "POST"]
+///     .into_iter()
+///     .spans_by_capacity(|s| s.len(), 8)
+///     .collect_connected_span_list();
+/// list.push("PUT");
+/// list.push("GET");
+///
+/// assert_eq!(
+///     list.as_list().iter().collect::<Vec<_>>(),
+///     vec![&["GET", "HEAD"][..], &["POST", "PUT"], &["GET"]]
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ConnectedSpanList<T, F> {
+    /// The collected spans.
+    list: SpanList<T>,
+    /// The connector, whose state is that after comparing the last two items.
+    connector: F,
+}
+
+impl<T, F> ConnectedSpanList<T, F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    /// Creates an empty list that compares adjacent items.
+    ///
+    /// This is the list counterpart of [`Spans::spans_by`][crate::Spans::spans_by].
+    pub fn new(are_connected: F) -> Self {
+        ConnectedSpanList::with_connector(are_connected)
+    }
+}
+
+impl<T, K, C, F> ConnectedSpanList<T, ByKey<K, C, F>>
+where
+    K: FnMut(&T) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    /// Creates an empty list that compares the keys of adjacent items.
+    ///
+    /// This is the list counterpart of [`Spans::spans_by_key`][crate::Spans::spans_by_key].
+    /// The key of each item is made once.
+    pub fn by_key(key: K, are_connected: F) -> Self {
+        ConnectedSpanList::with_connector(ByKey::new(key, are_connected))
+    }
+}
+
+impl<T, K, C> ConnectedSpanList<T, ByKeyChange<K, C>>
+where
+    K: FnMut(&T) -> C,
+    C: Clone + PartialEq,
+{
+    /// Creates an empty list that groups adjacent items with equal keys.
+    ///
+    /// This is the list counterpart of [`Spans::spans_by_key_change`][crate::Spans::spans_by_key_change].
+    pub fn by_key_change(key: K) -> Self {
+        ConnectedSpanList::by_key(key, are_equal)
+    }
+}
+
+impl<T, F> ConnectedSpanList<T, F>
+where
+    F: Connector<T>,
+{
+    /// Creates an empty list with the given connector.
+    pub fn with_connector(connector: F) -> Self {
+        ConnectedSpanList {
+            list: SpanList::new(),
+            connector,
+        }
+    }
+
+    /// Appends `item` to the last span if the connector connects it to the last item, or as a new span otherwise.
+    ///
+    /// The connector is called with the same pairs of items in the same order as if the items had been part of the collected iterator.
+    pub fn push(&mut self, item: T) {
+        let is_connected = match self.list.items.last() {
+            Some(last) => self.connector.are_connected(last, &item),
+            None => false,
+        };
+
+        if !is_connected {
+            self.connector.start_span(&item);
+        }
+
+        self.list.push_by(item, |_, _| is_connected);
+    }
+}
+
+impl<T, F> ConnectedSpanList<T, F> {
+    /// Returns the collected spans.
+    pub fn as_list(&self) -> &SpanList<T> {
+        &self.list
+    }
+
+    /// Returns a reference to the connector.
+    pub fn connector(&self) -> &F {
+        &self.connector
+    }
+
+    /// Unwraps `self`, returning the collected spans.
+    pub fn into_list(self) -> SpanList<T> {
+        self.list
+    }
+
+    /// Unwraps `self`, returning the collected spans and the connector.
+    pub fn into_parts(self) -> (SpanList<T>, F) {
+        (self.list, self.connector)
+    }
+}
+
+impl<T, F> Extend<T> for ConnectedSpanList<T, F>
+where
+    F: Connector<T>,
+{
+    fn extend<J: IntoIterator<Item = T>>(&mut self, items: J) {
+        for item in items {
+            self.push(item);
+        }
+    }
+}
+
+impl<T, F> From<ConnectedSpanList<T, F>> for SpanList<T> {
+    fn from(list: ConnectedSpanList<T, F>) -> Self {
+        list.list
+    }
+}
+
+impl<T, S> FromIterator<S> for SpanList<T>
+where
+    S: IntoIterator<Item = T>,
+{
+    fn from_iter<J: IntoIterator<Item = S>>(spans: J) -> Self {
+        let mut list = SpanList::new();
+
+        for span in spans {
+            list.extend_span(span);
+        }
+
+        list
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SpanList<T> {
+    type Item = &'a [T];
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// `Iter` is an iterator over the spans of a [`SpanList`] as slices.
+///
+/// See [`SpanList::iter`] for more information.
+#[derive(Clone)]
+pub struct Iter<'a, T> {
+    /// The items of all spans.
+    items: &'a [T],
+    /// The ends of the remaining spans.
+    ends: slice::Iter<'a, usize>,
+    /// The start of the next span.
+    start: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = *self.ends.next()?;
+        let span = &self.items[self.start..end];
+        self.start = end;
+        Some(span)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ends.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConnectedSpanList, SpanList};
+    use crate::Spans;
+
+    #[test]
+    fn test_from_iter_discards_empty_spans() {
+        let list: SpanList<i32> = vec![vec![], vec![1, 2], vec![], vec![3]]
+            .into_iter()
+            .collect();
+        assert_eq!(list.ends(), &[2, 3]);
+        assert_eq!(list.iter().len(), 2);
+        assert_eq!(
+            (&list).into_iter().collect::<Vec<_>>(),
+            vec![&[1, 2][..], &[3]]
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let list = SpanList::<i32>::new();
+        assert!(list.iter().next().is_none());
+        assert_eq!(list, SpanList::default());
+    }
+
+    #[test]
+    fn test_random_access() {
+        let list: SpanList<i32> = vec![vec![1, 2], vec![5, 6, 7], vec![11]]
+            .into_iter()
+            .collect();
+        assert_eq!((list.len(), list.span_count()), (6, 3));
+        assert_eq!(list.get(0), Some(&[1, 2][..]));
+        assert_eq!(list.get(2), Some(&[11][..]));
+        assert!(!list.is_empty());
+    }
+
+    #[test]
+    fn test_span_containing_key() {
+        let list: SpanList<i32> = vec![vec![1, 2], vec![5, 6, 7], vec![11]]
+            .into_iter()
+            .collect();
+        let found = (0..13)
+            .map(|x| list.span_containing_key(|&x| x, &x))
+            .collect::<Vec<_>>();
+        let none = None;
+        assert_eq!(
+            found,
+            vec![
+                none,
+                Some(0),
+                Some(0),
+                none,
+                none,
+                Some(1),
+                Some(1),
+                Some(1),
+                none,
+                none,
+                none,
+                Some(2),
+                none
+            ]
+        );
+        assert_eq!(SpanList::<i32>::new().span_containing_key(|&x| x, &1), None);
+    }
+
+    #[test]
+    fn test_push_matches_collect() {
+        let items = [1, 2, 5, 6, 7, 11];
+        let mut list = SpanList::new();
+        for &x in &items {
+            list.push_by_key(x, |&x| x, |a, b| a + 1 == b);
+        }
+        let collected = items
+            .iter()
+            .copied()
+            .spans_by_key(|&x| x, |a, b| a + 1 == b)
+            .collect_span_list();
+        assert_eq!(list, collected);
+    }
+
+    #[test]
+    fn test_push_span() {
+        let mut list = SpanList::new();
+        list.push_span(vec![1, 2]);
+        list.push_span(vec![]);
+        list.push_span(vec![3]);
+        assert_eq!(list.ends(), &[2, 3]);
+    }
+
+    #[test]
+    fn test_merge_matches_collect() {
+        let items = [1, 2, 5, 6, 7, 11, 12];
+        let collect = |items: &[i32]| {
+            items
+                .iter()
+                .copied()
+                .spans_by_key(|&x| x, |a, b| a + 1 == b)
+                .collect_span_list()
+        };
+
+        for seam in 0..=items.len() {
+            let mut list = collect(&items[..seam]);
+            list.merge_by_key(collect(&items[seam..]), |&x| x, |a, b| a + 1 == b);
+            assert_eq!(list, collect(&items));
+        }
+    }
+
+    #[test]
+    fn test_connected_push_matches_collect() {
+        let items = [3, 4, 1, 1, 6, 2, 2, 2, 5];
+        let collected = items
+            .iter()
+            .copied()
+            .spans_by_capacity(|&x| x, 6)
+            .collect_span_list();
+
+        for seam in 0..=items.len() {
+            let mut list = items[..seam]
+                .iter()
+                .copied()
+                .spans_by_capacity(|&x| x, 6)
+                .collect_connected_span_list();
+            list.extend(items[seam..].iter().copied());
+            assert_eq!(list.as_list(), &collected);
+        }
+    }
+
+    #[test]
+    fn test_connected_keys_are_made_once() {
+        let mut calls = 0;
+        let mut list = ConnectedSpanList::by_key(
+            |&x: &i32| {
+                calls += 1;
+                x
+            },
+            |a, b| a + 1 == b,
+        );
+        list.extend([1, 2, 5, 6, 7, 11]);
+        let list = list.into_list();
+        assert_eq!(calls, 6);
+        assert_eq!(list.ends(), &[2, 5, 6]);
+    }
+
+    #[test]
+    fn test_merge_sorted_matches_sorted_union() {
+        let collect = |items: &[i32]| {
+            items
+                .iter()
+                .copied()
+                .spans_by_key(|&x| x / 2, |a, b| a + 1 >= b)
+                .collect_span_list()
+        };
+        let inputs: &[(&[i32], &[i32])] = &[
+            (&[], &[]),
+            (&[1, 2, 9], &[]),
+            (&[], &[4, 5]),
+            (&[1, 2, 9, 20], &[3, 5, 5, 14, 15, 16]),
+            (&[10, 11], &[0, 1]),
+        ];
+
+        for &(left, right) in inputs {
+            let mut union = [left, right].concat();
+            union.sort_by_key(|&x| x / 2);
+            let mut list = collect(left);
+            list.merge_sorted_by_key(collect(right), |&x| x / 2, |a, b| a + 1 >= b);
+            assert_eq!(list, collect(&union));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let list = [1, 2, 5, 6, 7, 11]
+            .iter()
+            .copied()
+            .spans_by_key(|&x| x, |a, b| a + 1 == b)
+            .collect_span_list();
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, r#"{"items":[1,2,5,6,7,11],"ends":[2,5,6]}"#);
+        assert_eq!(serde_json::from_str::<SpanList<i32>>(&json).unwrap(), list);
+        assert_eq!(
+            serde_json::from_str::<SpanList<i32>>(r#"{"items":[],"ends":[]}"#).unwrap(),
+            SpanList::new()
+        );
+
+        for invalid in [
+            r#"{"items":[1,2],"ends":[]}"#,
+            r#"{"items":[1,2],"ends":[0,2]}"#,
+            r#"{"items":[1,2],"ends":[2,1]}"#,
+            r#"{"items":[1,2],"ends":[1,3]}"#,
+        ] {
+            assert!(serde_json::from_str::<SpanList<i32>>(invalid).is_err());
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{Connector, Span, SpansBy};
+use core::iter::FusedIterator;
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Converts `self` into an iterator that maps each span to a value using `f`.
+    ///
+    /// `f` is given each span in order and its return value is yielded by the returned [`MapSpans`].
+    /// Items of a span that `f` does not consume are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let sums: Vec<i32> = vec
+    ///     .iter()
+    ///     .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+    ///     .map_spans(|span| span.sum())
+    ///     .collect();
+    ///
+    /// assert_eq!(sums, vec![3, 18, 11]);
+    /// ```
+    pub fn map_spans<G, R>(self, f: G) -> MapSpans<I, F, G>
+    where
+        G: FnMut(Span<'_, I, F>) -> R,
+    {
+        MapSpans { spans: self, f }
+    }
+}
+
+/// `MapSpans` is an iterator that maps the spans of a `SpansBy` to values.
+///
+/// See [`SpansBy::map_spans`] for more information.
+pub struct MapSpans<I: Iterator, F, G> {
+    /// The wrapped spans.
+    spans: SpansBy<I, F>,
+    /// The function mapping a span to a value.
+    f: G,
+}
+
+impl<I, F, G, R> Iterator for MapSpans<I, F, G>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+    G: FnMut(Span<'_, I, F>) -> R,
+{
+    type Item = R;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let span = self.spans.next()?;
+        Some((self.f)(span))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.span_count_hint()
+    }
+}
+
+impl<I, F, G, R> FusedIterator for MapSpans<I, F, G>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+    G: FnMut(Span<'_, I, F>) -> R,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_empty() {
+        let mut spans = []
+            .iter()
+            .spans_by_key_change(|&&x: &&u8| x)
+            .map_spans(|span| span.count());
+        assert_eq!(spans.next(), None);
+    }
+
+    #[test]
+    fn test_adapters() {
+        let spans = "aabcccdd".chars().spans_by(|a, b| a == b);
+        let long_spans = spans
+            .map_spans(|span| span.collect::<String>())
+            .filter(|span| span.len() > 1)
+            .collect::<Vec<_>>();
+        assert_eq!(long_spans, vec!["aa", "ccc", "dd"]);
+    }
+}
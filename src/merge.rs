@@ -0,0 +1,222 @@
+use crate::{Connector, SpansBy};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+/// `MergePolicy` determines the neighbor into which a short span is merged.
+///
+/// See [`SpansBy::merge_short_spans`] for more information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MergePolicy {
+    /// Short spans are appended to the preceding span.
+    ///
+    /// Short spans at the start of the iterator have no preceding span and are prepended to the following span instead.
+    Preceding,
+    /// Short spans are prepended to the following span.
+    ///
+    /// Short spans at the end of the iterator have no following span and are appended to the preceding span instead.
+    Following,
+}
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Converts `self` into an iterator over owned spans, where spans shorter than `min_len` are merged into a neighboring span.
+    ///
+    /// `policy` determines whether a short span is merged into the preceding or the following span.
+    /// Merged spans are merged further as needed, such that all returned spans have at least `min_len` items, unless the iterator has fewer than `min_len` items in total, in which case all items are returned in a single span.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::{MergePolicy, Spans};
+    ///
+    /// let vec = vec![1, 1, 1, 2, 1, 1, 3, 3, 3];
+    ///
+    /// let spans: Vec<_> = vec
+    ///     .iter()
+    ///     .spans_by_key_change(|&&x| x)
+    ///     .merge_short_spans(2, MergePolicy::Preceding)
+    ///     .collect();
+    /// assert_eq!(spans, vec![vec![&1, &1, &1, &2], vec![&1, &1], vec![&3, &3, &3]]);
+    ///
+    /// let spans: Vec<_> = vec
+    ///     .iter()
+    ///     .spans_by_key_change(|&&x| x)
+    ///     .merge_short_spans(2, MergePolicy::Following)
+    ///     .collect();
+    /// assert_eq!(spans, vec![vec![&1, &1, &1], vec![&2, &1, &1], vec![&3, &3, &3]]);
+    /// ```
+    pub fn merge_short_spans(self, min_len: usize, policy: MergePolicy) -> MergeShortSpans<I, F> {
+        MergeShortSpans {
+            spans: self,
+            min_len,
+            policy,
+            pending: None,
+            ready: None,
+        }
+    }
+}
+
+/// `MergeShortSpans` is an iterator over the owned spans of a `SpansBy`, where short spans are merged into their neighbors.
+///
+/// See [`SpansBy::merge_short_spans`] for more information.
+pub struct MergeShortSpans<I: Iterator, F> {
+    /// The wrapped spans.
+    spans: SpansBy<I, F>,
+    /// The minimum number of items per span.
+    min_len: usize,
+    /// The neighbor into which a short span is merged.
+    policy: MergePolicy,
+    /// The span that may still be merged with the next span.
+    pending: Option<Vec<I::Item>>,
+    /// The complete span preceding `pending`, which is held back for `MergePolicy::Following`.
+    ready: Option<Vec<I::Item>>,
+}
+
+impl<I, F> Iterator for MergeShortSpans<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let span: Vec<_> = match self.spans.next() {
+                Some(span) => span.collect(),
+                None => return self.finish(),
+            };
+
+            let mut pending = match self.pending.take() {
+                Some(pending) => pending,
+                None => {
+                    self.pending = Some(span);
+                    continue;
+                }
+            };
+
+            let is_merged = pending.len() < self.min_len
+                || (self.policy == MergePolicy::Preceding && span.len() < self.min_len);
+
+            if is_merged {
+                pending.extend(span);
+                self.pending = Some(pending);
+            } else {
+                self.pending = Some(span);
+
+                match self.policy {
+                    MergePolicy::Preceding => return Some(pending),
+                    MergePolicy::Following => {
+                        // The span is held back in case the spans at the end are too short to stand alone.
+                        if let Some(ready) = self.ready.replace(pending) {
+                            return Some(ready);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<I, F> FusedIterator for MergeShortSpans<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+}
+
+impl<I, F> MergeShortSpans<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Returns the remaining spans after the wrapped spans terminated.
+    fn finish(&mut self) -> Option<Vec<I::Item>> {
+        match (self.ready.take(), self.pending.take()) {
+            (Some(mut ready), Some(pending)) if pending.len() < self.min_len => {
+                ready.extend(pending);
+                Some(ready)
+            }
+            (Some(ready), pending) => {
+                self.pending = pending;
+                Some(ready)
+            }
+            (None, pending) => pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MergePolicy;
+    use crate::Spans;
+
+    fn merge(s: &str, min_len: usize, policy: MergePolicy) -> Vec<String> {
+        s.chars()
+            .spans_by(|a, b| a == b)
+            .merge_short_spans(min_len, policy)
+            .map(|span| span.into_iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(merge("", 2, MergePolicy::Preceding).is_empty());
+        assert!(merge("", 2, MergePolicy::Following).is_empty());
+    }
+
+    #[test]
+    fn test_without_short_spans() {
+        assert_eq!(merge("aabbb", 2, MergePolicy::Preceding), vec!["aa", "bbb"]);
+        assert_eq!(merge("aabbb", 2, MergePolicy::Following), vec!["aa", "bbb"]);
+    }
+
+    #[test]
+    fn test_leading_short_spans() {
+        assert_eq!(merge("abccc", 3, MergePolicy::Preceding), vec!["abccc"]);
+        assert_eq!(merge("abcccdd", 3, MergePolicy::Preceding), vec!["abcccdd"]);
+        assert_eq!(
+            merge("abcccddd", 3, MergePolicy::Preceding),
+            vec!["abccc", "ddd"]
+        );
+        assert_eq!(
+            merge("abcccddd", 3, MergePolicy::Following),
+            vec!["abccc", "ddd"]
+        );
+    }
+
+    #[test]
+    fn test_trailing_short_spans() {
+        assert_eq!(merge("aaabc", 3, MergePolicy::Preceding), vec!["aaabc"]);
+        assert_eq!(merge("aaabc", 3, MergePolicy::Following), vec!["aaabc"]);
+        assert_eq!(
+            merge("aaabcdd", 3, MergePolicy::Following),
+            vec!["aaa", "bcdd"]
+        );
+    }
+
+    #[test]
+    fn test_inner_short_spans() {
+        assert_eq!(
+            merge("aaabccc", 3, MergePolicy::Preceding),
+            vec!["aaab", "ccc"]
+        );
+        assert_eq!(
+            merge("aaabccc", 3, MergePolicy::Following),
+            vec!["aaa", "bccc"]
+        );
+        assert_eq!(
+            merge("aaabcddd", 3, MergePolicy::Following),
+            vec!["aaa", "bcddd"]
+        );
+    }
+
+    #[test]
+    fn test_too_few_items() {
+        assert_eq!(merge("ab", 3, MergePolicy::Preceding), vec!["ab"]);
+        assert_eq!(merge("ab", 3, MergePolicy::Following), vec!["ab"]);
+    }
+}
@@ -0,0 +1,386 @@
+//! Compression of IP addresses into CIDR blocks.
+//!
+//! Use [`ipv4_range_blocks`] or [`ipv6_range_blocks`] to split a range of addresses into the minimal list of CIDR blocks, such as for firewall rules or allowlists:
+//!
+//! ```
+//! use spans::net;
+//! use std::net::Ipv4Addr;
+//!
+//! let range = Ipv4Addr::new(192, 168, 0, 0)..=Ipv4Addr::new(192, 168, 0, 9);
+//! let blocks: Vec<_> = net::ipv4_range_blocks(range).collect();
+//!
+//! assert_eq!(blocks, vec![(Ipv4Addr::new(192, 168, 0, 0), 29), (Ipv4Addr::new(192, 168, 0, 8), 31)]);
+//! ```
+//!
+//! Each block is given as its first address and its prefix length.
+//! With the `alloc` feature, `compress_ipv4` and `compress_ipv6` group any set of addresses into spans of consecutive addresses first, and then split each span into blocks.
+//! With the `ipnet` feature, the blocks can also be converted into `ipnet` networks.
+
+#[cfg(feature = "alloc")]
+use crate::{SpanKeyRanges, Spans};
+#[cfg(feature = "alloc")]
+use alloc::vec::{self, Vec};
+use core::iter::FusedIterator;
+#[cfg(feature = "ipnet")]
+use core::iter::Map;
+use core::net::{Ipv4Addr, Ipv6Addr};
+use core::ops::RangeInclusive;
+#[cfg(feature = "ipnet")]
+use ipnet::{Ipv4Net, Ipv6Net};
+
+/// Defines a function that splits the first CIDR block off a range of addresses given as integers.
+macro_rules! split_block {
+    ($name:ident, $t:ty) => {
+        /// Returns the first address and prefix length of the largest aligned CIDR block at the start of `start..=end`, and the start of the rest of the range.
+        fn $name(start: $t, end: $t) -> ($t, u8, Option<$t>) {
+            // The number of host bits is limited by the alignment of `start` and by the length of the range, which is `floor(log2(end - start + 1))` bits.
+            let len_bits = match (end - start).checked_add(1) {
+                Some(len) => <$t>::BITS - 1 - len.leading_zeros(),
+                None => <$t>::BITS,
+            };
+            let host_bits = start.trailing_zeros().min(len_bits);
+            let last = match host_bits {
+                <$t>::BITS => <$t>::MAX,
+                _ => start | ((1 << host_bits) - 1),
+            };
+            let rest = if last == end { None } else { Some(last + 1) };
+            (start, (<$t>::BITS - host_bits) as u8, rest)
+        }
+    };
+}
+
+split_block!(split_ipv4_block, u32);
+split_block!(split_ipv6_block, u128);
+
+/// Returns an iterator over the minimal list of CIDR blocks that cover `range`.
+///
+/// The blocks are yielded in ascending order as their first address and their prefix length.
+/// A range contains at most 62 blocks.
+///
+/// # Example
+///
+/// ```
+/// use spans::net;
+/// use std::net::Ipv4Addr;
+///
+/// let range = Ipv4Addr::new(10, 0, 0, 1)..=Ipv4Addr::new(10, 0, 0, 6);
+/// let blocks: Vec<_> = net::ipv4_range_blocks(range).collect();
+///
+/// assert_eq!(
+///     blocks,
+///     vec![
+///         (Ipv4Addr::new(10, 0, 0, 1), 32),
+///         (Ipv4Addr::new(10, 0, 0, 2), 31),
+///         (Ipv4Addr::new(10, 0, 0, 4), 31),
+///         (Ipv4Addr::new(10, 0, 0, 6), 32),
+///     ]
+/// );
+/// ```
+pub fn ipv4_range_blocks(range: RangeInclusive<Ipv4Addr>) -> Ipv4RangeBlocks {
+    let (start, end) = (u32::from(*range.start()), u32::from(*range.end()));
+
+    Ipv4RangeBlocks {
+        next: if start <= end { Some(start) } else { None },
+        end,
+    }
+}
+
+/// Returns an iterator over the minimal list of CIDR blocks that cover `range`.
+///
+/// This is like [`ipv4_range_blocks`] for IPv6 addresses.
+/// A range contains at most 254 blocks.
+pub fn ipv6_range_blocks(range: RangeInclusive<Ipv6Addr>) -> Ipv6RangeBlocks {
+    let (start, end) = (u128::from(*range.start()), u128::from(*range.end()));
+
+    Ipv6RangeBlocks {
+        next: if start <= end { Some(start) } else { None },
+        end,
+    }
+}
+
+/// `Ipv4RangeBlocks` is an iterator over the CIDR blocks that cover a range of IPv4 addresses.
+///
+/// See [`ipv4_range_blocks`] for more information.
+#[derive(Clone, Debug)]
+pub struct Ipv4RangeBlocks {
+    /// The first address that is not covered by a yielded block, or `None` if the whole range is covered.
+    next: Option<u32>,
+    /// The last address of the range.
+    end: u32,
+}
+
+impl Iterator for Ipv4RangeBlocks {
+    type Item = (Ipv4Addr, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, prefix_len, rest) = split_ipv4_block(self.next?, self.end);
+        self.next = rest;
+        Some((Ipv4Addr::from(start), prefix_len))
+    }
+}
+
+impl FusedIterator for Ipv4RangeBlocks {}
+
+/// `Ipv6RangeBlocks` is an iterator over the CIDR blocks that cover a range of IPv6 addresses.
+///
+/// See [`ipv6_range_blocks`] for more information.
+#[derive(Clone, Debug)]
+pub struct Ipv6RangeBlocks {
+    /// The first address that is not covered by a yielded block, or `None` if the whole range is covered.
+    next: Option<u128>,
+    /// The last address of the range.
+    end: u128,
+}
+
+impl Iterator for Ipv6RangeBlocks {
+    type Item = (Ipv6Addr, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, prefix_len, rest) = split_ipv6_block(self.next?, self.end);
+        self.next = rest;
+        Some((Ipv6Addr::from(start), prefix_len))
+    }
+}
+
+impl FusedIterator for Ipv6RangeBlocks {}
+
+/// The key ranges of the spans of sorted addresses given as integers.
+#[cfg(feature = "alloc")]
+type AddrRanges<T> = SpanKeyRanges<vec::IntoIter<T>, fn(&T) -> T, T, fn(T, T) -> bool>;
+
+/// Returns an iterator over the minimal list of CIDR blocks that contain exactly the addresses of `addrs`.
+///
+/// The addresses may be given in any order and may contain duplicates.
+/// The blocks are yielded in ascending order as their first address and their prefix length.
+///
+/// # Example
+///
+/// ```
+/// use spans::net;
+/// use std::net::Ipv4Addr;
+///
+/// let addrs = vec![Ipv4Addr::new(10, 0, 0, 3), Ipv4Addr::new(10, 0, 0, 2), Ipv4Addr::new(10, 0, 1, 0)];
+/// let blocks: Vec<_> = net::compress_ipv4(addrs).collect();
+///
+/// assert_eq!(blocks, vec![(Ipv4Addr::new(10, 0, 0, 2), 31), (Ipv4Addr::new(10, 0, 1, 0), 32)]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn compress_ipv4<A>(addrs: A) -> Ipv4Blocks
+where
+    A: IntoIterator<Item = Ipv4Addr>,
+{
+    let mut addrs: Vec<u32> = addrs.into_iter().map(u32::from).collect();
+    addrs.sort_unstable();
+    addrs.dedup();
+
+    Ipv4Blocks {
+        ranges: addrs.into_iter().consecutive_spans().span_key_ranges(),
+        blocks: None,
+    }
+}
+
+/// Returns an iterator over the minimal list of CIDR blocks that contain exactly the addresses of `addrs`.
+///
+/// This is like [`compress_ipv4`] for IPv6 addresses.
+#[cfg(feature = "alloc")]
+pub fn compress_ipv6<A>(addrs: A) -> Ipv6Blocks
+where
+    A: IntoIterator<Item = Ipv6Addr>,
+{
+    let mut addrs: Vec<u128> = addrs.into_iter().map(u128::from).collect();
+    addrs.sort_unstable();
+    addrs.dedup();
+
+    Ipv6Blocks {
+        ranges: addrs.into_iter().consecutive_spans().span_key_ranges(),
+        blocks: None,
+    }
+}
+
+/// `Ipv4Blocks` is an iterator over the CIDR blocks of a set of IPv4 addresses.
+///
+/// See [`compress_ipv4`] for more information.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct Ipv4Blocks {
+    /// The ranges of consecutive addresses.
+    ranges: AddrRanges<u32>,
+    /// The blocks of the current range.
+    blocks: Option<Ipv4RangeBlocks>,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for Ipv4Blocks {
+    type Item = (Ipv4Addr, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(block) = self.blocks.as_mut().and_then(Iterator::next) {
+                return Some(block);
+            }
+
+            let range = self.ranges.next()?;
+            let range = Ipv4Addr::from(*range.start())..=Ipv4Addr::from(*range.end());
+            self.blocks = Some(ipv4_range_blocks(range));
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FusedIterator for Ipv4Blocks {}
+
+/// `Ipv6Blocks` is an iterator over the CIDR blocks of a set of IPv6 addresses.
+///
+/// See [`compress_ipv6`] for more information.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct Ipv6Blocks {
+    /// The ranges of consecutive addresses.
+    ranges: AddrRanges<u128>,
+    /// The blocks of the current range.
+    blocks: Option<Ipv6RangeBlocks>,
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for Ipv6Blocks {
+    type Item = (Ipv6Addr, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(block) = self.blocks.as_mut().and_then(Iterator::next) {
+                return Some(block);
+            }
+
+            let range = self.ranges.next()?;
+            let range = Ipv6Addr::from(*range.start())..=Ipv6Addr::from(*range.end());
+            self.blocks = Some(ipv6_range_blocks(range));
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FusedIterator for Ipv6Blocks {}
+
+/// `Ipv4Nets` is an iterator over CIDR blocks as [`Ipv4Net`]s.
+///
+/// See [`ipv4_nets`] for more information.
+#[cfg(feature = "ipnet")]
+pub type Ipv4Nets<I> = Map<I, fn((Ipv4Addr, u8)) -> Ipv4Net>;
+
+/// `Ipv6Nets` is an iterator over CIDR blocks as [`Ipv6Net`]s.
+///
+/// See [`ipv6_nets`] for more information.
+#[cfg(feature = "ipnet")]
+pub type Ipv6Nets<I> = Map<I, fn((Ipv6Addr, u8)) -> Ipv6Net>;
+
+/// Converts the CIDR blocks of `blocks` into [`Ipv4Net`]s.
+///
+/// Requires the `ipnet` feature.
+///
+/// # Example
+///
+/// ```
+/// use spans::net;
+/// use std::net::Ipv4Addr;
+///
+/// let range = Ipv4Addr::new(192, 168, 0, 0)..=Ipv4Addr::new(192, 168, 0, 9);
+/// let nets: Vec<String> = net::ipv4_nets(net::ipv4_range_blocks(range)).map(|net| net.to_string()).collect();
+///
+/// assert_eq!(nets, vec!["192.168.0.0/29", "192.168.0.8/31"]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if a prefix length is greater than 32, which is never the case for the blocks of this module.
+#[cfg(feature = "ipnet")]
+pub fn ipv4_nets<I>(blocks: I) -> Ipv4Nets<I>
+where
+    I: Iterator<Item = (Ipv4Addr, u8)>,
+{
+    blocks.map(|(addr, prefix_len)| Ipv4Net::new(addr, prefix_len).expect("invalid prefix length"))
+}
+
+/// Converts the CIDR blocks of `blocks` into [`Ipv6Net`]s.
+///
+/// This is like [`ipv4_nets`] for IPv6 addresses.
+/// Requires the `ipnet` feature.
+///
+/// # Panics
+///
+/// Panics if a prefix length is greater than 128, which is never the case for the blocks of this module.
+#[cfg(feature = "ipnet")]
+pub fn ipv6_nets<I>(blocks: I) -> Ipv6Nets<I>
+where
+    I: Iterator<Item = (Ipv6Addr, u8)>,
+{
+    blocks.map(|(addr, prefix_len)| Ipv6Net::new(addr, prefix_len).expect("invalid prefix length"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ipv4_range_blocks, ipv6_range_blocks};
+    use core::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn test_range_bounds() {
+        let all =
+            ipv4_range_blocks(Ipv4Addr::UNSPECIFIED..=Ipv4Addr::BROADCAST).collect::<Vec<_>>();
+        assert_eq!(all, vec![(Ipv4Addr::UNSPECIFIED, 0)]);
+        let last = ipv4_range_blocks(Ipv4Addr::BROADCAST..=Ipv4Addr::BROADCAST).collect::<Vec<_>>();
+        assert_eq!(last, vec![(Ipv4Addr::BROADCAST, 32)]);
+        assert_eq!(
+            ipv4_range_blocks(Ipv4Addr::BROADCAST..=Ipv4Addr::UNSPECIFIED).next(),
+            None
+        );
+
+        let max = Ipv6Addr::from(u128::MAX);
+        let all = ipv6_range_blocks(Ipv6Addr::UNSPECIFIED..=max).collect::<Vec<_>>();
+        assert_eq!(all, vec![(Ipv6Addr::UNSPECIFIED, 0)]);
+        assert_eq!(ipv6_range_blocks(Ipv6Addr::from(1)..=max).count(), 128);
+    }
+
+    #[test]
+    fn test_blocks_cover_range() {
+        let blocks = ipv4_range_blocks(Ipv4Addr::from(3)..=Ipv4Addr::from(200));
+        let prefix_lens = blocks.map(|(_, prefix_len)| prefix_len).collect::<Vec<_>>();
+        assert_eq!(prefix_lens, vec![32, 30, 29, 28, 27, 26, 26, 29, 32]);
+
+        let ranges = [
+            (3, 200),
+            (0, 255),
+            (1, 1),
+            (64, 127),
+            (4_000_000_000, u32::MAX),
+        ];
+
+        for &(start, end) in &ranges {
+            let blocks =
+                ipv4_range_blocks(Ipv4Addr::from(start)..=Ipv4Addr::from(end)).collect::<Vec<_>>();
+            let mut next = u64::from(start);
+
+            for &(addr, prefix_len) in &blocks {
+                let size = 1u64 << (32 - prefix_len);
+                assert_eq!(u64::from(u32::from(addr)), next);
+                assert_eq!(next % size, 0);
+                next += size;
+            }
+
+            assert_eq!(next, u64::from(end) + 1);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_compress_ipv6() {
+        let addrs = (0..=4u128).chain(Some(u128::MAX)).map(Ipv6Addr::from).rev();
+        let blocks = super::compress_ipv6(addrs).collect::<Vec<_>>();
+        assert_eq!(
+            blocks,
+            vec![
+                (Ipv6Addr::from(0), 126),
+                (Ipv6Addr::from(4), 128),
+                (Ipv6Addr::from(u128::MAX), 128)
+            ]
+        );
+    }
+}
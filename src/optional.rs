@@ -0,0 +1,88 @@
+use crate::Connector;
+
+/// `ByOptionalKey` is a [`Connector`] that compares items by their optional keys.
+///
+/// Items without a key are not connected to any item.
+///
+/// See [`Spans::spans_by_optional_key`][crate::Spans::spans_by_optional_key] for more information.
+#[derive(Clone)]
+pub struct ByOptionalKey<K, C, F> {
+    /// A function transforming an iterator item to an optional comparison key.
+    key: K,
+    /// Whether two iterator items belong to the same span as determined by their respective keys.
+    are_connected: F,
+    /// The key of the item most recently passed as `next` to `Connector::are_connected`.
+    ///
+    /// `None` before the first comparison.
+    prev_key: Option<Option<C>>,
+}
+
+impl<K, C, F> ByOptionalKey<K, C, F> {
+    /// Creates a new `ByOptionalKey` connector.
+    pub(crate) fn new(key: K, are_connected: F) -> Self {
+        ByOptionalKey {
+            key,
+            are_connected,
+            prev_key: None,
+        }
+    }
+}
+
+impl<T, K, C, F> Connector<T> for ByOptionalKey<K, C, F>
+where
+    K: FnMut(&T) -> Option<C>,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        // Items are compared pairwise in iteration order, so the stored key is the key of `prev`.
+        let prev_key = match self.prev_key.take() {
+            Some(prev_key) => prev_key,
+            None => (self.key)(prev),
+        };
+        let next_key = (self.key)(next);
+        let is_connected = match (prev_key, &next_key) {
+            (Some(prev_key), Some(next_key)) => (self.are_connected)(prev_key, next_key.clone()),
+            _ => false,
+        };
+        self.prev_key = Some(next_key);
+        is_connected
+    }
+
+    fn reset(&mut self) {
+        self.prev_key = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_none_keys_are_isolated() {
+        let spans = "aa--bb-b"
+            .chars()
+            .spans_by_optional_key(|&c| if c == '-' { None } else { Some(c) }, |a, b| a == b)
+            .map_spans(|span| span.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec!["aa", "-", "-", "bb", "-", "b"]);
+    }
+
+    #[test]
+    fn test_keys_are_computed_once() {
+        let mut calls = 0;
+        let spans = [1, 2, 3]
+            .iter()
+            .spans_by_optional_key(
+                |&&x| {
+                    calls += 1;
+                    Some(x)
+                },
+                |a, b| a + 1 == b,
+            )
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![3]);
+        assert_eq!(calls, 3);
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{ByKey, Connector, SpansBy};
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Converts `self` into an iterator over owned spans.
+    ///
+    /// Each span is collected into a [`Vec`] before it is returned.
+    /// Since owned spans do not borrow their parent, the returned [`OwnedSpans`] implements [`Iterator`] and can be used in `for` loops and with iterator adapters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let spans = vec
+    ///     .into_iter()
+    ///     .spans_by_key(|&x| x, |a, b| a + 1 == b)
+    ///     .into_owned_spans();
+    ///
+    /// let lengths: Vec<usize> = spans.map(|span| span.len()).collect();
+    /// assert_eq!(lengths, vec![2, 3, 1]);
+    /// ```
+    pub fn into_owned_spans(self) -> OwnedSpans<I, F> {
+        OwnedSpans { spans: self }
+    }
+
+    /// Collects all spans into a vector of vectors.
+    ///
+    /// This is equivalent to `self.into_owned_spans().collect()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let spans = vec
+    ///     .into_iter()
+    ///     .spans_by_key(|&x| x, |a, b| a + 1 == b)
+    ///     .collect_spans();
+    ///
+    /// assert_eq!(spans, vec![vec![1, 2], vec![5, 6, 7], vec![11]]);
+    /// ```
+    pub fn collect_spans(self) -> Vec<Vec<I::Item>> {
+        self.into_owned_spans().collect()
+    }
+
+    /// Collects all spans into a vector of vectors, treating the iterator as circular.
+    ///
+    /// This is like [`collect_spans`][SpansBy::collect_spans], except that the last item of the iterator is compared with the first item of the iterator.
+    /// If they are connected, the first span is appended to the last span, since the two spans form a single span that wraps around.
+    /// This is useful for cyclic domains, such as hours of a day or angles.
+    ///
+    /// The connector is called once more for this comparison; a [`ByKey`] connector thus makes the key of the first item a second time.
+    /// [`Spans::circular_spans_by_key`][crate::Spans::circular_spans_by_key] reuses the key instead.
+    ///
+    /// See [`Spans::circular_spans_by_key`][crate::Spans::circular_spans_by_key] for an example.
+    pub fn collect_circular_spans(mut self) -> Vec<Vec<I::Item>> {
+        let mut spans = Vec::new();
+        while let Some(span) = self.next() {
+            spans.push(span.collect::<Vec<_>>());
+        }
+
+        if spans.len() > 1 {
+            let first = &spans[0][0];
+            let last = spans[spans.len() - 1].last().unwrap();
+
+            // The last item was the last item passed to the connector, such that comparing it with the first item continues in iteration order.
+            if self.connector.are_connected(last, first) {
+                join_wraparound(&mut spans);
+            }
+        }
+
+        spans
+    }
+}
+
+/// Appends the first span to the last span.
+fn join_wraparound<T>(spans: &mut Vec<Vec<T>>) {
+    let first_span = spans.remove(0);
+    if let Some(last_span) = spans.last_mut() {
+        last_span.extend(first_span);
+    }
+}
+
+impl<I, K, C, F> SpansBy<I, ByKey<K, C, F>>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    /// Collects all spans into a vector of spans paired with their keys.
+    ///
+    /// The key of a span is the key of its first item, as returned by [`Span::key`][crate::Span::key].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![("a", 1), ("a", 2), ("b", 3), ("c", 4), ("c", 5)];
+    /// let groups = vec
+    ///     .into_iter()
+    ///     .spans_by_key_change(|&(name, _)| name)
+    ///     .collect_grouped();
+    ///
+    /// assert_eq!(
+    ///     groups,
+    ///     vec![
+    ///         ("a", vec![("a", 1), ("a", 2)]),
+    ///         ("b", vec![("b", 3)]),
+    ///         ("c", vec![("c", 4), ("c", 5)]),
+    ///     ]
+    /// );
+    /// ```
+    pub fn collect_grouped(mut self) -> Vec<(C, Vec<I::Item>)> {
+        let mut groups = Vec::new();
+
+        while let Some(span) = self.next() {
+            let key = span.key().clone();
+            groups.push((key, span.collect()));
+        }
+
+        groups
+    }
+
+    /// Collects all spans like [`collect_circular_spans`][SpansBy::collect_circular_spans], but compares the keys of the last and the first item without making them again.
+    pub(crate) fn collect_circular_spans_by_key(mut self) -> Vec<Vec<I::Item>> {
+        let mut spans = Vec::new();
+        let mut first_key = None;
+
+        while let Some(span) = self.next() {
+            if first_key.is_none() {
+                first_key = Some(span.key().clone());
+            }
+            spans.push(span.collect::<Vec<_>>());
+        }
+
+        if let (true, Some(first_key)) = (spans.len() > 1, first_key) {
+            // The last item was the last item passed to the connector as `next`, such that the stored key is its key.
+            let last_key = match self.connector.prev_key.take() {
+                Some(key) => key,
+                None => (self.connector.key)(spans[spans.len() - 1].last().unwrap()),
+            };
+
+            if (self.connector.are_connected)(last_key, first_key) {
+                join_wraparound(&mut spans);
+            }
+        }
+
+        spans
+    }
+}
+
+/// `OwnedSpans` is an iterator over the spans of a `SpansBy`, collected into vectors.
+///
+/// See [`SpansBy::into_owned_spans`] for more information.
+pub struct OwnedSpans<I: Iterator, F> {
+    /// The wrapped spans.
+    spans: SpansBy<I, F>,
+}
+
+impl<I, F> Iterator for OwnedSpans<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.spans.next().map(|span| span.collect())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.span_count_hint()
+    }
+}
+
+impl<I, F> FusedIterator for OwnedSpans<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_empty() {
+        let mut spans = Vec::<u32>::new()
+            .into_iter()
+            .spans_by_key_change(|&x| x)
+            .into_owned_spans();
+        assert_eq!(spans.next(), None);
+    }
+
+    #[test]
+    fn test_for_loop() {
+        let spans = "aabccc".chars().spans_by(|a, b| a == b).into_owned_spans();
+        let mut strings = Vec::new();
+        for span in spans {
+            strings.push(span.into_iter().collect::<String>());
+        }
+        assert_eq!(strings, vec!["aa", "b", "ccc"]);
+    }
+
+    #[test]
+    fn test_collect_spans() {
+        let spans = "aabccc".chars().spans_by(|a, b| a == b).collect_spans();
+        assert_eq!(spans, vec![vec!['a', 'a'], vec!['b'], vec!['c', 'c', 'c']]);
+    }
+
+    #[test]
+    fn test_collect_spans_empty() {
+        let spans = "".chars().spans_by(|a, b| a == b).collect_spans();
+        assert!(spans.is_empty());
+    }
+
+    #[test]
+    fn test_collect_grouped() {
+        let groups = [1, 2, 4, 5, 7]
+            .iter()
+            .spans_by_key(|&&x| x / 3, |a, b| a == b)
+            .collect_grouped();
+        assert_eq!(
+            groups,
+            vec![(0, vec![&1, &2]), (1, vec![&4, &5]), (2, vec![&7])]
+        );
+    }
+
+    #[test]
+    fn test_circular_without_wraparound() {
+        let spans = [1, 2, 5, 6]
+            .iter()
+            .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+            .collect_circular_spans();
+        assert_eq!(spans, vec![vec![&1, &2], vec![&5, &6]]);
+    }
+
+    #[test]
+    fn test_circular_keys_are_made_once() {
+        let mut calls = 0;
+        let spans = [0, 1, 5, 22, 23].iter().circular_spans_by_key(
+            |&&x| {
+                calls += 1;
+                x
+            },
+            |a, b| (a + 1) % 24 == b,
+        );
+        assert_eq!(spans, vec![vec![&5], vec![&22, &23, &0, &1]]);
+        assert_eq!(calls, 5);
+    }
+
+    #[test]
+    fn test_circular_single_span() {
+        let spans = "aaa"
+            .chars()
+            .spans_by(|a, b| a == b)
+            .collect_circular_spans();
+        assert_eq!(spans, vec![vec!['a', 'a', 'a']]);
+    }
+
+    #[test]
+    fn test_circular_two_spans() {
+        let spans = "aabba"
+            .chars()
+            .spans_by(|a, b| a == b)
+            .collect_circular_spans();
+        assert_eq!(spans, vec![vec!['b', 'b'], vec!['a', 'a', 'a']]);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{Connector, SpansBy};
+use alloc::vec::Vec;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Maps each span to a value in parallel and returns the values in span order.
+    ///
+    /// The spans are first collected sequentially, like with [`collect_spans`][SpansBy::collect_spans].
+    /// Then `f` is called with each span on the threads of the [`rayon`] thread pool.
+    /// This is useful when the work done for each span is much heavier than finding the spans.
+    ///
+    /// Requires the `rayon` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let means: Vec<f64> = vec
+    ///     .into_iter()
+    ///     .spans_by_key(|&x| x, |a, b| a + 1 == b)
+    ///     .par_map_spans(|span| span.iter().sum::<i32>() as f64 / span.len() as f64);
+    ///
+    /// assert_eq!(means, vec![1.5, 6.0, 11.0]);
+    /// ```
+    pub fn par_map_spans<G, R>(self, f: G) -> Vec<R>
+    where
+        I::Item: Send,
+        G: Fn(Vec<I::Item>) -> R + Sync + Send,
+        R: Send,
+    {
+        self.collect_spans().into_par_iter().map(f).collect()
+    }
+
+    /// Folds each span into a single value in parallel and returns the values in span order.
+    ///
+    /// This is the parallel counterpart of [`fold_spans`][SpansBy::fold_spans].
+    /// The spans are first collected sequentially, then each span is folded on the threads of the [`rayon`] thread pool.
+    ///
+    /// Requires the `rayon` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let sums: Vec<i32> = vec
+    ///     .into_iter()
+    ///     .spans_by_key(|&x| x, |a, b| a + 1 == b)
+    ///     .par_fold_spans(|| 0, |sum, x| sum + x);
+    ///
+    /// assert_eq!(sums, vec![3, 18, 11]);
+    /// ```
+    pub fn par_fold_spans<B, N, G>(self, init: N, fold: G) -> Vec<B>
+    where
+        I::Item: Send,
+        B: Send,
+        N: Fn() -> B + Sync + Send,
+        G: Fn(B, I::Item) -> B + Sync + Send,
+    {
+        self.par_map_spans(|span| span.into_iter().fold(init(), &fold))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_par_map_keeps_span_order() {
+        let lengths = (0..1000)
+            .spans_by_key(|&x| x / 7, |a, b| a == b)
+            .par_map_spans(|span| (span[0], span.len()));
+        let expected = (0..1000)
+            .step_by(7)
+            .map(|start| (start, 7.min(1000 - start)))
+            .collect::<Vec<_>>();
+        assert_eq!(lengths, expected);
+    }
+
+    #[test]
+    fn test_par_fold_matches_fold() {
+        let vec = [3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5];
+        let sequential = vec
+            .iter()
+            .spans_by(|a, b| a < b)
+            .fold_spans(Vec::new, |mut acc, &x| {
+                acc.push(x);
+                acc
+            })
+            .collect::<Vec<_>>();
+        let parallel = vec
+            .iter()
+            .spans_by(|a, b| a < b)
+            .par_fold_spans(Vec::new, |mut acc, &x| {
+                acc.push(x);
+                acc
+            });
+        assert_eq!(parallel, sequential);
+    }
+}
@@ -0,0 +1,1243 @@
+//! Operations on iterators of ranges.
+//!
+//! Import the [`Ranges`] trait to coalesce iterators of [`Range`]s, which are sorted by their start:
+//!
+//! ```
+//! use spans::ranges::Ranges;
+//!
+//! let ranges = vec![0..2, 1..4, 4..5, 7..9];
+//! let coalesced: Vec<_> = ranges.into_iter().coalesce_ranges().collect();
+//!
+//! assert_eq!(coalesced, vec![0..5, 7..9]);
+//! ```
+//!
+//! The [`Ranges`] trait also provides the lazy [union][Ranges::union_ranges], [intersection][Ranges::intersection_ranges], and [difference][Ranges::difference_ranges] of two sorted iterators of ranges.
+//!
+//! Import the [`InclusiveRanges`] trait to do the same for iterators of [`RangeInclusive`]s of integers.
+//!
+//! Use [`mask_to_spans`] and [`spans_to_mask`] to convert between boolean masks and the index ranges of their runs of `true`.
+//!
+//! Use [`sweep`] to find the ranges covered by intervals given as start and end events.
+//!
+//! Use [`Ranges::coverage`] to compute how much of their extent a stream of ranges covers, and [`find_containing`] to look up the range that contains a key in a sorted slice of ranges.
+//!
+//! With the `rangemap` and `range-set-blaze` features, coalesced ranges convert into the range sets of these crates with [`From`].
+//! The ranges of these sets are sorted and disjoint, such that their iterators can be used with [`Ranges`] and [`InclusiveRanges`] directly.
+
+use crate::{ByKeyChange, Connector, Integer, Spans, SpansBy};
+use core::cmp;
+use core::iter::{self, FusedIterator, Peekable};
+use core::ops::{Add, Bound, Range, RangeBounds, RangeInclusive, Sub};
+use core::time::Duration;
+
+/// `Ranges` provides operations on iterators of [`Range`]s.
+pub trait Ranges<T>: Iterator<Item = Range<T>> {
+    /// Merges overlapping and adjacent ranges.
+    ///
+    /// The ranges must be sorted by their start.
+    /// Each yielded range is the union of a span of ranges where each range starts before or at the end of the union of the ranges before it.
+    /// If the ranges are sorted, the yielded ranges are thus sorted, non-empty, and separated by gaps.
+    /// Empty ranges are discarded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::ranges::Ranges;
+    ///
+    /// let ranges = vec![3..5, 0..0, 5..8, 6..7, 10..12];
+    /// let coalesced: Vec<_> = ranges.into_iter().coalesce_ranges().collect();
+    ///
+    /// assert_eq!(coalesced, vec![3..8, 10..12]);
+    /// ```
+    fn coalesce_ranges(self) -> CoalesceRanges<Self, T>
+    where
+        T: Ord + Clone,
+        Self: Sized,
+    {
+        CoalesceRanges {
+            spans: SpansBy::new(self, ByOverlap { end: None }),
+        }
+    }
+
+    /// Returns the ranges that are covered by `self` or by `other`.
+    ///
+    /// Both `self` and `other` must be sorted by their start.
+    /// The yielded ranges are coalesced as by [`coalesce_ranges`][Ranges::coalesce_ranges]; the ranges are merged lazily.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::ranges::Ranges;
+    ///
+    /// let a = vec![0..3, 10..12];
+    /// let b = vec![2..5, 7..8, 12..13];
+    /// let union: Vec<_> = a.into_iter().union_ranges(b).collect();
+    ///
+    /// assert_eq!(union, vec![0..5, 7..8, 10..13]);
+    /// ```
+    fn union_ranges<J>(self, other: J) -> UnionRanges<Self, J::IntoIter, T>
+    where
+        J: IntoIterator<Item = Range<T>>,
+        T: Ord + Clone,
+        Self: Sized,
+    {
+        let merged = MergeRanges {
+            a: self.peekable(),
+            b: other.into_iter().peekable(),
+        };
+        UnionRanges {
+            ranges: merged.coalesce_ranges(),
+        }
+    }
+
+    /// Returns the ranges that are covered by both `self` and `other`.
+    ///
+    /// Both `self` and `other` must be sorted by their start.
+    /// The yielded ranges are sorted, non-empty, and separated by gaps.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::ranges::Ranges;
+    ///
+    /// let a = vec![0..3, 10..12];
+    /// let b = vec![2..5, 7..11];
+    /// let intersection: Vec<_> = a.into_iter().intersection_ranges(b).collect();
+    ///
+    /// assert_eq!(intersection, vec![2..3, 10..11]);
+    /// ```
+    fn intersection_ranges<J>(self, other: J) -> IntersectionRanges<Self, J::IntoIter, T>
+    where
+        J: IntoIterator<Item = Range<T>>,
+        T: Ord + Clone,
+        Self: Sized,
+    {
+        IntersectionRanges {
+            a: self.coalesce_ranges(),
+            b: other.into_iter().coalesce_ranges(),
+            range_a: None,
+            range_b: None,
+        }
+    }
+
+    /// Returns the ranges that are covered by `self` but not by `other`.
+    ///
+    /// Both `self` and `other` must be sorted by their start.
+    /// The yielded ranges are sorted, non-empty, and separated by gaps.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::ranges::Ranges;
+    ///
+    /// let a = vec![0..10, 20..30];
+    /// let b = vec![2..3, 5..6, 25..40];
+    /// let difference: Vec<_> = a.into_iter().difference_ranges(b).collect();
+    ///
+    /// assert_eq!(difference, vec![0..2, 3..5, 6..10, 20..25]);
+    /// ```
+    fn difference_ranges<J>(self, other: J) -> DifferenceRanges<Self, J::IntoIter, T>
+    where
+        J: IntoIterator<Item = Range<T>>,
+        T: Ord + Clone,
+        Self: Sized,
+    {
+        DifferenceRanges {
+            a: self.coalesce_ranges(),
+            b: other.into_iter().coalesce_ranges(),
+            range_a: None,
+            range_b: None,
+        }
+    }
+
+    /// Returns the ranges within `bounds` that are not covered by `self`.
+    ///
+    /// `self` must be sorted by the start of the ranges.
+    /// This is equivalent to the [difference][Ranges::difference_ranges] of `bounds` and `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::ranges::Ranges;
+    ///
+    /// let used_ids = vec![0..4, 6..7, 9..12];
+    /// let free_ids: Vec<_> = used_ids.into_iter().complement_ranges(0..16).collect();
+    ///
+    /// assert_eq!(free_ids, vec![4..6, 7..9, 12..16]);
+    /// ```
+    fn complement_ranges(self, bounds: Range<T>) -> ComplementRanges<Self, T>
+    where
+        T: Ord + Clone,
+        Self: Sized,
+    {
+        ComplementRanges {
+            ranges: iter::once(bounds).difference_ranges(self),
+        }
+    }
+
+    /// Computes the total length covered by the ranges and the total length of the gaps between them.
+    ///
+    /// The ranges must be sorted by their start; overlapping ranges are [coalesced][Ranges::coalesce_ranges] first, such that no length is counted twice.
+    /// Lengths are computed by subtracting the start of a range from its end, which may have a different type, such as [`core::time::Duration`] for instants.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::ranges::Ranges;
+    ///
+    /// let coverage = vec![0..4, 2..6, 8..10].into_iter().coverage();
+    ///
+    /// assert_eq!(coverage.covered(), 8);
+    /// assert_eq!(coverage.gaps(), 2);
+    /// assert_eq!(coverage.extent(), Some(&(0..10)));
+    /// assert_eq!(coverage.fraction(), Some(0.8));
+    /// ```
+    fn coverage<D>(self) -> Coverage<T, D>
+    where
+        T: Ord + Clone + Sub<Output = D>,
+        D: Add<Output = D> + Default,
+        Self: Sized,
+    {
+        let mut coverage = Coverage {
+            covered: D::default(),
+            gaps: D::default(),
+            extent: None,
+        };
+
+        for range in self.coalesce_ranges() {
+            let extent = match coverage.extent.take() {
+                Some(extent) => {
+                    coverage.gaps = coverage.gaps + (range.start.clone() - extent.end);
+                    extent.start..range.end.clone()
+                }
+                None => range.clone(),
+            };
+            coverage.covered = coverage.covered + (range.end - range.start);
+            coverage.extent = Some(extent);
+        }
+
+        coverage
+    }
+}
+
+impl<T, I: Iterator<Item = Range<T>>> Ranges<T> for I {}
+
+/// Statistics about the coverage of an iterator of ranges.
+///
+/// See [`Ranges::coverage`] for more information.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Coverage<T, D> {
+    /// The total length of the ranges.
+    covered: D,
+    /// The total length of the gaps between the ranges.
+    gaps: D,
+    /// The range from the start of the first range to the end of the last range.
+    extent: Option<Range<T>>,
+}
+
+impl<T, D> Coverage<T, D> {
+    /// Returns the total length covered by the ranges.
+    pub fn covered(&self) -> D
+    where
+        D: Clone,
+    {
+        self.covered.clone()
+    }
+
+    /// Returns the total length of the gaps between the ranges.
+    pub fn gaps(&self) -> D
+    where
+        D: Clone,
+    {
+        self.gaps.clone()
+    }
+
+    /// Returns the range from the start of the first range to the end of the last range, or `None` if there are no non-empty ranges.
+    pub fn extent(&self) -> Option<&Range<T>> {
+        self.extent.as_ref()
+    }
+
+    /// Returns the fraction of the extent that is covered by the ranges, or `None` if there are no non-empty ranges.
+    ///
+    /// The fraction is between 0 and 1.
+    /// The lengths are converted to `f64` with [`ToF64`], which is implemented for all primitive numbers and [`Duration`].
+    pub fn fraction(&self) -> Option<f64>
+    where
+        D: Clone + ToF64,
+    {
+        self.extent.as_ref()?;
+        let covered = self.covered.clone().to_f64();
+        Some(covered / (covered + self.gaps.clone().to_f64()))
+    }
+}
+
+/// `ToF64` converts a length to an `f64` for computing ratios, such as in [`Coverage::fraction`].
+///
+/// `ToF64` is implemented for all primitive integer and floating-point types, where the conversion may round large integers, and for [`Duration`], which is converted to seconds.
+pub trait ToF64 {
+    /// Returns `self` as the nearest `f64`.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_to_f64 {
+    ($($t:ty),*) => {
+        $(
+            impl ToF64 for $t {
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_to_f64!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl ToF64 for Duration {
+    fn to_f64(self) -> f64 {
+        self.as_secs_f64()
+    }
+}
+
+/// `InclusiveRanges` provides operations on iterators of [`RangeInclusive`]s of integers.
+pub trait InclusiveRanges<T>: Iterator<Item = RangeInclusive<T>> {
+    /// Merges overlapping and adjacent ranges.
+    ///
+    /// This is like [`Ranges::coalesce_ranges`], except that two ranges are also adjacent if the start of the second range directly follows the end of the first range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::ranges::InclusiveRanges;
+    ///
+    /// let ranges = vec![1..=2, 3..=5, 7..=7, 9..=10];
+    /// let coalesced: Vec<_> = ranges.into_iter().coalesce_inclusive_ranges().collect();
+    ///
+    /// assert_eq!(coalesced, vec![1..=5, 7..=7, 9..=10]);
+    /// ```
+    fn coalesce_inclusive_ranges(self) -> CoalesceInclusiveRanges<Self, T>
+    where
+        T: Integer,
+        Self: Sized,
+    {
+        CoalesceInclusiveRanges {
+            spans: SpansBy::new(self, ByInclusiveOverlap { end: None }),
+        }
+    }
+}
+
+impl<T, I: Iterator<Item = RangeInclusive<T>>> InclusiveRanges<T> for I {}
+
+/// Returns the index of the range in `ranges` that contains `key`, or `None` if no range contains it.
+///
+/// The range is found by a binary search, which requires `ranges` to be sorted and not to overlap, such as the ranges yielded by [`Ranges::coalesce_ranges`].
+/// If the ranges are not sorted, the returned index is unspecified.
+/// Use `SpanList::span_containing_key` to search the spans of a `SpanList` instead.
+///
+/// # Example
+///
+/// ```
+/// use spans::ranges;
+///
+/// let ranges = vec![0..4, 6..7, 9..12];
+///
+/// assert_eq!(ranges::find_containing(&ranges, &10), Some(2));
+/// assert_eq!(ranges::find_containing(&ranges, &5), None);
+///
+/// let inclusive = vec![1..=2, 5..=8];
+///
+/// assert_eq!(ranges::find_containing(&inclusive, &8), Some(1));
+/// ```
+pub fn find_containing<R, T>(ranges: &[R], key: &T) -> Option<usize>
+where
+    R: RangeBounds<T>,
+    T: Ord,
+{
+    // The first range that does not end before `key` is the only candidate.
+    let index = ranges.partition_point(|range| match range.end_bound() {
+        Bound::Included(end) => end < key,
+        Bound::Excluded(end) => end <= key,
+        Bound::Unbounded => false,
+    });
+
+    if ranges.get(index)?.contains(key) {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+/// Returns the index ranges of the runs of `true` in `mask`.
+///
+/// This is the inverse of [`spans_to_mask`].
+///
+/// # Example
+///
+/// ```
+/// use spans::ranges;
+///
+/// let mask = vec![false, true, true, false, true];
+/// let runs: Vec<_> = ranges::mask_to_spans(mask).collect();
+///
+/// assert_eq!(runs, vec![1..3, 4..5]);
+/// ```
+pub fn mask_to_spans<M>(mask: M) -> MaskToSpans<M::IntoIter>
+where
+    M: IntoIterator<Item = bool>,
+{
+    MaskToSpans {
+        spans: mask.into_iter().spans_by_key_change(is_set),
+    }
+}
+
+/// Returns whether a bit of a mask is set.
+fn is_set(bit: &bool) -> bool {
+    *bit
+}
+
+/// A connector that connects equal bits.
+type ByBit = ByKeyChange<fn(&bool) -> bool, bool>;
+
+/// `MaskToSpans` is an iterator over the index ranges of the runs of `true` in a mask.
+///
+/// See [`mask_to_spans`] for more information.
+pub struct MaskToSpans<I: Iterator<Item = bool>> {
+    /// The runs of equal bits.
+    spans: SpansBy<I, ByBit>,
+}
+
+impl<I> Iterator for MaskToSpans<I>
+where
+    I: Iterator<Item = bool>,
+{
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let span = self.spans.next()?;
+
+            if *span.key() {
+                let start = span.start_index();
+                return Some(start..start + span.count());
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.spans.span_count_hint();
+        (0, upper)
+    }
+}
+
+impl<I> FusedIterator for MaskToSpans<I> where I: Iterator<Item = bool> {}
+
+/// Returns a mask of `len` bits, where the bits at the indices in `ranges` are `true`.
+///
+/// `ranges` must be sorted by their start.
+/// Indices in `ranges` at or after `len` are ignored.
+/// This is the inverse of [`mask_to_spans`].
+///
+/// # Example
+///
+/// ```
+/// use spans::ranges;
+///
+/// let mask: Vec<bool> = ranges::spans_to_mask(vec![1..3, 4..5], 6).collect();
+///
+/// assert_eq!(mask, vec![false, true, true, false, true, false]);
+/// ```
+pub fn spans_to_mask<R>(ranges: R, len: usize) -> SpansToMask<R::IntoIter>
+where
+    R: IntoIterator<Item = Range<usize>>,
+{
+    SpansToMask {
+        ranges: ranges.into_iter().peekable(),
+        index: 0,
+        len,
+    }
+}
+
+/// `SpansToMask` is an iterator over the bits of a mask given by the index ranges of its set bits.
+///
+/// See [`spans_to_mask`] for more information.
+pub struct SpansToMask<I: Iterator> {
+    /// The index ranges of the set bits.
+    ranges: Peekable<I>,
+    /// The index of the next bit.
+    index: usize,
+    /// The number of bits.
+    len: usize,
+}
+
+impl<I> Iterator for SpansToMask<I>
+where
+    I: Iterator<Item = Range<usize>>,
+{
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+
+        let index = self.index;
+        self.index += 1;
+
+        while self.ranges.next_if(|range| range.end <= index).is_some() {}
+
+        Some(matches!(self.ranges.peek(), Some(range) if range.start <= index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<I> ExactSizeIterator for SpansToMask<I> where I: Iterator<Item = Range<usize>> {}
+
+impl<I> FusedIterator for SpansToMask<I> where I: Iterator<Item = Range<usize>> {}
+
+/// An event of an interval for [`sweep`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SweepEvent {
+    /// An interval starts at the position of the event.
+    Start,
+    /// An interval ends at the position of the event.
+    End,
+}
+
+/// Returns the ranges of positions at which at least one interval is active, given the start and end events of the intervals.
+///
+/// The events must be sorted by their position.
+/// All events at the same position are applied before the number of active intervals is checked, such that an interval that ends where another interval starts does not split a range.
+/// An end event without an active interval is ignored.
+/// If intervals are still active after the last event, the range that they cover is not yielded, since it has no end.
+///
+/// # Example
+///
+/// ```
+/// use spans::ranges::{self, SweepEvent::{End, Start}};
+///
+/// let events = vec![(0, Start), (2, Start), (3, End), (5, End), (5, Start), (6, End), (8, Start), (9, End)];
+/// let covered: Vec<_> = ranges::sweep(events).collect();
+///
+/// assert_eq!(covered, vec![0..6, 8..9]);
+/// ```
+pub fn sweep<E, T>(events: E) -> Sweep<E::IntoIter, T>
+where
+    E: IntoIterator<Item = (T, SweepEvent)>,
+    T: PartialEq + Clone,
+{
+    Sweep {
+        events: events.into_iter().peekable(),
+        active: 0,
+        start: None,
+    }
+}
+
+/// `Sweep` is an iterator over the ranges of positions at which at least one interval is active.
+///
+/// See [`sweep`] for more information.
+pub struct Sweep<I: Iterator, T> {
+    /// The events of the intervals.
+    events: Peekable<I>,
+    /// The number of active intervals.
+    active: usize,
+    /// The position at which the current range started, if any interval is active.
+    start: Option<T>,
+}
+
+impl<I, T> Iterator for Sweep<I, T>
+where
+    I: Iterator<Item = (T, SweepEvent)>,
+    T: PartialEq + Clone,
+{
+    type Item = Range<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (position, event) = self.events.next()?;
+            let mut active = self.active;
+            let mut apply = |event| match event {
+                SweepEvent::Start => active += 1,
+                SweepEvent::End => active = active.saturating_sub(1),
+            };
+
+            apply(event);
+            while let Some((_, event)) = self.events.next_if(|(next, _)| *next == position) {
+                apply(event);
+            }
+
+            let was_active = self.active > 0;
+            self.active = active;
+
+            match (was_active, active > 0) {
+                (false, true) => self.start = Some(position),
+                (true, false) => return self.start.take().map(|start| start..position),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<I, T> FusedIterator for Sweep<I, T>
+where
+    I: FusedIterator<Item = (T, SweepEvent)>,
+    T: PartialEq + Clone,
+{
+}
+
+/// A connector that connects ranges that start before or at the end of the union of the previous ranges of their span.
+#[derive(Clone)]
+struct ByOverlap<T> {
+    /// The end of the union of the ranges of the current span.
+    end: Option<T>,
+}
+
+impl<T: Ord + Clone> Connector<Range<T>> for ByOverlap<T> {
+    fn are_connected(&mut self, prev: &Range<T>, next: &Range<T>) -> bool {
+        let end = match self.end.take() {
+            Some(end) => cmp::max(end, prev.end.clone()),
+            None => prev.end.clone(),
+        };
+        let is_connected = next.start <= end;
+
+        // The next span starts with `next`, so the end of the current span is no longer needed.
+        if is_connected {
+            self.end = Some(end);
+        }
+
+        is_connected
+    }
+
+    fn reset(&mut self) {
+        self.end = None;
+    }
+}
+
+/// A connector that connects inclusive ranges that start before, at, or directly after the end of the union of the previous ranges of their span.
+#[derive(Clone)]
+struct ByInclusiveOverlap<T> {
+    /// The end of the union of the ranges of the current span.
+    end: Option<T>,
+}
+
+impl<T: Integer> Connector<RangeInclusive<T>> for ByInclusiveOverlap<T> {
+    fn are_connected(&mut self, prev: &RangeInclusive<T>, next: &RangeInclusive<T>) -> bool {
+        let end = match self.end {
+            Some(end) => cmp::max(end, *prev.end()),
+            None => *prev.end(),
+        };
+        let is_connected = *next.start() <= end || end.checked_next() == Some(*next.start());
+
+        // The next span starts with `next`, so the end of the current span is no longer needed.
+        if is_connected {
+            self.end = Some(end);
+        }
+
+        is_connected
+    }
+
+    fn reset(&mut self) {
+        self.end = None;
+    }
+}
+
+/// `CoalesceRanges` is an iterator that merges overlapping and adjacent ranges.
+///
+/// See [`Ranges::coalesce_ranges`] for more information.
+pub struct CoalesceRanges<I: Iterator, T> {
+    /// The spans of connected ranges.
+    spans: SpansBy<I, ByOverlap<T>>,
+}
+
+impl<I, T> Iterator for CoalesceRanges<I, T>
+where
+    I: Iterator<Item = Range<T>>,
+    T: Ord + Clone,
+{
+    type Item = Range<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let union = self.spans.next()?.reduce(|union, range| Range {
+                start: union.start,
+                end: cmp::max(union.end, range.end),
+            })?;
+
+            if union.start < union.end {
+                return Some(union);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.spans.span_count_hint();
+        (0, upper)
+    }
+}
+
+impl<I, T> FusedIterator for CoalesceRanges<I, T>
+where
+    I: Iterator<Item = Range<T>>,
+    T: Ord + Clone,
+{
+}
+
+/// Collects the coalesced ranges into a [`rangemap::RangeSet`].
+///
+/// Requires the `rangemap` feature.
+#[cfg(feature = "rangemap")]
+impl<I, T> From<CoalesceRanges<I, T>> for rangemap::RangeSet<T>
+where
+    I: Iterator<Item = Range<T>>,
+    T: Ord + Clone,
+{
+    fn from(ranges: CoalesceRanges<I, T>) -> Self {
+        ranges.collect()
+    }
+}
+
+/// `MergeRanges` is an iterator that merges two iterators of ranges sorted by their start.
+struct MergeRanges<I: Iterator, J: Iterator> {
+    /// The first ranges.
+    a: Peekable<I>,
+    /// The second ranges.
+    b: Peekable<J>,
+}
+
+impl<I, J, T> Iterator for MergeRanges<I, J>
+where
+    I: Iterator<Item = Range<T>>,
+    J: Iterator<Item = Range<T>>,
+    T: Ord,
+{
+    type Item = Range<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.a.peek(), self.b.peek()) {
+            (Some(a), Some(b)) if b.start < a.start => self.b.next(),
+            (Some(_), _) => self.a.next(),
+            (None, _) => self.b.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => a.checked_add(b),
+            _ => None,
+        };
+        (a_lower.saturating_add(b_lower), upper)
+    }
+}
+
+/// `UnionRanges` is an iterator over the union of two iterators of ranges.
+///
+/// See [`Ranges::union_ranges`] for more information.
+pub struct UnionRanges<I, J, T>
+where
+    I: Iterator<Item = Range<T>>,
+    J: Iterator<Item = Range<T>>,
+    T: Ord,
+{
+    /// The coalesced ranges of both iterators.
+    ranges: CoalesceRanges<MergeRanges<I, J>, T>,
+}
+
+impl<I, J, T> Iterator for UnionRanges<I, J, T>
+where
+    I: Iterator<Item = Range<T>>,
+    J: Iterator<Item = Range<T>>,
+    T: Ord + Clone,
+{
+    type Item = Range<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ranges.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ranges.size_hint()
+    }
+}
+
+impl<I, J, T> FusedIterator for UnionRanges<I, J, T>
+where
+    I: Iterator<Item = Range<T>>,
+    J: Iterator<Item = Range<T>>,
+    T: Ord + Clone,
+{
+}
+
+/// `IntersectionRanges` is an iterator over the intersection of two iterators of ranges.
+///
+/// See [`Ranges::intersection_ranges`] for more information.
+pub struct IntersectionRanges<I: Iterator, J: Iterator, T> {
+    /// The coalesced first ranges.
+    a: CoalesceRanges<I, T>,
+    /// The coalesced second ranges.
+    b: CoalesceRanges<J, T>,
+    /// The current range of `a`.
+    range_a: Option<Range<T>>,
+    /// The current range of `b`.
+    range_b: Option<Range<T>>,
+}
+
+impl<I, J, T> Iterator for IntersectionRanges<I, J, T>
+where
+    I: Iterator<Item = Range<T>>,
+    J: Iterator<Item = Range<T>>,
+    T: Ord + Clone,
+{
+    type Item = Range<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let a = match self.range_a.take() {
+                Some(a) => a,
+                None => self.a.next()?,
+            };
+            let b = match self.range_b.take() {
+                Some(b) => b,
+                None => match self.b.next() {
+                    Some(b) => b,
+                    None => {
+                        self.range_a = Some(a);
+                        return None;
+                    }
+                },
+            };
+
+            let start = cmp::max(&a.start, &b.start).clone();
+            let end = cmp::min(&a.end, &b.end).clone();
+
+            // The range that ends first cannot intersect any later range of the other iterator.
+            if a.end <= b.end {
+                self.range_b = Some(b);
+            } else {
+                self.range_a = Some(a);
+            }
+
+            if start < end {
+                return Some(start..end);
+            }
+        }
+    }
+}
+
+impl<I, J, T> FusedIterator for IntersectionRanges<I, J, T>
+where
+    I: Iterator<Item = Range<T>>,
+    J: Iterator<Item = Range<T>>,
+    T: Ord + Clone,
+{
+}
+
+/// `DifferenceRanges` is an iterator over the difference of two iterators of ranges.
+///
+/// See [`Ranges::difference_ranges`] for more information.
+pub struct DifferenceRanges<I: Iterator, J: Iterator, T> {
+    /// The coalesced ranges to subtract from.
+    a: CoalesceRanges<I, T>,
+    /// The coalesced ranges to subtract.
+    b: CoalesceRanges<J, T>,
+    /// The part of the current range of `a` that has not been yielded or subtracted yet.
+    range_a: Option<Range<T>>,
+    /// The current range of `b`.
+    range_b: Option<Range<T>>,
+}
+
+impl<I, J, T> Iterator for DifferenceRanges<I, J, T>
+where
+    I: Iterator<Item = Range<T>>,
+    J: Iterator<Item = Range<T>>,
+    T: Ord + Clone,
+{
+    type Item = Range<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let a = match self.range_a.take() {
+                Some(a) => a,
+                None => self.a.next()?,
+            };
+
+            // Ranges of `b` that end before `a` cannot overlap any remaining range of `a`.
+            let b = loop {
+                match self.range_b.take().or_else(|| self.b.next()) {
+                    Some(b) if b.end <= a.start => continue,
+                    b => break b,
+                }
+            };
+
+            let b = match b {
+                Some(b) if b.start < a.end => b,
+                b => {
+                    self.range_b = b;
+                    return Some(a);
+                }
+            };
+
+            if b.end < a.end {
+                self.range_a = Some(b.end.clone()..a.end.clone());
+            }
+
+            let is_before_b = a.start < b.start;
+            let start = a.start;
+            let end = b.start.clone();
+            self.range_b = Some(b);
+
+            if is_before_b {
+                return Some(start..end);
+            }
+        }
+    }
+}
+
+impl<I, J, T> FusedIterator for DifferenceRanges<I, J, T>
+where
+    I: Iterator<Item = Range<T>>,
+    J: Iterator<Item = Range<T>>,
+    T: Ord + Clone,
+{
+}
+
+/// `ComplementRanges` is an iterator over the ranges within bounds that are not covered by an iterator of ranges.
+///
+/// See [`Ranges::complement_ranges`] for more information.
+pub struct ComplementRanges<I: Iterator, T> {
+    /// The difference of the bounds and the ranges.
+    ranges: DifferenceRanges<iter::Once<Range<T>>, I, T>,
+}
+
+impl<I, T> Iterator for ComplementRanges<I, T>
+where
+    I: Iterator<Item = Range<T>>,
+    T: Ord + Clone,
+{
+    type Item = Range<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ranges.next()
+    }
+}
+
+impl<I, T> FusedIterator for ComplementRanges<I, T>
+where
+    I: Iterator<Item = Range<T>>,
+    T: Ord + Clone,
+{
+}
+
+/// `CoalesceInclusiveRanges` is an iterator that merges overlapping and adjacent inclusive ranges.
+///
+/// See [`InclusiveRanges::coalesce_inclusive_ranges`] for more information.
+pub struct CoalesceInclusiveRanges<I: Iterator, T> {
+    /// The spans of connected ranges.
+    spans: SpansBy<I, ByInclusiveOverlap<T>>,
+}
+
+impl<I, T> Iterator for CoalesceInclusiveRanges<I, T>
+where
+    I: Iterator<Item = RangeInclusive<T>>,
+    T: Integer,
+{
+    type Item = RangeInclusive<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let union = self.spans.next()?.reduce(|union, range| {
+                RangeInclusive::new(*union.start(), cmp::max(*union.end(), *range.end()))
+            })?;
+
+            if !union.is_empty() {
+                return Some(union);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (_, upper) = self.spans.span_count_hint();
+        (0, upper)
+    }
+}
+
+impl<I, T> FusedIterator for CoalesceInclusiveRanges<I, T>
+where
+    I: Iterator<Item = RangeInclusive<T>>,
+    T: Integer,
+{
+}
+
+/// Collects the coalesced ranges into a [`rangemap::RangeInclusiveSet`].
+///
+/// Requires the `rangemap` feature.
+#[cfg(feature = "rangemap")]
+impl<I, T> From<CoalesceInclusiveRanges<I, T>> for rangemap::RangeInclusiveSet<T>
+where
+    I: Iterator<Item = RangeInclusive<T>>,
+    T: Integer + rangemap::StepLite,
+{
+    fn from(ranges: CoalesceInclusiveRanges<I, T>) -> Self {
+        ranges.collect()
+    }
+}
+
+/// Collects the coalesced ranges into a [`range_set_blaze::RangeSetBlaze`].
+///
+/// Requires the `range-set-blaze` feature.
+#[cfg(feature = "range-set-blaze")]
+impl<I, T> From<CoalesceInclusiveRanges<I, T>> for range_set_blaze::RangeSetBlaze<T>
+where
+    I: Iterator<Item = RangeInclusive<T>>,
+    T: Integer + range_set_blaze::Integer,
+{
+    fn from(ranges: CoalesceInclusiveRanges<I, T>) -> Self {
+        ranges.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InclusiveRanges, Ranges};
+    use core::ops::Range;
+
+    #[test]
+    fn test_coalesce_contained_ranges() {
+        let ranges = [0..10, 2..3, 4..5, 10..11, 12..13];
+        let coalesced = ranges.iter().cloned().coalesce_ranges().collect::<Vec<_>>();
+        assert_eq!(coalesced, vec![0..11, 12..13]);
+    }
+
+    #[test]
+    fn test_coalesce_empty_ranges() {
+        let ranges = [4..4, Range { start: 5, end: 3 }, 7..7, 7..8];
+        let coalesced = ranges.iter().cloned().coalesce_ranges().collect::<Vec<_>>();
+        assert_eq!(coalesced, vec![7..8]);
+    }
+
+    #[test]
+    fn test_coalesce_inclusive_at_max() {
+        let ranges = [0..=3, 250..=254, 255..=255, 255..=255];
+        let coalesced = ranges
+            .iter()
+            .cloned()
+            .coalesce_inclusive_ranges()
+            .collect::<Vec<_>>();
+        assert_eq!(coalesced, vec![0u8..=3, 250..=255]);
+    }
+
+    /// Returns the ranges covered by `ranges` as a bitmap of `0..64`.
+    fn bits(ranges: &[Range<u32>]) -> u64 {
+        ranges
+            .iter()
+            .flat_map(|range| range.clone())
+            .fold(0, |bits, x| bits | 1 << x)
+    }
+
+    /// Returns sample sorted ranges within `0..64`.
+    fn samples() -> Vec<Vec<Range<u32>>> {
+        vec![
+            vec![],
+            vec![0..64],
+            vec![0..3, 2..5, 9..9, 10..20, 20..21, 40..63],
+            vec![1..2, 4..12, 15..16, 18..41, 60..64],
+            vec![5..10, 10..15, 30..31],
+        ]
+    }
+
+    #[test]
+    fn test_set_operations() {
+        for a in samples() {
+            for b in samples() {
+                let union = a
+                    .iter()
+                    .cloned()
+                    .union_ranges(b.clone())
+                    .collect::<Vec<_>>();
+                let intersection = a
+                    .iter()
+                    .cloned()
+                    .intersection_ranges(b.clone())
+                    .collect::<Vec<_>>();
+                let difference = a
+                    .iter()
+                    .cloned()
+                    .difference_ranges(b.clone())
+                    .collect::<Vec<_>>();
+
+                assert_eq!(bits(&union), bits(&a) | bits(&b));
+                assert_eq!(bits(&intersection), bits(&a) & bits(&b));
+                assert_eq!(bits(&difference), bits(&a) & !bits(&b));
+
+                let complement = a
+                    .iter()
+                    .cloned()
+                    .complement_ranges(8..56)
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    bits(&complement),
+                    !bits(&a) & bits(core::slice::from_ref(&(8..56)))
+                );
+
+                for ranges in &[union, intersection, difference] {
+                    assert_eq!(
+                        &ranges.iter().cloned().coalesce_ranges().collect::<Vec<_>>(),
+                        ranges
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_find_containing() {
+        let ranges = [0..2, 5..8, 8..9];
+        let found = (0..10)
+            .map(|x| super::find_containing(&ranges, &x))
+            .collect::<Vec<_>>();
+        let none = None;
+        assert_eq!(
+            found,
+            vec![
+                Some(0),
+                Some(0),
+                none,
+                none,
+                none,
+                Some(1),
+                Some(1),
+                Some(1),
+                Some(2),
+                none
+            ]
+        );
+        assert_eq!(
+            super::find_containing(&[0..=3, 7..=u32::MAX], &u32::MAX),
+            Some(1)
+        );
+        assert_eq!(super::find_containing::<Range<i32>, _>(&[], &0), None);
+    }
+
+    #[test]
+    fn test_coverage() {
+        let empty = core::iter::empty::<Range<i32>>().coverage();
+        assert_eq!(
+            (
+                empty.covered(),
+                empty.gaps(),
+                empty.extent(),
+                empty.fraction()
+            ),
+            (0, 0, None, None)
+        );
+
+        let coverage = [0..0, 2..6, 3..4, 8..9].iter().cloned().coverage();
+        assert_eq!((coverage.covered(), coverage.gaps()), (5, 2));
+        assert_eq!(coverage.extent(), Some(&(2..9)));
+        assert_eq!(coverage.fraction(), Some(5.0 / 7.0));
+
+        let coverage = [0usize..3, 4..8].iter().cloned().coverage();
+        assert_eq!(coverage.fraction(), Some(7.0 / 8.0));
+        let coverage = [0u64..1, 3..4].iter().cloned().coverage();
+        assert_eq!(coverage.fraction(), Some(0.5));
+    }
+
+    #[test]
+    fn test_coverage_of_instants() {
+        use std::time::{Duration, Instant};
+
+        let t = Instant::now();
+        let sessions = [
+            t..t + Duration::from_secs(3),
+            t + Duration::from_secs(5)..t + Duration::from_secs(6),
+        ];
+        let coverage = sessions.iter().cloned().coverage();
+        assert_eq!(coverage.covered(), Duration::from_secs(4));
+        assert_eq!(coverage.gaps(), Duration::from_secs(2));
+        assert_eq!(coverage.fraction(), Some(4.0 / 6.0));
+    }
+
+    #[test]
+    fn test_sweep() {
+        use super::SweepEvent::{End, Start};
+
+        let events = [
+            (1, End),
+            (2, Start),
+            (2, Start),
+            (4, End),
+            (7, End),
+            (9, Start),
+        ];
+        let covered = super::sweep(events.iter().copied()).collect::<Vec<_>>();
+        assert_eq!(covered, vec![2..7]);
+
+        let intervals = [0..3, 1..2, 3..5, 8..10, 12..13];
+        let mut events = intervals
+            .iter()
+            .flat_map(|range| vec![(range.start, Start), (range.end, End)])
+            .collect::<Vec<_>>();
+        events.sort_by_key(|&(position, _)| position);
+        let covered = super::sweep(events).collect::<Vec<_>>();
+        assert_eq!(
+            covered,
+            intervals
+                .iter()
+                .cloned()
+                .coalesce_ranges()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_mask_round_trip() {
+        let masks: &[&[bool]] = &[
+            &[],
+            &[true],
+            &[false, false],
+            &[true, true, false, true, false, false, true],
+        ];
+        for &mask in masks {
+            let runs = super::mask_to_spans(mask.iter().copied()).collect::<Vec<_>>();
+            let round_trip = super::spans_to_mask(runs.clone(), mask.len()).collect::<Vec<_>>();
+            assert_eq!(round_trip, mask);
+            assert_eq!(
+                runs.iter().cloned().coalesce_ranges().collect::<Vec<_>>(),
+                runs
+            );
+        }
+    }
+
+    #[test]
+    fn test_spans_to_mask_overlapping() {
+        let mask = super::spans_to_mask(vec![0..2, 1..3, 5..9], 6).collect::<Vec<_>>();
+        assert_eq!(mask, vec![true, true, true, false, false, true]);
+    }
+
+    #[cfg(all(feature = "rangemap", feature = "range-set-blaze"))]
+    #[test]
+    fn test_into_range_sets() {
+        let set = rangemap::RangeSet::from([0..2, 1..3, 4..6].iter().cloned().coalesce_ranges());
+        assert_eq!(set.iter().cloned().collect::<Vec<_>>(), vec![0..3, 4..6]);
+
+        let ranges = || {
+            [0..=2u32, 3..=3, 4..=6, 9..=9]
+                .iter()
+                .cloned()
+                .coalesce_inclusive_ranges()
+        };
+        let set = rangemap::RangeInclusiveSet::from(ranges());
+        assert_eq!(set.iter().cloned().collect::<Vec<_>>(), vec![0..=6, 9..=9]);
+        let set = range_set_blaze::RangeSetBlaze::from(ranges());
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![0..=6, 9..=9]);
+        assert_eq!(
+            set.ranges().coalesce_inclusive_ranges().collect::<Vec<_>>(),
+            ranges().collect::<Vec<_>>()
+        );
+    }
+}
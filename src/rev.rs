@@ -0,0 +1,90 @@
+use crate::Connector;
+
+/// `ByRevKey` is a [`Connector`] that compares the items of a reversed iterator by their keys, in the order of the original iterator.
+///
+/// See [`Spans::rspans_by_key`][crate::Spans::rspans_by_key] for more information.
+#[derive(Clone)]
+pub struct ByRevKey<K, C, F> {
+    /// A function transforming an iterator item to a comparison key.
+    key: K,
+    /// Whether two iterator items belong to the same span as determined by their respective keys, in the order of the original iterator.
+    are_connected: F,
+    /// The key of the item most recently passed as `next` to `Connector::are_connected`.
+    ///
+    /// `None` before the first comparison.
+    prev_key: Option<C>,
+}
+
+impl<K, C, F> ByRevKey<K, C, F> {
+    /// Creates a new `ByRevKey` connector.
+    pub(crate) fn new(key: K, are_connected: F) -> Self {
+        ByRevKey {
+            key,
+            are_connected,
+            prev_key: None,
+        }
+    }
+}
+
+impl<T, K, C, F> Connector<T> for ByRevKey<K, C, F>
+where
+    K: FnMut(&T) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        // Items are compared pairwise in iteration order, so the stored key is the key of `prev`.
+        let prev_key = match self.prev_key.take() {
+            Some(prev_key) => prev_key,
+            None => (self.key)(prev),
+        };
+        let next_key = (self.key)(next);
+        // `next` precedes `prev` in the original iterator.
+        let is_connected = (self.are_connected)(next_key.clone(), prev_key);
+        self.prev_key = Some(next_key);
+        is_connected
+    }
+
+    fn reset(&mut self) {
+        self.prev_key = None;
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_rspans_match_spans() {
+        let vec = [1, 2, 3, 5, 7, 8];
+        let mut spans = vec
+            .iter()
+            .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+            .collect_spans();
+        let mut rspans = vec
+            .iter()
+            .rspans_by_key(|&&x| x, |a, b| a + 1 == b)
+            .collect_spans();
+
+        spans.reverse();
+        for span in &mut rspans {
+            span.reverse();
+        }
+        assert_eq!(rspans, spans);
+    }
+
+    #[test]
+    fn test_rspans_are_lazy() {
+        let mut calls = 0;
+        let vec = [1, 2, 5, 6];
+        let mut rspans = vec.iter().rspans_by_key(
+            |&&x| {
+                calls += 1;
+                x
+            },
+            |a, b| a + 1 == b,
+        );
+        assert_eq!(rspans.next().unwrap().collect::<Vec<_>>(), vec![&6, &5]);
+        assert_eq!(calls, 3);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::SpansBy;
+use core::iter::FusedIterator;
+
+/// Returns whether `a` and `b` are equal.
+pub(crate) fn are_equal_items<T: PartialEq>(a: &T, b: &T) -> bool {
+    a == b
+}
+
+/// A connector that connects equal items.
+type ByEquality<T> = fn(&T, &T) -> bool;
+
+/// `RunLengthEncode` is an iterator over the runs of equal items of an iterator and their lengths.
+///
+/// See [`Spans::run_length_encode`][crate::Spans::run_length_encode] for more information.
+pub struct RunLengthEncode<I: Iterator> {
+    /// The runs of equal items.
+    spans: SpansBy<I, ByEquality<I::Item>>,
+}
+
+impl<I> RunLengthEncode<I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    /// Creates a new `RunLengthEncode` iterator.
+    pub(crate) fn new(iter: I) -> Self {
+        RunLengthEncode {
+            spans: SpansBy::new(iter, are_equal_items),
+        }
+    }
+}
+
+impl<I> Iterator for RunLengthEncode<I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+    type Item = (I::Item, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut span = self.spans.next()?;
+        let first = span.next()?;
+        Some((first, 1 + span.count()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.span_count_hint()
+    }
+}
+
+impl<I> FusedIterator for RunLengthEncode<I>
+where
+    I: Iterator,
+    I::Item: PartialEq,
+{
+}
+
+/// `RunLengthDecode` is an iterator that repeats each item of an iterator of pairs of items and run lengths.
+///
+/// See [`Spans::run_length_decode`][crate::Spans::run_length_decode] for more information.
+#[derive(Clone)]
+pub struct RunLengthDecode<I, T> {
+    /// The pairs of items and run lengths.
+    iter: I,
+    /// The item of the current run and the number of times that it has yet to be yielded.
+    run: Option<(T, usize)>,
+}
+
+impl<I, T> RunLengthDecode<I, T> {
+    /// Creates a new `RunLengthDecode` iterator.
+    pub(crate) fn new(iter: I) -> Self {
+        RunLengthDecode { iter, run: None }
+    }
+}
+
+impl<I, T> Iterator for RunLengthDecode<I, T>
+where
+    I: Iterator<Item = (T, usize)>,
+    T: Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match &mut self.run {
+                Some((item, count)) if *count > 1 => {
+                    *count -= 1;
+                    return Some(item.clone());
+                }
+                Some((_, 1)) => {
+                    // The last item of a run is moved out instead of being cloned.
+                    return self.run.take().map(|(item, _)| item);
+                }
+                _ => self.run = Some(self.iter.next()?),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.run.as_ref().map_or(0, |&(_, count)| count);
+        match self.iter.size_hint() {
+            (_, Some(0)) => (remaining, Some(remaining)),
+            _ => (remaining, None),
+        }
+    }
+}
+
+impl<I, T> FusedIterator for RunLengthDecode<I, T>
+where
+    I: FusedIterator<Item = (T, usize)>,
+    T: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_empty() {
+        assert!(core::iter::empty::<()>()
+            .run_length_encode()
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn test_runs() {
+        let mut runs = "aaabccdd".chars().run_length_encode();
+        assert_eq!(runs.next(), Some(('a', 3)));
+        assert_eq!(runs.next(), Some(('b', 1)));
+        assert_eq!(runs.next(), Some(('c', 2)));
+        assert_eq!(runs.next(), Some(('d', 2)));
+        assert_eq!(runs.next(), None);
+    }
+
+    #[test]
+    fn test_decode() {
+        let runs = vec![('a', 2), ('b', 0), ('c', 1)];
+        let decoded = runs.into_iter().run_length_decode().collect::<String>();
+        assert_eq!(decoded, "aac");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let inputs: &[&[i32]] = &[&[], &[7], &[1, 1, 2, 1, 1, 1], &[3, 3, 3, 3]];
+        for &items in inputs {
+            let encoded = items
+                .iter()
+                .copied()
+                .run_length_encode()
+                .collect::<Vec<_>>();
+            assert!(encoded.iter().all(|&(_, count)| count > 0));
+            assert!(encoded.windows(2).all(|pair| pair[0].0 != pair[1].0));
+            let decoded = encoded.into_iter().run_length_decode().collect::<Vec<_>>();
+            assert_eq!(decoded, items);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Conversions between spans of integers and [`roaring`] bitmaps.
+//!
+//! Use [`to_bitmap`] to store the key ranges of spans of `u32`s in a [`RoaringBitmap`], and [`bitmap_runs`] to get the runs of a bitmap back as ranges:
+//!
+//! ```
+//! use spans::Spans;
+//!
+//! let ids: Vec<u32> = vec![1, 2, 3, 7, 8, 20];
+//! let bitmap = spans::roaring::to_bitmap(ids.into_iter().consecutive_spans().span_key_ranges());
+//!
+//! assert_eq!(bitmap.len(), 6);
+//! assert_eq!(spans::roaring::bitmap_runs(&bitmap).collect::<Vec<_>>(), vec![1..=3, 7..=8, 20..=20]);
+//! ```
+//!
+//! Ranges are inclusive, such that a run can end at [`u32::MAX`].
+
+use core::iter::FusedIterator;
+use core::ops::RangeInclusive;
+use roaring::bitmap::Iter;
+use roaring::RoaringBitmap;
+
+/// Returns a bitmap of the integers of all `ranges`.
+///
+/// Each range is inserted as a whole, and the bitmap is then [optimized][RoaringBitmap::optimize], such that long ranges are stored in run containers.
+/// The ranges may overlap and need not be sorted.
+///
+/// # Example
+///
+/// ```
+/// let bitmap = spans::roaring::to_bitmap(vec![0..=99_999, 200_000..=200_001]);
+///
+/// assert_eq!(bitmap.len(), 100_002);
+/// assert!(bitmap.contains(200_001));
+/// ```
+pub fn to_bitmap<R>(ranges: R) -> RoaringBitmap
+where
+    R: IntoIterator<Item = RangeInclusive<u32>>,
+{
+    let mut bitmap = RoaringBitmap::new();
+
+    for range in ranges {
+        bitmap.insert_range(range);
+    }
+
+    bitmap.optimize();
+    bitmap
+}
+
+/// Returns an iterator over the runs of consecutive integers in `bitmap`, in increasing order.
+///
+/// The runs are read a range at a time instead of an integer at a time, which is fast for run containers.
+/// The runs are the key ranges that [`consecutive_spans`][crate::Spans::consecutive_spans] would find for the integers of the bitmap.
+///
+/// # Example
+///
+/// ```
+/// use roaring::RoaringBitmap;
+///
+/// let bitmap: RoaringBitmap = [4, 5, 6, 9].into_iter().collect();
+///
+/// assert_eq!(spans::roaring::bitmap_runs(&bitmap).collect::<Vec<_>>(), vec![4..=6, 9..=9]);
+/// ```
+pub fn bitmap_runs(bitmap: &RoaringBitmap) -> BitmapRuns<'_> {
+    BitmapRuns {
+        iter: bitmap.iter(),
+    }
+}
+
+/// `BitmapRuns` is an iterator over the runs of consecutive integers in a [`RoaringBitmap`].
+///
+/// See [`bitmap_runs`] for more information.
+#[derive(Clone)]
+pub struct BitmapRuns<'a> {
+    /// The integers of the bitmap that have not been yielded.
+    iter: Iter<'a>,
+}
+
+impl Iterator for BitmapRuns<'_> {
+    type Item = RangeInclusive<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next_range()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        (lower.min(1), upper)
+    }
+}
+
+impl DoubleEndedIterator for BitmapRuns<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_range_back()
+    }
+}
+
+impl FusedIterator for BitmapRuns<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{bitmap_runs, to_bitmap};
+    use crate::Spans;
+
+    #[test]
+    fn test_round_trip() {
+        let ids = [
+            0,
+            1,
+            2,
+            65_535,
+            65_536,
+            65_537,
+            100_000,
+            u32::MAX - 1,
+            u32::MAX,
+        ];
+        let ranges = ids
+            .iter()
+            .copied()
+            .consecutive_spans()
+            .span_key_ranges()
+            .collect::<Vec<_>>();
+        let bitmap = to_bitmap(ranges.iter().cloned());
+        assert_eq!(bitmap.iter().collect::<Vec<_>>(), ids);
+        assert_eq!(bitmap_runs(&bitmap).collect::<Vec<_>>(), ranges);
+        assert_eq!(
+            bitmap_runs(&bitmap).rev().collect::<Vec<_>>(),
+            ranges.into_iter().rev().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_overlapping_ranges() {
+        let bitmap = to_bitmap([5..=9, 0..=2, 8..=12, 3..=3]);
+        assert_eq!(
+            bitmap_runs(&bitmap).collect::<Vec<_>>(),
+            vec![0..=3, 5..=12]
+        );
+        assert!(bitmap_runs(&to_bitmap([])).next().is_none());
+    }
+}
@@ -0,0 +1,237 @@
+#[cfg(feature = "alloc")]
+use crate::{Connector, SpansBy};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::cmp::Ordering;
+#[cfg(feature = "alloc")]
+use core::iter::FusedIterator;
+
+/// Returns whether `a` and `b` are in non-decreasing order.
+pub(crate) fn are_sorted<T: PartialOrd>(a: &T, b: &T) -> bool {
+    a <= b
+}
+
+/// Returns whether the keys `a` and `b` are in non-decreasing order.
+pub(crate) fn are_sorted_keys<C: PartialOrd>(a: C, b: C) -> bool {
+    a <= b
+}
+
+#[cfg(feature = "alloc")]
+/// The direction of a natural run.
+#[derive(Clone, Copy)]
+enum Direction {
+    /// The items of the run are in non-decreasing order.
+    Ascending,
+    /// The items of the run are in strictly decreasing order.
+    Descending,
+}
+
+#[cfg(feature = "alloc")]
+/// `ByNaturalOrder` is a [`Connector`] that connects items forming a non-decreasing or a strictly decreasing run.
+///
+/// The direction of a run is determined by its first two items.
+/// See [`Spans::natural_runs`][crate::Spans::natural_runs] for more information.
+#[derive(Clone)]
+pub struct ByNaturalOrder {
+    /// The direction of the current run, or `None` if the current run has only one item so far.
+    direction: Option<Direction>,
+}
+
+#[cfg(feature = "alloc")]
+impl ByNaturalOrder {
+    /// Creates a new `ByNaturalOrder` connector.
+    pub(crate) fn new() -> Self {
+        ByNaturalOrder { direction: None }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: PartialOrd> Connector<T> for ByNaturalOrder {
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        let ordering = next.partial_cmp(prev);
+
+        let is_connected = match (self.direction, ordering) {
+            (_, None) => false,
+            (None, Some(Ordering::Less)) => {
+                self.direction = Some(Direction::Descending);
+                true
+            }
+            (None, Some(_)) => {
+                self.direction = Some(Direction::Ascending);
+                true
+            }
+            (Some(Direction::Ascending), Some(ordering)) => ordering != Ordering::Less,
+            (Some(Direction::Descending), Some(ordering)) => ordering == Ordering::Less,
+        };
+
+        if !is_connected {
+            self.direction = None;
+        }
+
+        is_connected
+    }
+
+    fn reset(&mut self) {
+        self.direction = None;
+    }
+}
+
+/// A `Run` is a natural run of items yielded by [`NaturalRuns`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Run<T> {
+    /// A run of items in non-decreasing order.
+    Ascending(Vec<T>),
+    /// A run of items in strictly decreasing order.
+    ///
+    /// A descending run always has at least two items.
+    Descending(Vec<T>),
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Run<T> {
+    /// Returns whether the run is descending.
+    pub fn is_descending(&self) -> bool {
+        matches!(self, Run::Descending(_))
+    }
+
+    /// Returns the items of the run in iteration order.
+    pub fn into_vec(self) -> Vec<T> {
+        match self {
+            Run::Ascending(items) | Run::Descending(items) => items,
+        }
+    }
+
+    /// Returns the items of the run in non-decreasing order.
+    ///
+    /// Descending runs are reversed.
+    /// Since descending runs are strictly decreasing, reversing them does not reorder equal items.
+    pub fn into_sorted(self) -> Vec<T> {
+        match self {
+            Run::Ascending(items) => items,
+            Run::Descending(mut items) => {
+                items.reverse();
+                items
+            }
+        }
+    }
+}
+
+/// `NaturalRuns` is an iterator over the natural runs of an iterator.
+///
+/// See [`Spans::natural_runs`][crate::Spans::natural_runs] for more information.
+#[cfg(feature = "alloc")]
+pub struct NaturalRuns<I: Iterator> {
+    /// The wrapped spans.
+    spans: SpansBy<I, ByNaturalOrder>,
+}
+
+#[cfg(feature = "alloc")]
+impl<I: Iterator> NaturalRuns<I> {
+    /// Creates a new `NaturalRuns` iterator.
+    pub(crate) fn new(spans: SpansBy<I, ByNaturalOrder>) -> Self {
+        NaturalRuns { spans }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I> Iterator for NaturalRuns<I>
+where
+    I: Iterator,
+    I::Item: PartialOrd,
+{
+    type Item = Run<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let items: Vec<_> = self.spans.next()?.collect();
+
+        if items.len() > 1 && items[1] < items[0] {
+            Some(Run::Descending(items))
+        } else {
+            Some(Run::Ascending(items))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.span_count_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I> FusedIterator for NaturalRuns<I>
+where
+    I: Iterator,
+    I::Item: PartialOrd,
+{
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::Run;
+    use crate::Spans;
+
+    #[test]
+    fn test_sorted_runs() {
+        let runs = [1, 3, 3, 2, 5, 8, 0, 0]
+            .iter()
+            .sorted_runs()
+            .collect_spans();
+        assert_eq!(runs, vec![vec![&1, &3, &3], vec![&2, &5, &8], vec![&0, &0]]);
+    }
+
+    #[test]
+    fn test_sorted_runs_descending() {
+        let runs = [3, 2, 1].iter().sorted_runs().collect_spans();
+        assert_eq!(runs, vec![vec![&3], vec![&2], vec![&1]]);
+    }
+
+    #[test]
+    fn test_sorted_runs_by_key() {
+        let runs = ["a", "bb", "cc", "d", "eee"]
+            .iter()
+            .sorted_runs_by_key(|x| x.len())
+            .collect_spans();
+        assert_eq!(runs, vec![vec![&"a", &"bb", &"cc"], vec![&"d", &"eee"]]);
+    }
+
+    #[test]
+    fn test_natural_runs() {
+        let runs = [5, 3, 1, 2, 2, 4, 3, 3, 0]
+            .iter()
+            .natural_runs()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            runs,
+            vec![
+                Run::Descending(vec![&5, &3, &1]),
+                Run::Ascending(vec![&2, &2, &4]),
+                Run::Ascending(vec![&3, &3]),
+                Run::Ascending(vec![&0]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_natural_runs_sorted() {
+        let runs = [4, 3, 2, 1, 7, 8, 6]
+            .iter()
+            .copied()
+            .natural_runs()
+            .map(Run::into_sorted)
+            .collect::<Vec<_>>();
+        assert_eq!(runs, vec![vec![1, 2, 3, 4], vec![7, 8], vec![6]]);
+    }
+
+    #[test]
+    fn test_natural_runs_incomparable() {
+        let runs = [2.0, 1.0, f64::NAN, 1.0, 2.0]
+            .iter()
+            .copied()
+            .natural_runs()
+            .map(|run| (run.is_descending(), run.into_vec().len()))
+            .collect::<Vec<_>>();
+        assert_eq!(runs, vec![(true, 2), (false, 1), (false, 2)]);
+    }
+}
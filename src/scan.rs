@@ -0,0 +1,264 @@
+use crate::Connector;
+
+/// `SplitDecision` determines whether an item continues the current span, starts a new span, or ends the current span.
+///
+/// See [`Spans::spans_by_decision`][crate::Spans::spans_by_decision] and [`Spans::spans_scan`][crate::Spans::spans_scan] for more information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SplitDecision {
+    /// The item belongs to the current span.
+    Continue,
+    /// The item starts a new span.
+    SplitBefore,
+    /// The item is the last item of the current span; the next item starts a new span.
+    SplitAfter,
+}
+
+/// Returns whether an item is connected to the previous item, given the decision for the item.
+///
+/// `is_split_after` is whether the previous item ended its span; it is updated for the item.
+fn is_connected(is_split_after: &mut bool, decision: SplitDecision) -> bool {
+    let is_connected = !*is_split_after && decision != SplitDecision::SplitBefore;
+    *is_split_after = decision == SplitDecision::SplitAfter;
+    is_connected
+}
+
+/// `ByDecision` is a [`Connector`] that connects items according to a decision made for each item.
+///
+/// See [`Spans::spans_by_decision`][crate::Spans::spans_by_decision] for more information.
+#[derive(Clone)]
+pub struct ByDecision<G> {
+    /// A function deciding whether an item continues the current span, starts a new span, or ends the current span.
+    decide: G,
+    /// Whether the first item has been passed to `decide`.
+    is_init: bool,
+    /// Whether the decision for the item most recently passed to `decide` was `SplitDecision::SplitAfter`.
+    is_split_after: bool,
+    /// Whether the item most recently passed to `decide` ended the previous span, such that it starts the next span.
+    is_next_decided: bool,
+}
+
+impl<G> ByDecision<G> {
+    /// Creates a new `ByDecision` connector.
+    pub(crate) fn new(decide: G) -> Self {
+        ByDecision {
+            decide,
+            is_init: false,
+            is_split_after: false,
+            is_next_decided: false,
+        }
+    }
+}
+
+impl<T, G> Connector<T> for ByDecision<G>
+where
+    G: FnMut(&T) -> SplitDecision,
+{
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        if !self.is_init {
+            self.is_init = true;
+            // The first item always starts a span; only `SplitDecision::SplitAfter` affects the following item.
+            is_connected(&mut self.is_split_after, (self.decide)(prev));
+        }
+
+        let is_connected = is_connected(&mut self.is_split_after, (self.decide)(next));
+        self.is_next_decided = !is_connected;
+        is_connected
+    }
+
+    fn start_span(&mut self, _first: &T) {
+        self.is_next_decided = false;
+    }
+
+    fn reset(&mut self) {
+        // The item that ended the previous span has been decided already, and its decision still applies to the following item.
+        if !self.is_next_decided {
+            self.is_init = false;
+            self.is_split_after = false;
+        }
+    }
+}
+
+/// `ByScan` is a [`Connector`] that connects items according to a function over an accumulated state.
+///
+/// See [`Spans::spans_scan`][crate::Spans::spans_scan] for more information.
+#[derive(Clone)]
+pub struct ByScan<S, G> {
+    /// The accumulated state.
+    state: S,
+    /// A function updating the state with an item and deciding whether the item starts a new span.
+    scan: G,
+    /// Whether the first item has been passed to `scan`.
+    is_init: bool,
+    /// Whether the decision for the item most recently passed to `scan` was `SplitDecision::SplitAfter`.
+    is_split_after: bool,
+    /// Whether the item most recently passed to `scan` ended the previous span, such that it starts the next span.
+    is_next_scanned: bool,
+}
+
+impl<S, G> ByScan<S, G> {
+    /// Creates a new `ByScan` connector.
+    pub(crate) fn new(state: S, scan: G) -> Self {
+        ByScan {
+            state,
+            scan,
+            is_init: false,
+            is_split_after: false,
+            is_next_scanned: false,
+        }
+    }
+}
+
+impl<S, G> ByScan<S, G> {
+    /// Passes `first` to `scan` if no item has been passed to `scan` yet.
+    fn scan_first<T>(&mut self, first: &T)
+    where
+        G: FnMut(&mut S, &T) -> SplitDecision,
+    {
+        if !self.is_init {
+            self.is_init = true;
+            // The first item always starts a span; only `SplitDecision::SplitAfter` affects the following item.
+            let decision = (self.scan)(&mut self.state, first);
+            is_connected(&mut self.is_split_after, decision);
+        }
+    }
+}
+
+impl<T, S, G> Connector<T> for ByScan<S, G>
+where
+    G: FnMut(&mut S, &T) -> SplitDecision,
+{
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        // `prev` has not been scanned only after a reset.
+        self.scan_first(prev);
+
+        let decision = (self.scan)(&mut self.state, next);
+        let is_connected = is_connected(&mut self.is_split_after, decision);
+        self.is_next_scanned = !is_connected;
+        is_connected
+    }
+
+    fn start_span(&mut self, first: &T) {
+        // The first item of the iterator is scanned when its span starts, such that the state sees it even if it is the only item.
+        self.scan_first(first);
+        self.is_next_scanned = false;
+    }
+
+    fn reset(&mut self) {
+        // The item that ended the previous span has been scanned already and must not be scanned again when its span starts.
+        if !self.is_next_scanned {
+            self.is_init = false;
+            self.is_split_after = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Spans, SplitDecision};
+
+    #[test]
+    fn test_counter() {
+        let spans = "abcdefg"
+            .chars()
+            .spans_scan(0, |count, _| {
+                *count += 1;
+                if *count > 3 {
+                    *count = 1;
+                    SplitDecision::SplitBefore
+                } else {
+                    SplitDecision::Continue
+                }
+            })
+            .map_spans(|span| span.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec!["abc", "def", "g"]);
+    }
+
+    #[test]
+    fn test_split_before_and_after() {
+        let decide = |&c: &char| match c {
+            '<' => SplitDecision::SplitBefore,
+            '>' => SplitDecision::SplitAfter,
+            _ => SplitDecision::Continue,
+        };
+        let spans = "a<b>c<>>d"
+            .chars()
+            .spans_by_decision(decide)
+            .map_spans(|span| span.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec!["a", "<b>", "c", "<>", ">", "d"]);
+
+        let spans = ">a"
+            .chars()
+            .spans_by_decision(decide)
+            .map_spans(|span| span.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![">", "a"]);
+    }
+
+    #[test]
+    fn test_first_item_is_scanned() {
+        let mut scanned = Vec::new();
+        let spans = "ab"
+            .chars()
+            .spans_scan((), |_, &c| {
+                scanned.push(c);
+                SplitDecision::SplitBefore
+            })
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![1, 1]);
+        assert_eq!(scanned, vec!['a', 'b']);
+    }
+
+    #[test]
+    fn test_single_item_is_scanned() {
+        let mut total = 0;
+        let spans = [7]
+            .iter()
+            .spans_scan((), |_, &x| {
+                total += x;
+                SplitDecision::Continue
+            })
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![1]);
+        assert_eq!(total, 7);
+    }
+
+    #[test]
+    fn test_get_mut_does_not_rescan() {
+        let mut scanned = Vec::new();
+        let mut spans = vec![1, 2, 3, 4, 5].into_iter().spans_scan(0, |count, &x| {
+            scanned.push(x);
+            *count += 1;
+            if *count > 2 {
+                *count = 1;
+                SplitDecision::SplitBefore
+            } else {
+                SplitDecision::Continue
+            }
+        });
+        let mut lens = vec![spans.next().unwrap().count()];
+        spans.get_mut();
+        lens.extend(spans.map_spans(|span| span.count()));
+        assert_eq!(lens, vec![2, 2, 1]);
+        assert_eq!(scanned, vec![1, 2, 3, 4, 5]);
+
+        let mut decided = Vec::new();
+        let mut spans = "a>bc".chars().spans_by_decision(|&c| {
+            decided.push(c);
+            if c == '>' {
+                SplitDecision::SplitAfter
+            } else {
+                SplitDecision::Continue
+            }
+        });
+        let mut lens = vec![spans.next().unwrap().count()];
+        spans.get_mut();
+        lens.extend(spans.map_spans(|span| span.count()));
+        assert_eq!(lens, vec![2, 2]);
+        assert_eq!(decided, vec!['a', '>', 'b', 'c']);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::Connector;
+use core::ops::Sub;
+
+/// `BySessionGap` is a [`Connector`] that connects items whose timestamps are at most a maximum gap apart.
+///
+/// See [`Spans::sessions_by_gap`][crate::Spans::sessions_by_gap] for more information.
+#[derive(Clone)]
+pub struct BySessionGap<K, T, D> {
+    /// A function returning the timestamp of an iterator item.
+    timestamp: K,
+    /// The maximum difference between the timestamps of two connected items.
+    max_gap: D,
+    /// The timestamp of the item most recently passed as `next` to `Connector::are_connected`.
+    prev_timestamp: Option<T>,
+}
+
+impl<K, T, D> BySessionGap<K, T, D> {
+    /// Creates a new `BySessionGap` connector.
+    pub(crate) fn new(timestamp: K, max_gap: D) -> Self {
+        BySessionGap {
+            timestamp,
+            max_gap,
+            prev_timestamp: None,
+        }
+    }
+}
+
+impl<I, K, T, D> Connector<I> for BySessionGap<K, T, D>
+where
+    K: FnMut(&I) -> T,
+    T: Sub<Output = D> + PartialOrd + Copy,
+    D: PartialOrd,
+{
+    fn are_connected(&mut self, prev: &I, next: &I) -> bool {
+        let prev_timestamp = match self.prev_timestamp {
+            Some(prev_timestamp) => prev_timestamp,
+            None => (self.timestamp)(prev),
+        };
+        let next_timestamp = (self.timestamp)(next);
+        self.prev_timestamp = Some(next_timestamp);
+
+        // Checking the order first avoids subtracting a later timestamp from an earlier one, which panics for types such as `Duration`.
+        prev_timestamp <= next_timestamp && next_timestamp - prev_timestamp <= self.max_gap
+    }
+
+    fn reset(&mut self) {
+        self.prev_timestamp = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_integer_timestamps() {
+        let sessions = [(1, 'a'), (3, 'b'), (10, 'c'), (12, 'd'), (20, 'e')]
+            .iter()
+            .sessions_by_gap(|&&(t, _)| t, 2)
+            .map_spans(|span| span.map(|&(_, x)| x).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(sessions, vec!["ab", "cd", "e"]);
+    }
+
+    #[test]
+    fn test_out_of_order_timestamps() {
+        let sessions = [Duration::from_secs(5), Duration::from_secs(2)]
+            .iter()
+            .sessions_by_gap(|&&t| t, Duration::from_secs(10))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(sessions, vec![1, 1]);
+    }
+
+    #[test]
+    fn test_instants() {
+        let start = Instant::now();
+        let events = [
+            start,
+            start + Duration::from_millis(10),
+            start + Duration::from_secs(5),
+        ];
+        let sessions = events
+            .iter()
+            .sessions_by_gap(|&&t| t, Duration::from_secs(1))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(sessions, vec![2, 1]);
+    }
+}
@@ -0,0 +1,655 @@
+//! Spans of slices.
+//!
+//! Import the [`SliceSpans`] trait to split slices into subslices:
+//!
+//! ```
+//! use spans::slice::SliceSpans;
+//!
+//! let vec = vec![1, 2, 5, 6, 7, 11];
+//! let spans: Vec<&[i32]> = vec.spans_by_key(|&x| x, |a, b| a + 1 == b).collect();
+//!
+//! assert_eq!(spans, vec![&[1, 2][..], &[5, 6, 7], &[11]]);
+//! ```
+//!
+//! Spans can also be iterated as [`ExactSizeIterator`]s, whose lengths are known up front:
+//!
+//! ```
+//! use spans::slice::SliceSpans;
+//!
+//! let vec = vec![1, 2, 5, 6, 7, 11];
+//! let lengths: Vec<usize> = vec
+//!     .exact_spans_by_key(|&x| x, |a, b| a + 1 == b)
+//!     .map(|span| span.len())
+//!     .collect();
+//!
+//! assert_eq!(lengths, vec![2, 3, 1]);
+//! ```
+
+use crate::{ByKey, Connector};
+#[cfg(feature = "rayon")]
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::{mem, slice};
+#[cfg(feature = "rayon")]
+use rayon::iter::IntoParallelIterator;
+
+/// `SliceSpans` provides span adapters for slices.
+pub trait SliceSpans<T> {
+    /// Splits the slice into contiguous subslices by comparing adjacent items.
+    ///
+    /// This is like [`Spans::spans_by`][crate::Spans::spans_by], except that each span is a subslice.
+    /// Unlike [`slice::chunk_by`], `are_connected` may be a stateful [`FnMut`] closure; it is called with each pair of adjacent items in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::slice::SliceSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec!["apple", "avocado", "banana"];
+    /// let mut spans = vec.spans_by(|a, b| a[..1] == b[..1]);
+    ///
+    /// assert_eq!(spans.next()?, &["apple", "avocado"]);
+    /// assert_eq!(spans.next()?, &["banana"]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by<F>(&self, are_connected: F) -> SliceSpansBy<'_, T, F>
+    where
+        F: FnMut(&T, &T) -> bool;
+
+    /// Splits the slice into contiguous subslices by comparing the keys of adjacent items.
+    ///
+    /// This is like [`Spans::spans_by_key`][crate::Spans::spans_by_key], except that each span is a subslice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::slice::SliceSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let mut spans = vec.spans_by_key(|&x| x, |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(spans.next()?, &[1, 2]);
+    /// assert_eq!(spans.next()?, &[5, 6, 7]);
+    /// assert_eq!(spans.next()?, &[11]);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by_key<K, C, F>(
+        &self,
+        key: K,
+        are_connected: F,
+    ) -> SliceSpansBy<'_, T, ByKey<K, C, F>>
+    where
+        K: FnMut(&T) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool;
+
+    /// Splits the slice into contiguous mutable subslices by comparing adjacent items.
+    ///
+    /// This is like [`SliceSpans::spans_by`], except that each span is a mutable subslice, such that each span can be modified in place.
+    /// The end of each span is found before the span is returned; changes to a span thus do not affect where the following spans end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::slice::SliceSpans;
+    ///
+    /// let mut vec = vec![3, 1, 2, 0, 9, 7, 8];
+    ///
+    /// // Sort each run of non-zero items.
+    /// for span in vec.spans_by_mut(|&a, &b| (a == 0) == (b == 0)) {
+    ///     span.sort();
+    /// }
+    ///
+    /// assert_eq!(vec, vec![1, 2, 3, 0, 7, 8, 9]);
+    /// ```
+    fn spans_by_mut<F>(&mut self, are_connected: F) -> SliceSpansByMut<'_, T, F>
+    where
+        F: FnMut(&T, &T) -> bool;
+
+    /// Splits the slice into contiguous mutable subslices by comparing the keys of adjacent items.
+    ///
+    /// This is like [`SliceSpans::spans_by_key`], except that each span is a mutable subslice.
+    /// See [`SliceSpans::spans_by_mut`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::slice::SliceSpans;
+    ///
+    /// let mut readings = vec![("a", 2.0), ("a", 6.0), ("b", 5.0)];
+    ///
+    /// // Normalize the readings of each sensor to their maximum.
+    /// for span in readings.spans_by_key_mut(|&(sensor, _)| sensor, |a, b| a == b) {
+    ///     let max = span.iter().map(|&(_, x)| x).fold(f64::MIN, f64::max);
+    ///     for (_, x) in span {
+    ///         *x /= max;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(readings, vec![("a", 2.0 / 6.0), ("a", 1.0), ("b", 1.0)]);
+    /// ```
+    fn spans_by_key_mut<K, C, F>(
+        &mut self,
+        key: K,
+        are_connected: F,
+    ) -> SliceSpansByMut<'_, T, ByKey<K, C, F>>
+    where
+        K: FnMut(&T) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool;
+
+    /// Splits the slice into contiguous spans by comparing adjacent items.
+    ///
+    /// This is like [`Spans::spans_by`][crate::Spans::spans_by], except that each span is a [`slice::Iter`], which implements [`ExactSizeIterator`].
+    /// The end of each span is found when the span is returned, such that its length is known before its items are iterated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::slice::SliceSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec!["apple", "avocado", "banana"];
+    /// let mut spans = vec.exact_spans_by(|a, b| a[..1] == b[..1]);
+    ///
+    /// let span = spans.next()?;
+    /// assert_eq!(span.len(), 2);
+    /// assert_eq!(span.collect::<Vec<_>>(), vec![&"apple", &"avocado"]);
+    /// assert_eq!(spans.next()?.len(), 1);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn exact_spans_by<F>(&self, are_connected: F) -> ExactSpans<'_, T, F>
+    where
+        F: FnMut(&T, &T) -> bool;
+
+    /// Splits the slice into contiguous spans by comparing the keys of adjacent items.
+    ///
+    /// This is like [`Spans::spans_by_key`][crate::Spans::spans_by_key], except that each span is a [`slice::Iter`], which implements [`ExactSizeIterator`].
+    /// See [`SliceSpans::exact_spans_by`] for more information.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::slice::SliceSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let mut spans = vec.exact_spans_by_key(|&x| x, |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(spans.next()?.len(), 2);
+    /// assert_eq!(spans.next()?.as_slice(), &[5, 6, 7]);
+    /// assert_eq!(spans.next()?.len(), 1);
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn exact_spans_by_key<K, C, F>(
+        &self,
+        key: K,
+        are_connected: F,
+    ) -> ExactSpans<'_, T, ByKey<K, C, F>>
+    where
+        K: FnMut(&T) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool;
+
+    /// Splits the slice into contiguous subslices by comparing items to the first item of their span, using an exponential search.
+    ///
+    /// `is_in_span` is called with the first item of a span, another item, and the distance between the two items.
+    /// For each span, `is_in_span` must return `true` for a prefix of the remaining items and `false` for all items after it, like the predicate of [`slice::partition_point`].
+    /// The span ends at the first item for which `is_in_span` returns `false`.
+    ///
+    /// The end of a span is searched with a stride that doubles after each item in the span, followed by a binary search between the last two probed items.
+    /// `is_in_span` is thus called _O_(log _n_) times for a span of _n_ items, which is faster than comparing adjacent items for long spans.
+    /// If `is_in_span` is not monotonic, the spans are unspecified, but each span is still non-empty and the spans still cover the slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::slice::SliceSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let vec: Vec<u32> = (0..1000).chain(2000..3000).collect();
+    ///
+    /// // Consecutive integers differ from the first integer of their span by their distance.
+    /// let mut spans = vec.galloping_spans_by(|&first, &x, distance| x - first == distance as u32);
+    ///
+    /// assert_eq!(spans.next()?.len(), 1000);
+    /// assert_eq!(spans.next()?.len(), 1000);
+    /// assert!(spans.next().is_none());
+    ///
+    /// // Equal items of a sorted slice are runs.
+    /// let sorted = vec![1, 1, 1, 2, 3, 3];
+    /// let runs: Vec<&[i32]> = sorted.galloping_spans_by(|first, x, _| first == x).collect();
+    ///
+    /// assert_eq!(runs, vec![&[1, 1, 1][..], &[2], &[3, 3]]);
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn galloping_spans_by<F>(&self, is_in_span: F) -> GallopingSpans<'_, T, F>
+    where
+        F: FnMut(&T, &T, usize) -> bool;
+
+    /// Splits the slice into contiguous subslices by comparing the keys of adjacent items, and returns a parallel iterator over the subslices.
+    ///
+    /// The spans are found sequentially, like with [`SliceSpans::spans_by_key`], before any span is processed.
+    /// The returned [`ParSpans`] then processes the spans on the threads of the [`rayon`] thread pool.
+    /// It is an indexed parallel iterator, such that collecting it keeps the order of the spans.
+    ///
+    /// This is useful when the work done for each span is much heavier than finding the spans.
+    /// Requires the `rayon` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use spans::slice::SliceSpans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let sums: Vec<i32> = vec
+    ///     .par_spans_by_key(|&x| x, |a, b| a + 1 == b)
+    ///     .map(|span| span.iter().sum())
+    ///     .collect();
+    ///
+    /// assert_eq!(sums, vec![3, 18, 11]);
+    /// ```
+    #[cfg(feature = "rayon")]
+    fn par_spans_by_key<K, C, F>(&self, key: K, are_connected: F) -> ParSpans<'_, T>
+    where
+        T: Sync,
+        K: FnMut(&T) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool;
+}
+
+impl<T> SliceSpans<T> for [T] {
+    fn spans_by<F>(&self, are_connected: F) -> SliceSpansBy<'_, T, F>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        SliceSpansBy {
+            slice: self,
+            connector: are_connected,
+        }
+    }
+
+    fn spans_by_key<K, C, F>(&self, key: K, are_connected: F) -> SliceSpansBy<'_, T, ByKey<K, C, F>>
+    where
+        K: FnMut(&T) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+    {
+        SliceSpansBy {
+            slice: self,
+            connector: ByKey::new(key, are_connected),
+        }
+    }
+
+    fn spans_by_mut<F>(&mut self, are_connected: F) -> SliceSpansByMut<'_, T, F>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        SliceSpansByMut {
+            slice: self,
+            connector: are_connected,
+        }
+    }
+
+    fn spans_by_key_mut<K, C, F>(
+        &mut self,
+        key: K,
+        are_connected: F,
+    ) -> SliceSpansByMut<'_, T, ByKey<K, C, F>>
+    where
+        K: FnMut(&T) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+    {
+        SliceSpansByMut {
+            slice: self,
+            connector: ByKey::new(key, are_connected),
+        }
+    }
+
+    fn exact_spans_by<F>(&self, are_connected: F) -> ExactSpans<'_, T, F>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        ExactSpans {
+            spans: self.spans_by(are_connected),
+        }
+    }
+
+    fn exact_spans_by_key<K, C, F>(
+        &self,
+        key: K,
+        are_connected: F,
+    ) -> ExactSpans<'_, T, ByKey<K, C, F>>
+    where
+        K: FnMut(&T) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+    {
+        ExactSpans {
+            spans: self.spans_by_key(key, are_connected),
+        }
+    }
+
+    fn galloping_spans_by<F>(&self, is_in_span: F) -> GallopingSpans<'_, T, F>
+    where
+        F: FnMut(&T, &T, usize) -> bool,
+    {
+        GallopingSpans {
+            slice: self,
+            is_in_span,
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_spans_by_key<K, C, F>(&self, key: K, are_connected: F) -> ParSpans<'_, T>
+    where
+        T: Sync,
+        K: FnMut(&T) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+    {
+        let spans: Vec<&[T]> = self.spans_by_key(key, are_connected).collect();
+        spans.into_par_iter()
+    }
+}
+
+/// Returns the length of the span at the start of `slice`, or `None` if `slice` is empty.
+fn span_len<T, F: Connector<T>>(slice: &[T], connector: &mut F) -> Option<usize> {
+    connector.start_span(slice.first()?);
+
+    let len = 1 + slice
+        .windows(2)
+        .take_while(|pair| connector.are_connected(&pair[0], &pair[1]))
+        .count();
+    Some(len)
+}
+
+/// `ParSpans` is a parallel iterator over the spans of a slice.
+///
+/// See [`SliceSpans::par_spans_by_key`] for more information.
+#[cfg(feature = "rayon")]
+pub type ParSpans<'a, T> = rayon::vec::IntoIter<&'a [T]>;
+
+/// `SliceSpansBy` is an iterator over the spans of a slice as subslices.
+///
+/// See [`SliceSpans::spans_by`] and [`SliceSpans::spans_by_key`] for more information.
+#[derive(Clone)]
+pub struct SliceSpansBy<'a, T, F> {
+    /// The items that are not part of any returned span yet.
+    slice: &'a [T],
+    /// Whether two adjacent items belong to the same span.
+    connector: F,
+}
+
+impl<'a, T, F> Iterator for SliceSpansBy<'a, T, F>
+where
+    F: Connector<T>,
+{
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = span_len(self.slice, &mut self.connector)?;
+        let (span, rest) = self.slice.split_at(len);
+        self.slice = rest;
+        Some(span)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.slice.len().min(1), Some(self.slice.len()))
+    }
+}
+
+impl<T, F> FusedIterator for SliceSpansBy<'_, T, F> where F: Connector<T> {}
+
+/// `SliceSpansByMut` is an iterator over the spans of a slice as mutable subslices.
+///
+/// See [`SliceSpans::spans_by_mut`] and [`SliceSpans::spans_by_key_mut`] for more information.
+pub struct SliceSpansByMut<'a, T, F> {
+    /// The items that are not part of any returned span yet.
+    slice: &'a mut [T],
+    /// Whether two adjacent items belong to the same span.
+    connector: F,
+}
+
+impl<'a, T, F> Iterator for SliceSpansByMut<'a, T, F>
+where
+    F: Connector<T>,
+{
+    type Item = &'a mut [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = span_len(self.slice, &mut self.connector)?;
+        let (span, rest) = mem::take(&mut self.slice).split_at_mut(len);
+        self.slice = rest;
+        Some(span)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.slice.len().min(1), Some(self.slice.len()))
+    }
+}
+
+impl<T, F> FusedIterator for SliceSpansByMut<'_, T, F> where F: Connector<T> {}
+
+/// `ExactSpans` is an iterator over the spans of a slice, where each span has a known length.
+///
+/// See [`SliceSpans::exact_spans_by`] and [`SliceSpans::exact_spans_by_key`] for more information.
+#[derive(Clone)]
+pub struct ExactSpans<'a, T, F> {
+    /// The spans as subslices.
+    spans: SliceSpansBy<'a, T, F>,
+}
+
+impl<'a, T, F> Iterator for ExactSpans<'a, T, F>
+where
+    F: Connector<T>,
+{
+    type Item = slice::Iter<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.spans.next().map(<[T]>::iter)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.size_hint()
+    }
+}
+
+impl<T, F> FusedIterator for ExactSpans<'_, T, F> where F: Connector<T> {}
+
+/// `GallopingSpans` is an iterator over the spans of a slice as subslices, found by an exponential search.
+///
+/// See [`SliceSpans::galloping_spans_by`] for more information.
+#[derive(Clone)]
+pub struct GallopingSpans<'a, T, F> {
+    /// The items that are not part of any returned span yet.
+    slice: &'a [T],
+    /// Whether an item belongs to the span of the given first item at the given distance.
+    is_in_span: F,
+}
+
+impl<'a, T, F> Iterator for GallopingSpans<'a, T, F>
+where
+    F: FnMut(&T, &T, usize) -> bool,
+{
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let slice = self.slice;
+        let first = slice.first()?;
+        let mut is_in_span = |index: usize| (self.is_in_span)(first, &slice[index], index);
+
+        // The items before `start` are in the span, and the items from `end` on are not.
+        let mut start = 1;
+        let mut end = slice.len();
+        let mut stride = 1;
+
+        while start < end {
+            let probe = start - 1 + stride;
+            if probe >= end {
+                break;
+            }
+            if is_in_span(probe) {
+                start = probe + 1;
+                stride *= 2;
+            } else {
+                end = probe;
+                break;
+            }
+        }
+
+        while start < end {
+            let mid = start + (end - start) / 2;
+            if is_in_span(mid) {
+                start = mid + 1;
+            } else {
+                end = mid;
+            }
+        }
+
+        let (span, rest) = slice.split_at(start);
+        self.slice = rest;
+        Some(span)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.slice.len().min(1), Some(self.slice.len()))
+    }
+}
+
+impl<T, F> FusedIterator for GallopingSpans<'_, T, F> where F: FnMut(&T, &T, usize) -> bool {}
+
+#[cfg(test)]
+mod tests {
+    use super::SliceSpans;
+
+    #[test]
+    fn test_empty() {
+        let vec: Vec<i32> = Vec::new();
+        assert!(vec.exact_spans_by(|a, b| a == b).next().is_none());
+    }
+
+    #[test]
+    fn test_subslices() {
+        let vec = [1, 1, 2, 3, 3, 3];
+        let spans = vec.spans_by(|a, b| a == b).collect::<Vec<_>>();
+        assert_eq!(spans, vec![&[1, 1][..], &[2], &[3, 3, 3]]);
+    }
+
+    #[test]
+    fn test_stateful_connector() {
+        let mut pairs = 0;
+        let spans = [1, 2, 3, 4]
+            .spans_by(|_, _| {
+                pairs += 1;
+                pairs % 2 == 1
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![&[1, 2][..], &[3, 4]]);
+    }
+
+    #[test]
+    fn test_mutable_subslices() {
+        let mut vec = [1, 1, 2, 3, 3];
+        for span in vec.spans_by_mut(|a, b| a == b) {
+            let len = span.len();
+            span.fill(len);
+        }
+        assert_eq!(vec, [2, 2, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_lengths() {
+        let lengths = [1, 1, 2, 3, 3, 3]
+            .exact_spans_by(|a, b| a == b)
+            .map(|span| span.len())
+            .collect::<Vec<_>>();
+        assert_eq!(lengths, vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn test_keys_are_computed_once() {
+        let mut calls = 0;
+        let lengths = [1, 2, 5]
+            .exact_spans_by_key(
+                |&x| {
+                    calls += 1;
+                    x
+                },
+                |a, b| a + 1 == b,
+            )
+            .map(|span| span.len())
+            .collect::<Vec<_>>();
+        assert_eq!(lengths, vec![2, 1]);
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn test_galloping() {
+        let vec = (0..100)
+            .flat_map(|n| core::iter::repeat_n(n, n * 7 % 23 + 1))
+            .collect::<Vec<_>>();
+        let mut calls = 0;
+        let spans = vec
+            .galloping_spans_by(|first, x, _| {
+                calls += 1;
+                first == x
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec.spans_by(|a, b| a == b).collect::<Vec<_>>());
+        assert!(calls < vec.len());
+
+        let long = vec![0; 4096];
+        let mut calls = 0;
+        let lengths = long
+            .galloping_spans_by(|_, _, _| {
+                calls += 1;
+                true
+            })
+            .map(|span| span.len())
+            .collect::<Vec<_>>();
+        assert_eq!(lengths, vec![4096]);
+        assert!(calls <= 24);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_spans_keep_order() {
+        use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+
+        let vec = (0..1000).map(|x| x / 7).collect::<Vec<_>>();
+        let spans = vec.par_spans_by_key(|&x| x, |a, b| a == b);
+        assert_eq!(spans.len(), 143);
+        assert_eq!(
+            spans.collect::<Vec<_>>(),
+            vec.spans_by(|a, b| a == b).collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_spans_empty() {
+        use rayon::iter::ParallelIterator;
+
+        let vec: Vec<i32> = Vec::new();
+        assert_eq!(vec.par_spans_by_key(|&x| x, |a, b| a == b).count(), 0);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{Connector, Span, SpansBy};
+
+/// `BySeparator` is a [`Connector`] that connects an item to the next item unless the next item is a separator.
+///
+/// See [`Spans::split_by`][crate::Spans::split_by] for more information.
+#[derive(Clone)]
+pub struct BySeparator<P> {
+    /// Returns whether an item is a separator.
+    is_separator: P,
+}
+
+impl<T, P> Connector<T> for BySeparator<P>
+where
+    P: FnMut(&T) -> bool,
+{
+    fn are_connected(&mut self, _prev: &T, next: &T) -> bool {
+        // Spans never contain separators, so only `next` needs to be checked.
+        !(self.is_separator)(next)
+    }
+}
+
+/// `SplitBy` wraps an iterator and provides progressive access to the spans between separators.
+///
+/// See [`Spans::split_by`][crate::Spans::split_by] for more information.
+pub struct SplitBy<I: Iterator, P> {
+    /// The spans between separators and of separators.
+    spans: SpansBy<I, BySeparator<P>>,
+}
+
+impl<I: Iterator, P> SplitBy<I, P> {
+    /// Creates a new `SplitBy` splitting `iter` at the items for which `is_separator` returns `true`.
+    pub(crate) fn new(iter: I, is_separator: P) -> Self {
+        SplitBy {
+            spans: SpansBy::new(iter, BySeparator { is_separator }),
+        }
+    }
+}
+
+impl<I, P> SplitBy<I, P>
+where
+    I: Iterator,
+    P: FnMut(&I::Item) -> bool,
+{
+    /// Returns the next span or `None` if the iterator terminated.
+    ///
+    /// Separators preceding the span are consumed, as are the remaining items of the previous span if it was dropped before it was exhausted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let mut spans = "a,b".chars().split_by(|&c| c == ',');
+    ///
+    /// while let Some(span) = spans.next() {
+    ///     assert_eq!(span.count(), 1);
+    /// }
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Span<'_, I, BySeparator<P>>> {
+        let spans = &mut self.spans;
+        spans.skip_current_span();
+
+        while !spans.is_at_end() {
+            let item = spans.iter.peek()?;
+
+            if !(spans.connector.is_separator)(item) {
+                return spans.next();
+            }
+
+            spans.iter.next();
+            spans.position += 1;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    fn split(s: &str) -> Vec<String> {
+        let mut spans = s.chars().split_by(|&c| c == ',');
+        let mut result = Vec::new();
+
+        while let Some(span) = spans.next() {
+            result.push(span.collect());
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(split("ab,c,def"), vec!["ab", "c", "def"]);
+    }
+
+    #[test]
+    fn test_dropped_spans_are_skipped() {
+        let mut spans = "ab,cd".chars().split_by(|&c| c == ',');
+        assert_eq!(spans.next().unwrap().next(), Some('a'));
+        assert_eq!(spans.next().unwrap().collect::<String>(), "cd");
+        assert!(spans.next().is_none());
+    }
+
+    #[test]
+    fn test_empty_spans_are_skipped() {
+        assert_eq!(split(",,ab,,c,"), vec!["ab", "c"]);
+        assert!(split(",,,").is_empty());
+        assert!(split("").is_empty());
+    }
+}
@@ -0,0 +1,480 @@
+//! Spans of asynchronous streams.
+//!
+//! Import the [`StreamSpans`] trait to group the items of a [`Stream`] into spans:
+//!
+//! ```
+//! use futures::executor::block_on;
+//! use futures::stream::{self, StreamExt};
+//! use spans::stream::StreamSpans;
+//!
+//! let spans: Vec<Vec<i32>> = block_on(
+//!     stream::iter(vec![1, 2, 5, 6, 7, 11])
+//!         .spans_by_key(|&x| x, |a, b| a + 1 == b)
+//!         .collect(),
+//! );
+//!
+//! assert_eq!(spans, vec![vec![1, 2], vec![5, 6, 7], vec![11]]);
+//! ```
+//!
+//! Items are compared like with [`Spans`][crate::Spans], using the same [`Connector`] logic.
+//! Since the items of a stream arrive over time, each span is collected into a [`Vec`], which is yielded as soon as the first item of the next span arrives or the stream ends.
+//! With the `tokio` feature, [`StreamSpansBy::with_idle_timeout`] also ends a span when no item arrives for some time.
+
+use crate::{ByKey, ByKeyChange, Connector};
+use alloc::vec::Vec;
+use core::mem;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use futures_core::stream::{FusedStream, Stream};
+#[cfg(feature = "tokio")]
+use {alloc::boxed::Box, core::future::Future, core::time::Duration, tokio::time::Sleep};
+
+/// `StreamSpans` provides span adapters for streams.
+///
+/// The adapters require the stream to be [`Unpin`]; pin other streams with [`Box::pin`][alloc::boxed::Box::pin] first.
+pub trait StreamSpans: Stream + Sized {
+    /// Groups the items of the stream into spans by comparing adjacent items.
+    ///
+    /// This is like [`Spans::spans_by`][crate::Spans::spans_by], but yields each span as a [`Vec`].
+    fn spans_by<F>(self, are_connected: F) -> StreamSpansBy<Self, F>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        StreamSpansBy::new(self, are_connected)
+    }
+
+    /// Groups the items of the stream into spans by comparing the keys of adjacent items.
+    ///
+    /// This is like [`Spans::spans_by_key`][crate::Spans::spans_by_key], but yields each span as a [`Vec`].
+    /// The key of each item is made once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::stream::{self, StreamExt};
+    /// use spans::stream::StreamSpans;
+    ///
+    /// let spans: Vec<Vec<&str>> = block_on(
+    ///     stream::iter(vec!["apple", "avocado", "banana"])
+    ///         .spans_by_key(|s| s.as_bytes()[0], |a, b| a == b)
+    ///         .collect(),
+    /// );
+    ///
+    /// assert_eq!(spans, vec![vec!["apple", "avocado"], vec!["banana"]]);
+    /// ```
+    fn spans_by_key<K, C, F>(self, key: K, are_connected: F) -> StreamSpansBy<Self, ByKey<K, C, F>>
+    where
+        K: FnMut(&Self::Item) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+    {
+        StreamSpansBy::new(self, ByKey::new(key, are_connected))
+    }
+
+    /// Groups the items of the stream into spans of items with equal keys.
+    ///
+    /// This is like [`Spans::spans_by_key_change`][crate::Spans::spans_by_key_change], but yields each span as a [`Vec`].
+    fn spans_by_key_change<K, C>(self, key: K) -> StreamSpansBy<Self, ByKeyChange<K, C>>
+    where
+        K: FnMut(&Self::Item) -> C,
+        C: Clone + PartialEq,
+    {
+        StreamSpansBy::new(self, ByKey::new(key, |a: C, b: C| a == b))
+    }
+
+    /// Groups the items of the stream into spans using `connector`.
+    ///
+    /// This allows using any [`Connector`], such as a stateful connector, with a stream.
+    fn spans_with<F>(self, connector: F) -> StreamSpansBy<Self, F>
+    where
+        F: Connector<Self::Item>,
+    {
+        StreamSpansBy::new(self, connector)
+    }
+}
+
+impl<S: Stream> StreamSpans for S {}
+
+/// `StreamSpansBy` is a stream of the spans of a stream, each collected into a [`Vec`].
+///
+/// See [`StreamSpans`] for more information.
+#[derive(Debug)]
+pub struct StreamSpansBy<S: Stream, F> {
+    /// The underlying stream.
+    stream: S,
+    /// The connector deciding whether two adjacent items belong to the same span.
+    connector: F,
+    /// The items of the current span.
+    span: Vec<S::Item>,
+    /// Whether the underlying stream has ended.
+    is_done: bool,
+}
+
+impl<S: Stream, F> StreamSpansBy<S, F> {
+    /// Creates a new `StreamSpansBy`.
+    fn new(stream: S, connector: F) -> Self {
+        StreamSpansBy {
+            stream,
+            connector,
+            span: Vec::new(),
+            is_done: false,
+        }
+    }
+
+    /// Returns a reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Returns the items of the current span that have been received so far.
+    pub fn pending_span(&self) -> &[S::Item] {
+        &self.span
+    }
+
+    /// Consumes `self`, returning the underlying stream.
+    ///
+    /// The items of the current span are discarded.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    /// Ends the current span when no item arrives within `timeout`.
+    ///
+    /// The timer restarts with every item and is only running while a span has items.
+    /// When it fires, the current span is yielded and the connector is reset, such that the next item starts a new span.
+    /// This turns the spans into session windows of live event streams, which end on disconnected items or inactivity.
+    ///
+    /// The timer uses [`tokio::time`], so the returned [`IdleSpans`] must be polled within a Tokio runtime with the time driver enabled.
+    /// It can be created outside of a runtime, since the timer starts with the first item.
+    /// Requires the `tokio` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use futures::stream::StreamExt;
+    /// use spans::stream::StreamSpans;
+    /// use std::time::Duration;
+    ///
+    /// # #[tokio::main(flavor = "current_thread", start_paused = true)]
+    /// # async fn main() {
+    /// let (sender, receiver) = futures::channel::mpsc::unbounded();
+    /// tokio::spawn(async move {
+    ///     for (delay, click) in [(0, "a"), (5, "b"), (60, "c")] {
+    ///         tokio::time::sleep(Duration::from_secs(delay)).await;
+    ///         sender.unbounded_send(click).unwrap();
+    ///     }
+    /// });
+    ///
+    /// let sessions: Vec<Vec<&str>> = receiver
+    ///     .spans_by(|_, _| true)
+    ///     .with_idle_timeout(Duration::from_secs(30))
+    ///     .collect()
+    ///     .await;
+    ///
+    /// assert_eq!(sessions, vec![vec!["a", "b"], vec!["c"]]);
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn with_idle_timeout(self, timeout: Duration) -> IdleSpans<S, F> {
+        IdleSpans {
+            spans: self,
+            timeout,
+            sleep: None,
+        }
+    }
+
+    /// Adds `item` to the current span, returning the current span if `item` starts a new span.
+    pub(crate) fn push(&mut self, item: S::Item) -> Option<Vec<S::Item>>
+    where
+        F: Connector<S::Item>,
+    {
+        let is_connected = match self.span.last() {
+            Some(prev) => self.connector.are_connected(prev, &item),
+            None => {
+                self.connector.start_span(&item);
+                self.span.push(item);
+                return None;
+            }
+        };
+
+        if is_connected {
+            self.span.push(item);
+            return None;
+        }
+
+        self.connector.start_span(&item);
+        let mut span = Vec::with_capacity(self.span.len());
+        span.push(item);
+        Some(mem::replace(&mut self.span, span))
+    }
+
+    /// Removes and returns the current span, if it is non-empty.
+    pub(crate) fn take_span(&mut self) -> Option<Vec<S::Item>> {
+        if self.span.is_empty() {
+            None
+        } else {
+            Some(mem::take(&mut self.span))
+        }
+    }
+}
+
+// No field is structurally pinned; the stream is only polled when it is `Unpin`.
+impl<S: Stream + Unpin, F> Unpin for StreamSpansBy<S, F> {}
+
+impl<S, F> Stream for StreamSpansBy<S, F>
+where
+    S: Stream + Unpin,
+    F: Connector<S::Item>,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.is_done {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            match Pin::new(&mut this.stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    if let Some(span) = this.push(item) {
+                        return Poll::Ready(Some(span));
+                    }
+                }
+                Poll::Ready(None) => {
+                    this.is_done = true;
+                    return Poll::Ready(this.take_span());
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.is_done {
+            return (0, Some(0));
+        }
+
+        let (lower, upper) = self.stream.size_hint();
+        let has_span = !self.span.is_empty();
+        let lower = if has_span || lower > 0 { 1 } else { 0 };
+        let upper = upper.and_then(|upper| upper.checked_add(has_span as usize));
+        (lower, upper)
+    }
+}
+
+impl<S, F> FusedStream for StreamSpansBy<S, F>
+where
+    S: Stream + Unpin,
+    F: Connector<S::Item>,
+{
+    fn is_terminated(&self) -> bool {
+        self.is_done
+    }
+}
+
+/// `IdleSpans` is a stream of the spans of a stream that also ends spans after a period of inactivity.
+///
+/// See [`StreamSpansBy::with_idle_timeout`] for more information.
+#[cfg(feature = "tokio")]
+pub struct IdleSpans<S: Stream, F> {
+    /// The wrapped spans.
+    spans: StreamSpansBy<S, F>,
+    /// The time after the most recent item at which the current span ends.
+    timeout: Duration,
+    /// The timer of the current span, restarted with every item.
+    ///
+    /// `None` before the first item, such that `IdleSpans` can be created outside of a Tokio runtime.
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+#[cfg(feature = "tokio")]
+impl<S: Stream, F> IdleSpans<S, F> {
+    /// Returns the maximum time between two items of the same span.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Consumes `self`, returning the wrapped spans.
+    pub fn into_inner(self) -> StreamSpansBy<S, F> {
+        self.spans
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<S, F> Stream for IdleSpans<S, F>
+where
+    S: Stream + Unpin,
+    F: Connector<S::Item>,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if this.spans.is_done {
+            return Poll::Ready(None);
+        }
+
+        loop {
+            match Pin::new(&mut this.spans.stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let deadline = tokio::time::Instant::now() + this.timeout;
+                    match &mut this.sleep {
+                        Some(sleep) => sleep.as_mut().reset(deadline),
+                        None => this.sleep = Some(Box::pin(tokio::time::sleep_until(deadline))),
+                    }
+
+                    if let Some(span) = this.spans.push(item) {
+                        return Poll::Ready(Some(span));
+                    }
+                }
+                Poll::Ready(None) => {
+                    this.spans.is_done = true;
+                    return Poll::Ready(this.spans.take_span());
+                }
+                Poll::Pending => {
+                    // The timer is only polled while a span has items, so it never fires between spans.
+                    let sleep = match &mut this.sleep {
+                        Some(sleep) if !this.spans.span.is_empty() => sleep,
+                        _ => return Poll::Pending,
+                    };
+                    if sleep.as_mut().poll(cx).is_pending() {
+                        return Poll::Pending;
+                    }
+
+                    this.spans.connector.reset();
+                    return Poll::Ready(this.spans.take_span());
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.size_hint()
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl<S, F> FusedStream for IdleSpans<S, F>
+where
+    S: Stream + Unpin,
+    F: Connector<S::Item>,
+{
+    fn is_terminated(&self) -> bool {
+        self.spans.is_done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StreamSpans;
+    use futures::executor::block_on;
+    use futures::stream::{self, StreamExt};
+
+    #[test]
+    fn test_spans_match_iterator_spans() {
+        use crate::Spans;
+
+        let vec = [1, 1, 2, 3, 3, 5, 8, 8, 8];
+        let spans = block_on(
+            stream::iter(vec)
+                .spans_by_key_change(|&x| x % 2)
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            spans,
+            vec.iter()
+                .copied()
+                .spans_by_key_change(|&x| x % 2)
+                .map_spans(|span| span.collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+        assert!(block_on(
+            stream::iter(Vec::<i32>::new())
+                .spans_by(|a, b| a == b)
+                .next()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_pending_items_stay_in_span() {
+        let (sender, receiver) = futures::channel::mpsc::unbounded();
+        let mut spans = receiver.spans_by(|a: &i32, b: &i32| a == b);
+        let mut cx = futures::task::Context::from_waker(futures::task::noop_waker_ref());
+
+        sender.unbounded_send(1).unwrap();
+        sender.unbounded_send(1).unwrap();
+        assert!(spans.poll_next_unpin(&mut cx).is_pending());
+        assert_eq!(spans.pending_span(), [1, 1]);
+
+        sender.unbounded_send(2).unwrap();
+        drop(sender);
+        assert_eq!(block_on(spans.next()), Some(vec![1, 1]));
+        assert_eq!(block_on(spans.next()), Some(vec![2]));
+        assert_eq!(block_on(spans.next()), None);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_timeout_ends_span() {
+        use std::time::Duration;
+
+        let send_after = |delays: &'static [u64]| {
+            let (sender, receiver) = futures::channel::mpsc::unbounded();
+            tokio::spawn(async move {
+                for &delay in delays {
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                    sender.unbounded_send(1).unwrap();
+                }
+            });
+            receiver
+        };
+
+        let spans = send_after(&[0, 5, 50, 5])
+            .spans_by_key_change(|&x| x)
+            .with_idle_timeout(Duration::from_millis(10))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(spans, vec![vec![1, 1], vec![1, 1]]);
+
+        let spans = send_after(&[0, 5, 50, 5])
+            .spans_by_key_change(|&x| x)
+            .with_idle_timeout(Duration::from_millis(100))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(spans, vec![vec![1, 1, 1, 1]]);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_timeout_keeps_key_breaks() {
+        use std::time::Duration;
+
+        let spans = stream::iter([1, 1, 2])
+            .spans_by_key_change(|&x| x)
+            .with_idle_timeout(Duration::from_millis(10))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(spans, vec![vec![1, 1], vec![2]]);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_idle_timeout_outside_runtime() {
+        use std::time::Duration;
+
+        let spans = stream::iter([1, 2])
+            .spans_by(|a, b| a == b)
+            .with_idle_timeout(Duration::from_millis(10));
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let spans = runtime.block_on(spans.collect::<Vec<_>>());
+        assert_eq!(spans, vec![vec![1], vec![2]]);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{Connector, SpansBy};
+use alloc::vec::Vec;
+use streaming_iterator::{StreamingIterator, StreamingIteratorMut};
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Converts `self` into a [`StreamingIterator`] over the spans of the iterator.
+    ///
+    /// Each span is collected into a buffer that is reused for all spans.
+    /// The items of the current span are lent as a slice until the iterator is advanced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    /// use streaming_iterator::StreamingIterator;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let mut spans = vec
+    ///     .into_iter()
+    ///     .spans_by_key(|&x| x, |a, b| a + 1 == b)
+    ///     .streaming_spans();
+    ///
+    /// assert_eq!(spans.next(), Some(&[1, 2][..]));
+    /// assert_eq!(spans.next(), Some(&[5, 6, 7][..]));
+    /// assert_eq!(spans.next(), Some(&[11][..]));
+    /// assert_eq!(spans.next(), None);
+    /// ```
+    pub fn streaming_spans(self) -> StreamingSpans<I, F> {
+        StreamingSpans {
+            spans: self,
+            buffer: Vec::new(),
+            is_done: true,
+        }
+    }
+}
+
+/// `StreamingSpans` is a [`StreamingIterator`] over the spans of a `SpansBy`.
+///
+/// See [`SpansBy::streaming_spans`] for more information.
+pub struct StreamingSpans<I: Iterator, F> {
+    /// The wrapped spans.
+    spans: SpansBy<I, F>,
+    /// The items of the current span.
+    buffer: Vec<I::Item>,
+    /// Whether there is no current span.
+    ///
+    /// `true` initially and after the wrapped spans terminated.
+    is_done: bool,
+}
+
+impl<I, F> StreamingIterator for StreamingSpans<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    type Item = [I::Item];
+
+    fn advance(&mut self) {
+        self.buffer.clear();
+
+        match self.spans.next() {
+            Some(span) => {
+                self.buffer.extend(span);
+                self.is_done = false;
+            }
+            None => self.is_done = true,
+        }
+    }
+
+    fn get(&self) -> Option<&Self::Item> {
+        if self.is_done {
+            None
+        } else {
+            Some(&self.buffer)
+        }
+    }
+}
+
+impl<I, F> StreamingIteratorMut for StreamingSpans<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    fn get_mut(&mut self) -> Option<&mut Self::Item> {
+        if self.is_done {
+            None
+        } else {
+            Some(&mut self.buffer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+    use streaming_iterator::{StreamingIterator, StreamingIteratorMut};
+
+    #[test]
+    fn test_empty() {
+        let mut spans = Vec::<u32>::new()
+            .into_iter()
+            .spans_by_key_change(|&x| x)
+            .streaming_spans();
+        assert!(spans.get().is_none());
+        assert!(spans.next().is_none());
+        assert!(spans.next().is_none());
+    }
+
+    #[test]
+    fn test_adapters() {
+        let spans = "aabccc".chars().spans_by(|a, b| a == b).streaming_spans();
+        let lengths: Vec<usize> = spans.map(|span| span.len()).cloned().collect();
+        assert_eq!(lengths, vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let mut spans = [3, 1, 2, 9, 7]
+            .iter()
+            .copied()
+            .spans_by_key(|&x| x < 5, |a, b| a == b)
+            .streaming_spans();
+        let mut sorted = Vec::new();
+        while let Some(span) = spans.next_mut() {
+            span.sort_unstable();
+            sorted.push(span.to_vec());
+        }
+        assert_eq!(sorted, vec![vec![1, 2, 3], vec![7, 9]]);
+    }
+}
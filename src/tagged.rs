@@ -0,0 +1,204 @@
+use crate::{Connector, SpansBy};
+use core::iter::FusedIterator;
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    /// Converts `self` into a flat iterator over all items, each tagged with the index of its span.
+    ///
+    /// The index of a span is the same as [`Span::index`][crate::Span::index].
+    /// Unlike the spans themselves, the returned [`TaggedItems`] is an ordinary [`Iterator`], which is convenient for inserting rows into tables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let rows: Vec<(usize, i32)> = vec
+    ///     .into_iter()
+    ///     .spans_by_key(|&x| x, |a, b| a + 1 == b)
+    ///     .tagged_items()
+    ///     .collect();
+    ///
+    /// assert_eq!(rows, vec![(0, 1), (0, 2), (1, 5), (1, 6), (1, 7), (2, 11)]);
+    /// ```
+    pub fn tagged_items(self) -> TaggedItems<I, F> {
+        TaggedItems { spans: self }
+    }
+
+    /// Converts `self` into a flat iterator over all items with a [`Element::Boundary`] between each two spans.
+    ///
+    /// This lets consumers react to the start of a new span inline, without nested iteration.
+    /// There is no boundary before the first span or after the last span.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::{Element, Spans};
+    ///
+    /// let mut output = String::new();
+    /// for element in "aabccc".chars().spans_by(|a, b| a == b).with_boundaries() {
+    ///     match element {
+    ///         Element::Item(c) => output.push(c),
+    ///         Element::Boundary => output.push('|'),
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(output, "aa|b|ccc");
+    /// ```
+    pub fn with_boundaries(self) -> WithBoundaries<I, F> {
+        WithBoundaries { spans: self }
+    }
+}
+
+/// `TaggedItems` is an iterator over the items of a `SpansBy`, each tagged with the index of its span.
+///
+/// See [`SpansBy::tagged_items`] for more information.
+pub struct TaggedItems<I: Iterator, F> {
+    /// The wrapped spans.
+    spans: SpansBy<I, F>,
+}
+
+impl<I, F> Iterator for TaggedItems<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    type Item = (usize, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = match self.spans.next_item() {
+            Some(item) => item,
+            None => {
+                self.spans.start_span()?;
+                self.spans.next_item()?
+            }
+        };
+
+        Some((self.spans.span_count - 1, item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.spans.is_done {
+            (0, Some(0))
+        } else {
+            self.spans.iter.size_hint()
+        }
+    }
+}
+
+impl<I, F> FusedIterator for TaggedItems<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+}
+
+/// An element of [`WithBoundaries`], which is either an item or a boundary between two spans.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Element<T> {
+    /// An item of a span.
+    Item(T),
+    /// The boundary between the previous span and the next span.
+    Boundary,
+}
+
+/// `WithBoundaries` is an iterator over the items of a `SpansBy` with boundaries between the spans.
+///
+/// See [`SpansBy::with_boundaries`] for more information.
+pub struct WithBoundaries<I: Iterator, F> {
+    /// The wrapped spans.
+    spans: SpansBy<I, F>,
+}
+
+impl<I, F> Iterator for WithBoundaries<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+    type Item = Element<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.spans.next_item() {
+            return Some(Element::Item(item));
+        }
+
+        let is_first_span = self.spans.span_count == 0;
+        self.spans.start_span()?;
+
+        if is_first_span {
+            self.spans.next_item().map(Element::Item)
+        } else {
+            // The first item of the started span is yielded by the next call.
+            Some(Element::Boundary)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.spans.is_done {
+            return (0, Some(0));
+        }
+
+        // Each remaining item may be preceded by a boundary.
+        let (lower, upper) = self.spans.iter.size_hint();
+        (lower, upper.and_then(|upper| upper.checked_mul(2)))
+    }
+}
+
+impl<I, F> FusedIterator for WithBoundaries<I, F>
+where
+    I: Iterator,
+    F: Connector<I::Item>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Element, Spans};
+
+    #[test]
+    fn test_empty() {
+        let mut items = core::iter::empty::<i32>()
+            .spans_by(|a, b| a == b)
+            .tagged_items();
+        assert_eq!(items.size_hint(), (0, Some(0)));
+        assert_eq!(items.next(), None);
+    }
+
+    #[test]
+    fn test_after_partial_span() {
+        let vec = [1, 1, 1, 2];
+        let mut spans = vec.iter().spans_by(|a, b| a == b);
+        assert_eq!(spans.next().unwrap().next(), Some(&1));
+        let items = spans.tagged_items().collect::<Vec<_>>();
+        assert_eq!(items, vec![(0, &1), (0, &1), (1, &2)]);
+    }
+
+    #[test]
+    fn test_boundaries() {
+        let elements = [1, 2, 2]
+            .iter()
+            .copied()
+            .spans_by(|a, b| a == b)
+            .with_boundaries()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            elements,
+            vec![
+                Element::Item(1),
+                Element::Boundary,
+                Element::Item(2),
+                Element::Item(2)
+            ]
+        );
+        assert!(core::iter::empty::<i32>()
+            .spans_by(|a, b| a == b)
+            .with_boundaries()
+            .next()
+            .is_none());
+    }
+}
@@ -0,0 +1,360 @@
+//! Spans of text.
+//!
+//! Import the [`StrSpans`] trait to split strings into string slices by their characters:
+//!
+//! ```
+//! use spans::text::StrSpans;
+//!
+//! let runs: Vec<&str> = "abc123  def"
+//!     .spans_by_key_change(|c| {
+//!         if c.is_alphabetic() {
+//!             0
+//!         } else if c.is_numeric() {
+//!             1
+//!         } else {
+//!             2
+//!         }
+//!     })
+//!     .collect();
+//!
+//! assert_eq!(runs, vec!["abc", "123", "  ", "def"]);
+//! ```
+
+use crate::{are_equal, ByKey, ByKeyChange, Connector};
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+/// `StrSpans` provides span adapters for string slices.
+pub trait StrSpans {
+    /// Splits the string into contiguous string slices by comparing adjacent characters.
+    ///
+    /// This is like [`Spans::spans_by`][crate::Spans::spans_by] for [`str::chars`], except that each span is a string slice.
+    /// Spans always end at character boundaries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::text::StrSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let mut spans = "äaaÖo".spans_by(|a, b| a.is_lowercase() == b.is_lowercase());
+    ///
+    /// assert_eq!(spans.next()?, "äaa");
+    /// assert_eq!(spans.next()?, "Ö");
+    /// assert_eq!(spans.next()?, "o");
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by<F>(&self, are_connected: F) -> StrSpansBy<'_, F>
+    where
+        F: FnMut(&char, &char) -> bool;
+
+    /// Splits the string into contiguous string slices by comparing the keys of adjacent characters.
+    ///
+    /// This is like [`Spans::spans_by_key`][crate::Spans::spans_by_key] for [`str::chars`], except that each span is a string slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::text::StrSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let mut spans = "abdxy".spans_by_key(|&c| c as u32, |a, b| a + 1 == b);
+    ///
+    /// assert_eq!(spans.next()?, "ab");
+    /// assert_eq!(spans.next()?, "d");
+    /// assert_eq!(spans.next()?, "xy");
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by_key<K, C, F>(&self, key: K, are_connected: F) -> StrSpansBy<'_, ByKey<K, C, F>>
+    where
+        K: FnMut(&char) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool;
+
+    /// Splits the string into contiguous string slices of characters with equal keys.
+    ///
+    /// This is like [`Spans::spans_by_key_change`][crate::Spans::spans_by_key_change] for [`str::chars`], except that each span is a string slice.
+    /// The key can be thought of as a character class, such as alphabetic, numeric, or whitespace.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::text::StrSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let mut spans = "one  two".spans_by_key_change(|c| c.is_whitespace());
+    ///
+    /// assert_eq!(spans.next()?, "one");
+    /// assert_eq!(spans.next()?, "  ");
+    /// assert_eq!(spans.next()?, "two");
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn spans_by_key_change<K, C>(&self, key: K) -> StrSpansBy<'_, ByKeyChange<K, C>>
+    where
+        K: FnMut(&char) -> C,
+        C: Clone + PartialEq;
+
+    /// Splits the string into paragraphs and their byte ranges.
+    ///
+    /// A paragraph is a span of lines that contain more than whitespace.
+    /// Paragraphs are separated by one or more blank lines, which are not part of any paragraph.
+    /// The line terminator (`\n` or `\r\n`) of the last line of a paragraph is not part of the paragraph.
+    ///
+    /// Each paragraph is yielded together with its byte range in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::text::StrSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let text = "one\ntwo\n\n  \nthree\n";
+    /// let mut paragraphs = text.paragraphs();
+    ///
+    /// assert_eq!(paragraphs.next()?, (0..7, "one\ntwo"));
+    /// assert_eq!(paragraphs.next()?, (12..17, "three"));
+    /// assert!(paragraphs.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn paragraphs(&self) -> Paragraphs<'_>;
+}
+
+impl StrSpans for str {
+    fn spans_by<F>(&self, are_connected: F) -> StrSpansBy<'_, F>
+    where
+        F: FnMut(&char, &char) -> bool,
+    {
+        StrSpansBy {
+            text: self,
+            connector: are_connected,
+        }
+    }
+
+    fn spans_by_key<K, C, F>(&self, key: K, are_connected: F) -> StrSpansBy<'_, ByKey<K, C, F>>
+    where
+        K: FnMut(&char) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+    {
+        StrSpansBy {
+            text: self,
+            connector: ByKey::new(key, are_connected),
+        }
+    }
+
+    fn spans_by_key_change<K, C>(&self, key: K) -> StrSpansBy<'_, ByKeyChange<K, C>>
+    where
+        K: FnMut(&char) -> C,
+        C: Clone + PartialEq,
+    {
+        self.spans_by_key(key, are_equal)
+    }
+
+    fn paragraphs(&self) -> Paragraphs<'_> {
+        Paragraphs {
+            text: self,
+            offset: 0,
+        }
+    }
+}
+
+/// `StrSpansBy` is an iterator over the spans of a string as string slices.
+///
+/// See [`StrSpans::spans_by`] and [`StrSpans::spans_by_key`] for more information.
+#[derive(Clone)]
+pub struct StrSpansBy<'a, F> {
+    /// The text that is not part of any returned span yet.
+    text: &'a str,
+    /// Whether two adjacent characters belong to the same span.
+    connector: F,
+}
+
+impl<'a, F> Iterator for StrSpansBy<'a, F>
+where
+    F: Connector<char>,
+{
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chars = self.text.char_indices();
+        let (_, mut prev) = chars.next()?;
+        self.connector.start_span(&prev);
+
+        let mut len = self.text.len();
+
+        for (index, next) in chars {
+            if !self.connector.are_connected(&prev, &next) {
+                len = index;
+                break;
+            }
+
+            prev = next;
+        }
+
+        let (span, rest) = self.text.split_at(len);
+        self.text = rest;
+        Some(span)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.text.len().min(1), Some(self.text.len()))
+    }
+}
+
+impl<F> FusedIterator for StrSpansBy<'_, F> where F: Connector<char> {}
+
+impl<'a, F> StrSpansBy<'a, F>
+where
+    F: Connector<char>,
+{
+    /// Converts `self` into an iterator over the byte range of each span.
+    ///
+    /// The ranges are relative to the text that had not been returned as part of a span when this method was called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::text::StrSpans;
+    ///
+    /// let text = "aäb12";
+    /// let ranges: Vec<_> = text
+    ///     .spans_by_key_change(|c| c.is_alphabetic())
+    ///     .byte_ranges()
+    ///     .collect();
+    ///
+    /// assert_eq!(ranges, vec![0..4, 4..6]);
+    /// assert_eq!(&text[ranges[0].clone()], "aäb");
+    /// ```
+    pub fn byte_ranges(self) -> ByteRanges<'a, F> {
+        ByteRanges {
+            spans: self,
+            offset: 0,
+        }
+    }
+}
+
+/// `ByteRanges` is an iterator over the byte range of each span of a `StrSpansBy`.
+///
+/// See [`StrSpansBy::byte_ranges`] for more information.
+#[derive(Clone)]
+pub struct ByteRanges<'a, F> {
+    /// The wrapped spans.
+    spans: StrSpansBy<'a, F>,
+    /// The byte offset of the next span.
+    offset: usize,
+}
+
+impl<F> Iterator for ByteRanges<'_, F>
+where
+    F: Connector<char>,
+{
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.offset;
+        self.offset += self.spans.next()?.len();
+        Some(start..self.offset)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.spans.size_hint()
+    }
+}
+
+impl<F> FusedIterator for ByteRanges<'_, F> where F: Connector<char> {}
+
+/// `Paragraphs` is an iterator over the paragraphs of a string and their byte ranges.
+///
+/// See [`StrSpans::paragraphs`] for more information.
+#[derive(Clone)]
+pub struct Paragraphs<'a> {
+    /// The whole text.
+    text: &'a str,
+    /// The byte offset of the first line that has not been visited yet.
+    offset: usize,
+}
+
+impl<'a> Iterator for Paragraphs<'a> {
+    type Item = (Range<usize>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut range: Option<Range<usize>> = None;
+
+        while self.offset < self.text.len() {
+            let rest = &self.text[self.offset..];
+            let line_len = rest.find('\n').map_or(rest.len(), |index| index + 1);
+            let line = &rest[..line_len];
+            let content = match line.strip_suffix('\n') {
+                Some(line) => line.strip_suffix('\r').unwrap_or(line),
+                None => line,
+            };
+
+            let start = self.offset;
+            self.offset += line_len;
+
+            if content.trim().is_empty() {
+                if range.is_some() {
+                    break;
+                }
+            } else {
+                let end = start + content.len();
+                match &mut range {
+                    Some(range) => range.end = end,
+                    None => range = Some(start..end),
+                }
+            }
+        }
+
+        range.map(|range| (range.clone(), &self.text[range]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.text.len() - self.offset;
+        (0, Some(len.div_ceil(2)))
+    }
+}
+
+impl FusedIterator for Paragraphs<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::StrSpans;
+
+    #[test]
+    fn test_empty() {
+        assert!("".spans_by(|a, b| a == b).next().is_none());
+    }
+
+    #[test]
+    fn test_multibyte_boundaries() {
+        let spans = "ßß€€€a🦀".spans_by(|a, b| a == b).collect::<Vec<_>>();
+        assert_eq!(spans, vec!["ßß", "€€€", "a", "🦀"]);
+    }
+
+    #[test]
+    fn test_char_classes() {
+        let spans = "x1 2yz"
+            .spans_by_key_change(|c| (c.is_alphabetic(), c.is_numeric()))
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec!["x", "1", " ", "2", "yz"]);
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let text = "\r\n a\r\nb \r\n\t\r\n\nc";
+        let paragraphs = text.paragraphs().collect::<Vec<_>>();
+        assert_eq!(paragraphs, vec![(2..8, " a\r\nb "), (14..15, "c")]);
+        assert!("\n \n".paragraphs().next().is_none());
+    }
+}
@@ -0,0 +1,295 @@
+//! Helpers for splitting iterators of [`time`] date-times into contiguous spans.
+//!
+//! Use [`within`] as a connector for [`Spans::spans_by_key`] to split by time gap:
+//!
+//! ```
+//! use spans::Spans;
+//! use time::{Duration, OffsetDateTime};
+//!
+//! let logins = vec![
+//!     OffsetDateTime::from_unix_timestamp(1_714_550_400).unwrap(),
+//!     OffsetDateTime::from_unix_timestamp(1_714_551_000).unwrap(),
+//!     OffsetDateTime::from_unix_timestamp(1_714_572_000).unwrap(),
+//! ];
+//! let sessions: Vec<usize> = logins
+//!     .iter()
+//!     .spans_by_key(|&&t| t, spans::time::within(Duration::minutes(30)))
+//!     .map_spans(|span| span.count())
+//!     .collect();
+//!
+//! assert_eq!(sessions, vec![2, 1]);
+//! ```
+//!
+//! Use a calendar bucket, such as [`hour`], [`day`], [`iso_week`], or [`month`], with [`Spans::spans_by_bucket`] to split at calendar boundaries:
+//!
+//! ```
+//! use spans::Spans;
+//! use time::OffsetDateTime;
+//!
+//! let readings = vec![
+//!     (OffsetDateTime::from_unix_timestamp(1_714_550_400).unwrap(), 12.5),
+//!     (OffsetDateTime::from_unix_timestamp(1_714_604_400).unwrap(), 9.0),
+//!     (OffsetDateTime::from_unix_timestamp(1_714_611_600).unwrap(), 8.5),
+//! ];
+//! let days: Vec<usize> = readings
+//!     .iter()
+//!     .spans_by_bucket(|(t, _)| spans::time::day(t))
+//!     .map_spans(|span| span.count())
+//!     .collect();
+//!
+//! assert_eq!(days, vec![2, 1]);
+//! ```
+//!
+//! Calendar boundaries are determined in the UTC offset of each date-time.
+//!
+//! Use [`Weekdays`] with [`consecutive_business_days`] to split dates into spans of consecutive business days.
+//!
+//! [`Spans::spans_by_key`]: crate::Spans::spans_by_key
+//! [`consecutive_business_days`]: crate::consecutive_business_days
+//! [`Spans::spans_by_bucket`]: crate::Spans::spans_by_bucket
+
+use crate::Calendar;
+use core::ops::Sub;
+use time::{Date, Duration, Month, OffsetDateTime, Time, Weekday};
+
+/// Returns a connector for date-times, dates, or times that are at most `max_gap` apart.
+///
+/// Two values are connected if the second is not before the first and the difference between them is at most `max_gap`.
+/// For [`OffsetDateTime`], the difference is computed on the instants in time, such that changes of the UTC offset, for example due to daylight saving time, do not affect it.
+pub fn within<T>(max_gap: Duration) -> impl FnMut(T, T) -> bool
+where
+    T: Sub<Output = Duration> + PartialOrd,
+{
+    move |prev, next| prev <= next && next - prev <= max_gap
+}
+
+/// Returns the calendar day of `datetime` in its UTC offset.
+pub fn day(datetime: &OffsetDateTime) -> Date {
+    datetime.date()
+}
+
+/// Returns the ISO 8601 year and week number of `datetime` in its UTC offset.
+///
+/// ISO weeks start on Monday; the first and last days of a calendar year may belong to a week of the adjacent ISO year.
+pub fn iso_week(datetime: &OffsetDateTime) -> (i32, u8) {
+    let (year, week, _) = datetime.to_iso_week_date();
+    (year, week)
+}
+
+/// Returns the year and the month of `datetime` in its UTC offset.
+pub fn month(datetime: &OffsetDateTime) -> (i32, Month) {
+    (datetime.year(), datetime.month())
+}
+
+/// Returns the start of the hour of `datetime` in its UTC offset.
+///
+/// The UTC offset is kept, such that the repeated hour at the end of daylight saving time is distinct from the hour before it.
+pub fn hour(datetime: &OffsetDateTime) -> OffsetDateTime {
+    let time = Time::from_hms(datetime.hour(), 0, 0).expect("the hour of a date-time is valid");
+    datetime.replace_time(time)
+}
+
+/// `Weekdays` is a [`Calendar`] of weekdays, where Saturdays, Sundays, and holidays are not business days.
+///
+/// # Example
+///
+/// ```
+/// use spans::time::Weekdays;
+/// use spans::{consecutive_business_days, Spans};
+/// use time::{Date, Month};
+///
+/// let christmas = Date::from_calendar_date(2024, Month::December, 25).unwrap();
+/// let calendar = Weekdays::with_holidays(move |&date| date == christmas);
+///
+/// let dates: Vec<_> = [20, 23, 24, 26, 30]
+///     .iter()
+///     .map(|&day| Date::from_calendar_date(2024, Month::December, day).unwrap())
+///     .collect();
+/// let lengths: Vec<_> = dates
+///     .iter()
+///     .spans_by_key(|&&date| date, consecutive_business_days(calendar))
+///     .map_spans(|span| span.count())
+///     .collect();
+///
+/// assert_eq!(lengths, vec![4, 1]);
+/// ```
+pub struct Weekdays<H = fn(&Date) -> bool> {
+    /// Whether a date is a holiday.
+    is_holiday: H,
+}
+
+impl Weekdays {
+    /// Creates a new `Weekdays` calendar without holidays.
+    pub fn new() -> Self {
+        Weekdays {
+            is_holiday: |_| false,
+        }
+    }
+}
+
+impl Default for Weekdays {
+    fn default() -> Self {
+        Weekdays::new()
+    }
+}
+
+impl<H> Weekdays<H>
+where
+    H: Fn(&Date) -> bool,
+{
+    /// Creates a new `Weekdays` calendar where dates for which `is_holiday` returns `true` are not business days.
+    pub fn with_holidays(is_holiday: H) -> Self {
+        Weekdays { is_holiday }
+    }
+}
+
+impl<H> Calendar<Date> for Weekdays<H>
+where
+    H: Fn(&Date) -> bool,
+{
+    fn next_business_day(&self, date: &Date) -> Option<Date> {
+        let mut date = date.next_day()?;
+
+        while matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday)
+            || (self.is_holiday)(&date)
+        {
+            date = date.next_day()?;
+        }
+
+        Some(date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Spans;
+    use time::UtcOffset;
+
+    /// Returns the time in New York with the given offset in hours.
+    fn new_york(offset: i8, (y, m, d): (i32, Month, u8), (h, min): (u8, u8)) -> OffsetDateTime {
+        Date::from_calendar_date(y, m, d)
+            .unwrap()
+            .with_hms(h, min, 0)
+            .unwrap()
+            .assume_offset(UtcOffset::from_hms(offset, 0, 0).unwrap())
+    }
+
+    #[test]
+    fn test_within_spring_forward() {
+        // 01:59 EST is followed by 03:00 EDT one minute later.
+        let times = [
+            new_york(-5, (2024, Month::March, 10), (1, 59)),
+            new_york(-4, (2024, Month::March, 10), (3, 0)),
+            new_york(-4, (2024, Month::March, 10), (3, 30)),
+        ];
+        let spans = times
+            .iter()
+            .spans_by_key(|&&t| t, within(Duration::minutes(5)))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_within_dates() {
+        let dates = [
+            Date::from_calendar_date(2024, Month::February, 28).unwrap(),
+            Date::from_calendar_date(2024, Month::February, 29).unwrap(),
+            Date::from_calendar_date(2024, Month::March, 1).unwrap(),
+            Date::from_calendar_date(2024, Month::March, 3).unwrap(),
+        ];
+        let spans = dates
+            .iter()
+            .spans_by_key(|&&d| d, within(Duration::days(1)))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![3, 1]);
+    }
+
+    #[test]
+    fn test_hour_fall_back() {
+        // The hour from 01:00 to 02:00 occurs twice, once in EDT and once in EST.
+        let times = [
+            new_york(-4, (2024, Month::November, 3), (1, 10)),
+            new_york(-4, (2024, Month::November, 3), (1, 50)),
+            new_york(-5, (2024, Month::November, 3), (1, 10)),
+            new_york(-5, (2024, Month::November, 3), (2, 10)),
+        ];
+        let spans = times
+            .iter()
+            .spans_by_bucket(|t| hour(t))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![2, 1, 1]);
+    }
+
+    #[test]
+    fn test_day_fall_back() {
+        // The day of the transition has 25 hours.
+        let times = [
+            new_york(-4, (2024, Month::November, 3), (0, 0)),
+            new_york(-5, (2024, Month::November, 3), (23, 59)),
+            new_york(-5, (2024, Month::November, 4), (0, 0)),
+        ];
+        let spans = times
+            .iter()
+            .spans_by_bucket(|t| day(t))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_iso_week_year_change() {
+        // 2024-12-30 is the Monday of the first ISO week of 2025.
+        let times = [
+            new_york(-5, (2024, Month::December, 29), (12, 0)),
+            new_york(-5, (2024, Month::December, 30), (12, 0)),
+            new_york(-5, (2025, Month::January, 5), (12, 0)),
+            new_york(-5, (2025, Month::January, 6), (12, 0)),
+        ];
+        let spans = times
+            .iter()
+            .spans_by_bucket(|t| iso_week(t))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn test_month() {
+        let times = [
+            new_york(-5, (2024, Month::January, 31), (23, 59)),
+            new_york(-5, (2024, Month::February, 1), (0, 0)),
+            new_york(-5, (2024, Month::February, 29), (0, 0)),
+            new_york(-5, (2025, Month::February, 1), (0, 0)),
+        ];
+        let spans = times
+            .iter()
+            .spans_by_bucket(|t| month(t))
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn test_weekdays() {
+        let date = |d| Date::from_calendar_date(2024, Month::May, d).unwrap();
+        let next = |d| Weekdays::new().next_business_day(&date(d));
+        // Thursday, Friday, Saturday, and Sunday.
+        assert_eq!(next(2), Some(date(3)));
+        assert_eq!(next(3), Some(date(6)));
+        assert_eq!(next(4), Some(date(6)));
+        assert_eq!(next(5), Some(date(6)));
+        assert_eq!(Weekdays::new().next_business_day(&Date::MAX), None);
+    }
+
+    #[test]
+    fn test_weekdays_with_holidays() {
+        // Friday and Monday are holidays.
+        let date = |d| Date::from_calendar_date(2024, Month::May, d).unwrap();
+        let calendar = Weekdays::with_holidays(|date: &Date| matches!(date.day(), 3 | 6));
+        assert_eq!(calendar.next_business_day(&date(2)), Some(date(7)));
+    }
+}
@@ -0,0 +1,133 @@
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::{Fuse, FusedIterator};
+
+/// `TolerantSpans` is an iterator over owned spans, where short runs of disconnected items are tolerated within a span.
+///
+/// See [`Spans::spans_by_with_tolerance`][crate::Spans::spans_by_with_tolerance] for more information.
+pub struct TolerantSpans<I: Iterator, F> {
+    /// The wrapped iterator, fused such that no items are taken from it once it terminated.
+    iter: Fuse<I>,
+    /// Returns whether two items are part of the same span.
+    are_connected: F,
+    /// The maximum number of consecutive outliers within a span.
+    max_outliers: usize,
+    /// The items taken from `iter` while looking ahead, which are not part of any returned span yet.
+    buffer: VecDeque<I::Item>,
+}
+
+impl<I: Iterator, F> TolerantSpans<I, F> {
+    /// Creates a new `TolerantSpans` iterator.
+    pub(crate) fn new(iter: I, max_outliers: usize, are_connected: F) -> Self {
+        TolerantSpans {
+            iter: iter.fuse(),
+            are_connected,
+            max_outliers,
+            buffer: VecDeque::new(),
+        }
+    }
+}
+
+impl<I, F> Iterator for TolerantSpans<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.buffer.pop_front() {
+            Some(item) => item,
+            None => self.iter.next()?,
+        };
+        let mut span = vec![first];
+
+        loop {
+            // The last item of the span is never an outlier, since outliers are only added together with a connected item.
+            let anchor = span.last()?;
+            let mut connected_index = None;
+
+            for index in 0..=self.max_outliers {
+                if index == self.buffer.len() {
+                    match self.iter.next() {
+                        Some(item) => self.buffer.push_back(item),
+                        None => break,
+                    }
+                }
+
+                if (self.are_connected)(anchor, &self.buffer[index]) {
+                    connected_index = Some(index);
+                    break;
+                }
+            }
+
+            match connected_index {
+                Some(index) => span.extend(self.buffer.drain(..=index)),
+                None => return Some(span),
+            }
+        }
+    }
+}
+
+impl<I, F> FusedIterator for TolerantSpans<I, F>
+where
+    I: Iterator,
+    F: FnMut(&I::Item, &I::Item) -> bool,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    fn tolerant_spans(items: &[i32], max_outliers: usize) -> Vec<Vec<i32>> {
+        items
+            .iter()
+            .copied()
+            .spans_by_with_tolerance(max_outliers, |a, b| (a - b).abs() <= 1)
+            .collect()
+    }
+
+    #[test]
+    fn test_no_tolerance() {
+        assert_eq!(
+            tolerant_spans(&[1, 2, 9, 3], 0),
+            vec![vec![1, 2], vec![9], vec![3]]
+        );
+    }
+
+    #[test]
+    fn test_consecutive_outliers() {
+        assert_eq!(
+            tolerant_spans(&[1, 2, 9, 9, 3, 4], 2),
+            vec![vec![1, 2, 9, 9, 3, 4]]
+        );
+        assert_eq!(
+            tolerant_spans(&[1, 2, 9, 8, 7, 3], 2),
+            vec![vec![1, 2], vec![9, 8, 7], vec![3]]
+        );
+    }
+
+    #[test]
+    fn test_trailing_outliers() {
+        assert_eq!(tolerant_spans(&[1, 2, 9], 1), vec![vec![1, 2], vec![9]]);
+    }
+
+    #[test]
+    fn test_unfused_iterator() {
+        let mut calls = 0;
+        let iter = core::iter::from_fn(|| {
+            calls += 1;
+            if calls == 2 {
+                None
+            } else {
+                Some(1)
+            }
+        });
+        let mut spans = iter.spans_by_with_tolerance(1, |a, b| a == b);
+        assert_eq!(spans.next(), Some(vec![1]));
+        assert_eq!(spans.next(), None);
+        assert_eq!(spans.next(), None);
+    }
+}
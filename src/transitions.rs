@@ -0,0 +1,114 @@
+use crate::{ByKey, SpansBy};
+use core::iter::FusedIterator;
+
+impl<I, K, C, F> SpansBy<I, ByKey<K, C, F>>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    /// Converts `self` into an iterator over the pair of keys at each boundary between two spans.
+    ///
+    /// The returned [`KeyTransitions`] yields the key of the last item of a span and the key of the first item of the following span, which are the keys that were found not to be connected.
+    /// The items themselves are discarded.
+    /// If the current span has not been exhausted, the transition at its end is included.
+    /// The keys are not made again; to keep the key of the last item of each span, each key is cloned once more.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![1, 2, 5, 6, 7, 11];
+    /// let jumps: Vec<(i32, i32)> = vec
+    ///     .iter()
+    ///     .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+    ///     .key_transitions()
+    ///     .collect();
+    ///
+    /// assert_eq!(jumps, vec![(2, 5), (7, 11)]);
+    /// ```
+    pub fn key_transitions(mut self) -> KeyTransitions<I, K, C, F> {
+        self.connector.is_tracking_last_key = true;
+        let is_in_span = self.is_connected;
+        KeyTransitions {
+            spans: self,
+            is_in_span,
+        }
+    }
+}
+
+/// `KeyTransitions` is an iterator over the pair of keys at each boundary between two spans of a `SpansBy`.
+///
+/// See [`SpansBy::key_transitions`] for more information.
+pub struct KeyTransitions<I: Iterator, K, C, F> {
+    /// The wrapped spans.
+    spans: SpansBy<I, ByKey<K, C, F>>,
+    /// Whether a span has been started whose end is the next transition.
+    is_in_span: bool,
+}
+
+impl<I, K, C, F> Iterator for KeyTransitions<I, K, C, F>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    type Item = (C, C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.is_in_span {
+            if !self.spans.skip_span() {
+                return None;
+            }
+            self.is_in_span = true;
+        }
+
+        let next_key = self.spans.next()?.key().clone();
+
+        // The last comparison was the one that ended the previous span, with the key of its last item as `prev`.
+        let prev_key = self.spans.connector.last_key.clone()?;
+        Some((prev_key, next_key))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // The first remaining span may not end in a transition.
+        let (_, upper) = self.spans.span_count_hint();
+        (0, upper)
+    }
+}
+
+impl<I, K, C, F> FusedIterator for KeyTransitions<I, K, C, F>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> C,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    #[test]
+    fn test_single_span() {
+        let mut transitions = [1, 2, 3]
+            .iter()
+            .spans_by_key(|&&x| x, |a, b| a + 1 == b)
+            .key_transitions();
+        assert_eq!(transitions.next(), None);
+        assert_eq!(transitions.next(), None);
+    }
+
+    #[test]
+    fn test_partially_consumed_span() {
+        let vec = [1, 2, 4, 8];
+        let mut spans = vec.iter().spans_by_key(|&&x| x * 10, |a, b| a + 10 == b);
+        assert_eq!(spans.next().unwrap().next(), Some(&1));
+        let transitions = spans.key_transitions().collect::<Vec<_>>();
+        assert_eq!(transitions, vec![(20, 40), (40, 80)]);
+    }
+}
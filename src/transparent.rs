@@ -0,0 +1,192 @@
+use crate::{Connector, SpansBy};
+
+impl<I, F> SpansBy<I, F>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    /// Makes the items for which `is_transparent` returns `true` transparent to the connector.
+    ///
+    /// A transparent item is always part of the span of the previous item and is never passed to the connector.
+    /// Instead, the connector compares the item following transparent items with the last item before them.
+    /// Transparent items at the start of the iterator are part of the first span.
+    /// The connector still observes every pair of adjacent items that are not transparent, in iteration order.
+    ///
+    /// The last item before transparent items is cloned to compare it later.
+    /// `is_transparent` is called once with each item.
+    /// Transparency applies from the next span on; it should be set before iterating over any spans.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let lines = vec!["a1", "# note", "a2", "b1", "", "b2"];
+    /// let spans = lines
+    ///     .into_iter()
+    ///     .spans_by(|a, b| a[..1] == b[..1])
+    ///     .with_transparent(|line| line.is_empty() || line.starts_with('#'))
+    ///     .map_spans(|span| span.collect::<Vec<_>>())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(spans, vec![vec!["a1", "# note", "a2"], vec!["b1", "", "b2"]]);
+    /// ```
+    pub fn with_transparent<P>(self, is_transparent: P) -> SpansBy<I, Transparent<F, P, I::Item>>
+    where
+        P: FnMut(&I::Item) -> bool,
+    {
+        SpansBy {
+            iter: self.iter,
+            connector: Transparent {
+                connector: self.connector,
+                is_transparent,
+                is_prev_transparent: None,
+                last_opaque: None,
+            },
+            is_connected: self.is_connected,
+            is_done: self.is_done,
+            span_count: self.span_count,
+            position: self.position,
+            span_start: self.span_start,
+        }
+    }
+}
+
+/// `Transparent` is a [`Connector`] that skips transparent items when connecting items using another connector.
+///
+/// See [`SpansBy::with_transparent`] for more information.
+#[derive(Clone)]
+pub struct Transparent<F, P, T> {
+    /// The wrapped connector.
+    connector: F,
+    /// Returns whether an item is transparent.
+    is_transparent: P,
+    /// Whether the item most recently passed as `next` is transparent.
+    ///
+    /// `None` before the first comparison.
+    is_prev_transparent: Option<bool>,
+    /// The last item that is not transparent, if it is followed by transparent items.
+    last_opaque: Option<T>,
+}
+
+impl<T, F, P> Connector<T> for Transparent<F, P, T>
+where
+    T: Clone,
+    F: Connector<T>,
+    P: FnMut(&T) -> bool,
+{
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        let is_prev_transparent = match self.is_prev_transparent {
+            Some(is_prev_transparent) => is_prev_transparent,
+            None => (self.is_transparent)(prev),
+        };
+        let is_next_transparent = (self.is_transparent)(next);
+        self.is_prev_transparent = Some(is_next_transparent);
+
+        match (is_prev_transparent, is_next_transparent) {
+            (_, true) => {
+                if !is_prev_transparent {
+                    self.last_opaque = Some(prev.clone());
+                }
+                true
+            }
+            (false, false) => self.connector.are_connected(prev, next),
+            (true, false) => match self.last_opaque.take() {
+                Some(last_opaque) => self.connector.are_connected(&last_opaque, next),
+                None => {
+                    // Only transparent items precede `next` in its span, such that the wrapped connector sees the span start with `next`.
+                    self.connector.start_span(next);
+                    true
+                }
+            },
+        }
+    }
+
+    fn start_span(&mut self, first: &T) {
+        // The first item of a span was passed as `next` before, unless it is the first item of the iterator.
+        let is_first_transparent = match self.is_prev_transparent {
+            Some(is_first_transparent) => is_first_transparent,
+            None => {
+                let is_first_transparent = (self.is_transparent)(first);
+                self.is_prev_transparent = Some(is_first_transparent);
+                is_first_transparent
+            }
+        };
+
+        if !is_first_transparent {
+            self.connector.start_span(first);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.connector.reset();
+        self.is_prev_transparent = None;
+        self.last_opaque = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Connector, Spans, SpansBy};
+
+    fn spans(s: &str) -> Vec<String> {
+        s.chars()
+            .spans_by_key_change(|c| c.to_ascii_lowercase())
+            .with_transparent(|&c| c == '.')
+            .map_spans(|span| span.collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_transparent_items() {
+        assert_eq!(spans("a.A..b.a"), vec!["a.A..", "b.", "a"]);
+    }
+
+    #[test]
+    fn test_leading_transparent_items() {
+        assert_eq!(spans("..ab"), vec!["..a", "b"]);
+        assert_eq!(spans("..."), vec!["..."]);
+    }
+
+    #[test]
+    fn test_key_cache_skips_transparent_items() {
+        let mut keys = Vec::new();
+        let spans = "a.b"
+            .chars()
+            .spans_by_key(
+                |&c| {
+                    keys.push(c);
+                    c
+                },
+                |a, b| a != b,
+            )
+            .with_transparent(|&c| c == '.')
+            .map_spans(|span| span.count())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![3]);
+        assert_eq!(keys, vec!['a', 'b']);
+    }
+
+    /// A connector of equal items that records the first item of each span.
+    struct RecordingStarts(Vec<char>);
+
+    impl Connector<char> for RecordingStarts {
+        fn are_connected(&mut self, prev: &char, next: &char) -> bool {
+            prev == next
+        }
+
+        fn start_span(&mut self, first: &char) {
+            self.0.push(*first);
+        }
+    }
+
+    #[test]
+    fn test_span_starts_are_forwarded() {
+        let mut spans = SpansBy::new("..aa.b".chars(), RecordingStarts(Vec::new()))
+            .with_transparent(|&c| c == '.');
+
+        while spans.skip_span() {}
+
+        assert_eq!(spans.connector.connector.0, vec!['a', 'b']);
+    }
+}
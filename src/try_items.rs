@@ -0,0 +1,165 @@
+use crate::{Connector, Span, SpansBy};
+use core::iter::FusedIterator;
+
+/// `ByOk` is a [`Connector`] that compares the values of successful items and never connects errors.
+///
+/// See [`Spans::try_spans_by`][crate::Spans::try_spans_by] for more information.
+#[derive(Clone)]
+pub struct ByOk<F> {
+    /// Whether two values belong to the same span.
+    are_connected: F,
+}
+
+impl<T, E, F> Connector<Result<T, E>> for ByOk<F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    fn are_connected(&mut self, prev: &Result<T, E>, next: &Result<T, E>) -> bool {
+        match (prev, next) {
+            (Ok(prev), Ok(next)) => (self.are_connected)(prev, next),
+            _ => false,
+        }
+    }
+}
+
+/// `TrySpans` wraps an iterator of results and provides progressive access to the contiguous spans of its values and to its errors.
+///
+/// See [`Spans::try_spans_by`][crate::Spans::try_spans_by] for more information.
+pub struct TrySpans<I: Iterator, F> {
+    /// The spans of values and of single errors.
+    spans: SpansBy<I, ByOk<F>>,
+}
+
+impl<I: Iterator, F> TrySpans<I, F> {
+    /// Creates a new `TrySpans` comparing the values of `iter` with `are_connected`.
+    pub(crate) fn new(iter: I, are_connected: F) -> Self {
+        TrySpans {
+            spans: SpansBy::new(iter, ByOk { are_connected }),
+        }
+    }
+}
+
+impl<I, T, E, F> TrySpans<I, F>
+where
+    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(&T, &T) -> bool,
+{
+    /// Returns the next span of values, the next error, or `None` if the iterator terminated.
+    ///
+    /// An error ends the current span and is returned by the following call.
+    /// The call after that resumes with the item following the error.
+    /// Like [`SpansBy::next`], this skips the remaining values of the previous span if it was dropped before it was exhausted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let vec = vec![Ok(1), Ok(2), Err("e"), Ok(3)];
+    /// let mut spans = vec.into_iter().try_spans_by(|a, b| a + 1 == *b);
+    ///
+    /// assert_eq!(spans.next().unwrap().unwrap().collect::<Vec<_>>(), vec![1, 2]);
+    /// assert!(spans.next().unwrap().is_err());
+    /// assert_eq!(spans.next().unwrap().unwrap().collect::<Vec<_>>(), vec![3]);
+    /// assert!(spans.next().is_none());
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Result<TrySpan<'_, I, F>, E>> {
+        let spans = &mut self.spans;
+        spans.skip_current_span();
+
+        if spans.is_at_end() {
+            return None;
+        }
+
+        // Errors are consumed here, such that spans only ever contain values.
+        if let Some(Err(_)) = spans.iter.peek() {
+            spans.position += 1;
+            return spans.iter.next()?.err().map(Err);
+        }
+
+        spans.next().map(|span| Ok(TrySpan { span }))
+    }
+}
+
+/// `TrySpan` is an iterator over the values of a span of a [`TrySpans`].
+///
+/// See [`Spans::try_spans_by`][crate::Spans::try_spans_by] for more information.
+pub struct TrySpan<'a, I: Iterator, F> {
+    /// The span of results, which are all values.
+    span: Span<'a, I, ByOk<F>>,
+}
+
+impl<I, T, E, F> Iterator for TrySpan<'_, I, F>
+where
+    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(&T, &T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Errors are never connected to other items and are consumed by `TrySpans::next`, so every item is a value.
+        self.span.next()?.ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.span.size_hint()
+    }
+}
+
+impl<I, T, E, F> FusedIterator for TrySpan<'_, I, F>
+where
+    I: Iterator<Item = Result<T, E>>,
+    F: FnMut(&T, &T) -> bool,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    fn try_spans(items: Vec<Result<i32, char>>) -> Vec<Result<Vec<i32>, char>> {
+        let mut spans = items.into_iter().try_spans_by(|a, b| a + 1 == *b);
+        let mut result = Vec::new();
+
+        while let Some(span) = spans.next() {
+            result.push(span.map(|span| span.collect()));
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_errors_are_yielded_once() {
+        assert_eq!(
+            try_spans(vec![
+                Err('a'),
+                Ok(1),
+                Ok(2),
+                Err('b'),
+                Err('c'),
+                Ok(3),
+                Ok(5)
+            ]),
+            vec![
+                Err('a'),
+                Ok(vec![1, 2]),
+                Err('b'),
+                Err('c'),
+                Ok(vec![3]),
+                Ok(vec![5]),
+            ]
+        );
+        assert!(try_spans(vec![]).is_empty());
+    }
+
+    #[test]
+    fn test_dropped_spans_are_skipped() {
+        let items: Vec<Result<i32, char>> = vec![Ok(1), Ok(2), Err('e'), Ok(3)];
+        let mut spans = items.into_iter().try_spans_by(|a, b| a + 1 == *b);
+        assert_eq!(spans.next().unwrap().unwrap().next(), Some(1));
+        assert_eq!(spans.next().unwrap().err(), Some('e'));
+        assert_eq!(spans.next().unwrap().unwrap().collect::<Vec<_>>(), vec![3]);
+        assert!(spans.next().is_none());
+    }
+}
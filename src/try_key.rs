@@ -0,0 +1,187 @@
+use crate::{Connector, Span, SpansBy};
+
+/// `ByTryKey` is a [`Connector`] that compares items by their fallible keys.
+///
+/// An item whose key cannot be made is not connected to the preceding item, and the error is stored until it is returned by [`TrySpansBy::next`].
+///
+/// See [`Spans::try_spans_by_key`][crate::Spans::try_spans_by_key] for more information.
+#[derive(Clone)]
+pub struct ByTryKey<K, C, E, F> {
+    /// A function transforming an iterator item to a comparison key, or failing.
+    key: K,
+    /// Whether two iterator items belong to the same span as determined by their respective keys.
+    are_connected: F,
+    /// The key of the item most recently passed as `next` to `Connector::are_connected`.
+    ///
+    /// `None` before the first comparison.
+    prev_key: Option<C>,
+    /// The error of the first key that could not be made and has not been returned yet.
+    error: Option<E>,
+}
+
+impl<T, K, C, E, F> Connector<T> for ByTryKey<K, C, E, F>
+where
+    K: FnMut(&T) -> Result<C, E>,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    fn are_connected(&mut self, prev: &T, next: &T) -> bool {
+        // Items are compared pairwise in iteration order, so the stored key is the key of `prev`.
+        let keys = match self.prev_key.take() {
+            Some(prev_key) => (self.key)(next).map(|next_key| (prev_key, next_key)),
+            None => (self.key)(prev).and_then(|prev_key| Ok((prev_key, (self.key)(next)?))),
+        };
+
+        match keys {
+            Ok((prev_key, next_key)) => {
+                let is_connected = (self.are_connected)(prev_key, next_key.clone());
+                self.prev_key = Some(next_key);
+                is_connected
+            }
+            Err(error) => {
+                self.error = Some(error);
+                false
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.prev_key = None;
+    }
+}
+
+/// `TrySpansBy` wraps an iterator and provides progressive access to contiguous spans of the iterator, where making the key of an item may fail.
+///
+/// See [`Spans::try_spans_by_key`][crate::Spans::try_spans_by_key] for more information.
+pub struct TrySpansBy<I: Iterator, K, C, E, F> {
+    /// The spans of the items with keys.
+    spans: SpansBy<I, ByTryKey<K, C, E, F>>,
+    /// Whether an error has been returned.
+    is_failed: bool,
+}
+
+impl<I: Iterator, K, C, E, F> TrySpansBy<I, K, C, E, F> {
+    /// Creates a new `TrySpansBy` comparing the keys of the items of `iter`.
+    pub(crate) fn new(iter: I, key: K, are_connected: F) -> Self {
+        TrySpansBy {
+            spans: SpansBy::new(
+                iter,
+                ByTryKey {
+                    key,
+                    are_connected,
+                    prev_key: None,
+                    error: None,
+                },
+            ),
+            is_failed: false,
+        }
+    }
+}
+
+impl<I, K, C, E, F> TrySpansBy<I, K, C, E, F>
+where
+    I: Iterator,
+    K: FnMut(&I::Item) -> Result<C, E>,
+    C: Clone,
+    F: FnMut(C, C) -> bool,
+{
+    /// Returns the next span, the error of the first key that could not be made, or `None` if the iterator terminated.
+    ///
+    /// A span ends before the first item whose key cannot be made.
+    /// The next call then returns the error, and all calls after that return `None`.
+    /// The item whose key could not be made is not consumed.
+    /// Like [`SpansBy::next`], this skips the remaining items of the previous span if it was dropped before it was exhausted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::Spans;
+    ///
+    /// let mut spans = "1 2 x 3".split(' ').try_spans_by_key(|s| s.parse::<i32>(), |a, b| a + 1 == b);
+    ///
+    /// while let Some(span) = spans.next() {
+    ///     match span {
+    ///         Ok(span) => assert_eq!(span.collect::<Vec<_>>(), vec!["1", "2"]),
+    ///         Err(error) => assert_eq!(error.to_string(), "invalid digit found in string"),
+    ///     }
+    /// }
+    /// ```
+    #[allow(clippy::should_implement_trait, clippy::type_complexity)]
+    pub fn next(&mut self) -> Option<Result<Span<'_, I, ByTryKey<K, C, E, F>>, E>> {
+        let spans = &mut self.spans;
+        spans.skip_current_span();
+
+        if let Some(error) = spans.connector.error.take() {
+            self.is_failed = true;
+            return Some(Err(error));
+        }
+
+        if self.is_failed || spans.is_at_end() {
+            return None;
+        }
+
+        // The key of the first item is made before the span starts, such that an error is returned instead of the span.
+        if spans.connector.prev_key.is_none() {
+            let item = spans.iter.peek()?;
+
+            match (spans.connector.key)(item) {
+                Ok(key) => spans.connector.prev_key = Some(key),
+                Err(error) => {
+                    self.is_failed = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+
+        spans.next().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Spans;
+
+    fn try_spans(s: &str) -> Vec<Result<String, char>> {
+        let mut spans = s
+            .chars()
+            .try_spans_by_key(|&c| if c == '!' { Err(c) } else { Ok(c) }, |a, b| a == b);
+        let mut result = Vec::new();
+
+        while let Some(span) = spans.next() {
+            result.push(span.map(|span| span.collect()));
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_error_ends_iteration() {
+        assert_eq!(
+            try_spans("aab!bc"),
+            vec![Ok("aa".to_string()), Ok("b".to_string()), Err('!')]
+        );
+        assert_eq!(try_spans("!a"), vec![Err('!')]);
+        assert_eq!(
+            try_spans("ab"),
+            vec![Ok("a".to_string()), Ok("b".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_keys_are_made_once() {
+        let mut calls = 0;
+        let mut spans = [1, 2, 3, 5].iter().try_spans_by_key(
+            |&&x| {
+                calls += 1;
+                Ok::<_, ()>(x)
+            },
+            |a, b| a + 1 == b,
+        );
+
+        while let Some(span) = spans.next() {
+            assert!(span.is_ok());
+        }
+
+        assert_eq!(calls, 4);
+    }
+}
@@ -0,0 +1,378 @@
+//! Spans of grapheme clusters and words.
+//!
+//! This module requires the `unicode` feature, which segments text with the [`unicode_segmentation`] crate.
+//!
+//! Import the [`UnicodeSpans`] trait to split strings into string slices by their grapheme clusters or words.
+//! Unlike the spans of [`StrSpans`][crate::text::StrSpans], these spans never split a user-perceived character,
+//! such as a letter with a combining accent or an emoji sequence:
+//!
+//! ```
+//! use spans::unicode::UnicodeSpans;
+//!
+//! let runs: Vec<&str> = "ne\u{301}e\u{301}123"
+//!     .grapheme_spans_by_key_change(|g| g.chars().all(char::is_numeric))
+//!     .collect();
+//!
+//! assert_eq!(runs, vec!["ne\u{301}e\u{301}", "123"]);
+//! ```
+
+use crate::{are_equal, ByKey, ByKeyChange, Connector};
+use core::iter::{FusedIterator, Peekable};
+use unicode_segmentation::{GraphemeIndices, UWordBoundIndices, UnicodeSegmentation};
+
+/// `UnicodeSpans` provides span adapters for the grapheme clusters and words of string slices.
+pub trait UnicodeSpans {
+    /// Splits the string into contiguous string slices by comparing adjacent extended grapheme clusters.
+    ///
+    /// This is like [`Spans::spans_by`][crate::Spans::spans_by] for [`UnicodeSegmentation::graphemes`], except that each span is a string slice.
+    /// Spans always end at grapheme cluster boundaries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::unicode::UnicodeSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let mut spans = "aa\u{308}👍🏽👍🏽b".grapheme_spans_by(|a, b| a.len() == b.len());
+    ///
+    /// assert_eq!(spans.next()?, "a");
+    /// assert_eq!(spans.next()?, "a\u{308}");
+    /// assert_eq!(spans.next()?, "👍🏽👍🏽");
+    /// assert_eq!(spans.next()?, "b");
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn grapheme_spans_by<F>(&self, are_connected: F) -> GraphemeSpansBy<'_, F>
+    where
+        F: FnMut(&&str, &&str) -> bool;
+
+    /// Splits the string into contiguous string slices by comparing the keys of adjacent extended grapheme clusters.
+    ///
+    /// This is like [`Spans::spans_by_key`][crate::Spans::spans_by_key] for [`UnicodeSegmentation::graphemes`], except that each span is a string slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::unicode::UnicodeSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let mut spans = "e\u{301}e\u{301}ee".grapheme_spans_by_key(|g| g.len(), |a, b| a >= b);
+    ///
+    /// assert_eq!(spans.next()?, "e\u{301}e\u{301}ee");
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn grapheme_spans_by_key<K, C, F>(
+        &self,
+        key: K,
+        are_connected: F,
+    ) -> GraphemeSpansBy<'_, ByKey<K, C, F>>
+    where
+        K: FnMut(&&str) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool;
+
+    /// Splits the string into contiguous string slices of extended grapheme clusters with equal keys.
+    ///
+    /// This is like [`Spans::spans_by_key_change`][crate::Spans::spans_by_key_change] for [`UnicodeSegmentation::graphemes`], except that each span is a string slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::unicode::UnicodeSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let mut spans = "🇩🇪🇫🇷 ok".grapheme_spans_by_key_change(|g| g.is_ascii());
+    ///
+    /// assert_eq!(spans.next()?, "🇩🇪🇫🇷");
+    /// assert_eq!(spans.next()?, " ok");
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn grapheme_spans_by_key_change<K, C>(&self, key: K) -> GraphemeSpansBy<'_, ByKeyChange<K, C>>
+    where
+        K: FnMut(&&str) -> C,
+        C: Clone + PartialEq;
+
+    /// Splits the string into contiguous string slices by comparing adjacent word-bounded segments.
+    ///
+    /// The segments are the words and the punctuation and whitespace between them, as returned by [`UnicodeSegmentation::split_word_bounds`].
+    /// Spans always end at word boundaries.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::unicode::UnicodeSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let mut spans = "can't stop".word_spans_by(|a, b| a.len() > 1 && b.len() > 1);
+    ///
+    /// assert_eq!(spans.next()?, "can't");
+    /// assert_eq!(spans.next()?, " ");
+    /// assert_eq!(spans.next()?, "stop");
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn word_spans_by<F>(&self, are_connected: F) -> WordSpansBy<'_, F>
+    where
+        F: FnMut(&&str, &&str) -> bool;
+
+    /// Splits the string into contiguous string slices by comparing the keys of adjacent word-bounded segments.
+    ///
+    /// See [`word_spans_by`][UnicodeSpans::word_spans_by] for the segments that are compared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::unicode::UnicodeSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let mut spans = "a bb ccc".word_spans_by_key(|w| w.len(), |a, b| a <= b);
+    ///
+    /// assert_eq!(spans.next()?, "a bb");
+    /// assert_eq!(spans.next()?, " ccc");
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn word_spans_by_key<K, C, F>(
+        &self,
+        key: K,
+        are_connected: F,
+    ) -> WordSpansBy<'_, ByKey<K, C, F>>
+    where
+        K: FnMut(&&str) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool;
+
+    /// Splits the string into contiguous string slices of word-bounded segments with equal keys.
+    ///
+    /// See [`word_spans_by`][UnicodeSpans::word_spans_by] for the segments that are compared.
+    /// The key can be thought of as a word class, such as whether the segment is alphanumeric.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use spans::unicode::UnicodeSpans;
+    /// # fn test() -> Option<()> {
+    ///
+    /// let mut spans = "Hello, world!".word_spans_by_key_change(|w| w.chars().all(char::is_alphanumeric));
+    ///
+    /// assert_eq!(spans.next()?, "Hello");
+    /// assert_eq!(spans.next()?, ", ");
+    /// assert_eq!(spans.next()?, "world");
+    /// assert_eq!(spans.next()?, "!");
+    /// assert!(spans.next().is_none());
+    /// # Some(())
+    /// # }
+    /// # fn main() { assert_eq!(test(), Some(())) }
+    /// ```
+    fn word_spans_by_key_change<K, C>(&self, key: K) -> WordSpansBy<'_, ByKeyChange<K, C>>
+    where
+        K: FnMut(&&str) -> C,
+        C: Clone + PartialEq;
+}
+
+impl UnicodeSpans for str {
+    fn grapheme_spans_by<F>(&self, are_connected: F) -> GraphemeSpansBy<'_, F>
+    where
+        F: FnMut(&&str, &&str) -> bool,
+    {
+        GraphemeSpansBy {
+            text: self,
+            graphemes: self.grapheme_indices(true).peekable(),
+            connector: are_connected,
+        }
+    }
+
+    fn grapheme_spans_by_key<K, C, F>(
+        &self,
+        key: K,
+        are_connected: F,
+    ) -> GraphemeSpansBy<'_, ByKey<K, C, F>>
+    where
+        K: FnMut(&&str) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+    {
+        GraphemeSpansBy {
+            text: self,
+            graphemes: self.grapheme_indices(true).peekable(),
+            connector: ByKey::new(key, are_connected),
+        }
+    }
+
+    fn grapheme_spans_by_key_change<K, C>(&self, key: K) -> GraphemeSpansBy<'_, ByKeyChange<K, C>>
+    where
+        K: FnMut(&&str) -> C,
+        C: Clone + PartialEq,
+    {
+        self.grapheme_spans_by_key(key, are_equal)
+    }
+
+    fn word_spans_by<F>(&self, are_connected: F) -> WordSpansBy<'_, F>
+    where
+        F: FnMut(&&str, &&str) -> bool,
+    {
+        WordSpansBy {
+            text: self,
+            words: self.split_word_bound_indices().peekable(),
+            connector: are_connected,
+        }
+    }
+
+    fn word_spans_by_key<K, C, F>(
+        &self,
+        key: K,
+        are_connected: F,
+    ) -> WordSpansBy<'_, ByKey<K, C, F>>
+    where
+        K: FnMut(&&str) -> C,
+        C: Clone,
+        F: FnMut(C, C) -> bool,
+    {
+        WordSpansBy {
+            text: self,
+            words: self.split_word_bound_indices().peekable(),
+            connector: ByKey::new(key, are_connected),
+        }
+    }
+
+    fn word_spans_by_key_change<K, C>(&self, key: K) -> WordSpansBy<'_, ByKeyChange<K, C>>
+    where
+        K: FnMut(&&str) -> C,
+        C: Clone + PartialEq,
+    {
+        self.word_spans_by_key(key, are_equal)
+    }
+}
+
+/// Returns the next span of `segments` as a slice of `text`.
+///
+/// The segments are the byte offsets and string slices of consecutive segments of `text`.
+fn next_span<'a, I, F>(
+    text: &'a str,
+    segments: &mut Peekable<I>,
+    connector: &mut F,
+) -> Option<&'a str>
+where
+    I: Iterator<Item = (usize, &'a str)>,
+    F: Connector<&'a str>,
+{
+    let (start, mut prev) = segments.next()?;
+    connector.start_span(&prev);
+
+    while let Some(&(_, next)) = segments.peek() {
+        if !connector.are_connected(&prev, &next) {
+            break;
+        }
+
+        prev = next;
+        segments.next();
+    }
+
+    let end = prev.as_ptr() as usize - text.as_ptr() as usize + prev.len();
+    Some(&text[start..end])
+}
+
+/// `GraphemeSpansBy` is an iterator over the spans of the grapheme clusters of a string as string slices.
+///
+/// See [`UnicodeSpans::grapheme_spans_by`] and [`UnicodeSpans::grapheme_spans_by_key`] for more information.
+#[derive(Clone)]
+pub struct GraphemeSpansBy<'a, F> {
+    /// The whole text.
+    text: &'a str,
+    /// The grapheme clusters that are not part of any returned span yet.
+    graphemes: Peekable<GraphemeIndices<'a>>,
+    /// Whether two adjacent grapheme clusters belong to the same span.
+    connector: F,
+}
+
+impl<'a, F> Iterator for GraphemeSpansBy<'a, F>
+where
+    F: Connector<&'a str>,
+{
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_span(self.text, &mut self.graphemes, &mut self.connector)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.graphemes.size_hint();
+        (lower.min(1), upper)
+    }
+}
+
+impl<'a, F> FusedIterator for GraphemeSpansBy<'a, F> where F: Connector<&'a str> {}
+
+/// `WordSpansBy` is an iterator over the spans of the word-bounded segments of a string as string slices.
+///
+/// See [`UnicodeSpans::word_spans_by`] and [`UnicodeSpans::word_spans_by_key`] for more information.
+#[derive(Clone)]
+pub struct WordSpansBy<'a, F> {
+    /// The whole text.
+    text: &'a str,
+    /// The word-bounded segments that are not part of any returned span yet.
+    words: Peekable<UWordBoundIndices<'a>>,
+    /// Whether two adjacent segments belong to the same span.
+    connector: F,
+}
+
+impl<'a, F> Iterator for WordSpansBy<'a, F>
+where
+    F: Connector<&'a str>,
+{
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_span(self.text, &mut self.words, &mut self.connector)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.words.size_hint();
+        (lower.min(1), upper)
+    }
+}
+
+impl<'a, F> FusedIterator for WordSpansBy<'a, F> where F: Connector<&'a str> {}
+
+#[cfg(test)]
+mod tests {
+    use super::UnicodeSpans;
+
+    #[test]
+    fn test_empty() {
+        assert!("".grapheme_spans_by(|a, b| a == b).next().is_none());
+        assert!("".word_spans_by(|a, b| a == b).next().is_none());
+    }
+
+    #[test]
+    fn test_cluster_boundaries() {
+        let text = "a\u{301}b👩‍👩‍👧🇯🇵c\r\n";
+        let spans = text
+            .grapheme_spans_by_key_change(|g| g.is_ascii())
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec!["a\u{301}", "b", "👩‍👩‍👧🇯🇵", "c\r\n"]);
+
+        let spans = text.grapheme_spans_by(|_, _| false).collect::<Vec<_>>();
+        assert_eq!(spans, vec!["a\u{301}", "b", "👩‍👩‍👧", "🇯🇵", "c", "\r\n"]);
+    }
+
+    #[test]
+    fn test_word_classes() {
+        let spans = "Grüße, 3.14  día!"
+            .word_spans_by_key_change(|w| w.chars().next().map(char::is_whitespace))
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec!["Grüße,", " ", "3.14", "  ", "día!"]);
+    }
+}